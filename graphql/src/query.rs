@@ -20,11 +20,16 @@ use itertools::{Itertools, Position};
 use log::info;
 
 use {
-    dailp::async_graphql::{self, dataloader::DataLoader, Context, FieldResult},
+    dailp::async_graphql::{
+        self,
+        connection::{Connection, CursorType, Edge, OpaqueCursor},
+        dataloader::DataLoader,
+        Context, ErrorExtensions, FieldResult,
+    },
     dailp::{
         AbstractMorphemeTag, AnnotatedDoc, AnnotatedFormUpdate, CherokeeOrthography, Database,
         EditedCollection, Menu, MenuUpdate, MorphemeId, MorphemeReference, MorphemeTag,
-        ParagraphUpdate, WordsInDocument,
+        OrthographyId, ParagraphUpdate, WordsInDocument,
     },
 };
 
@@ -82,16 +87,33 @@ impl Query {
             .await?)
     }
 
-    /// List of all the functional morpheme tags available
+    /// All registered writing systems, optionally only those for one language.
+    /// Their identifiers can be passed as the `orthography` argument of
+    /// `AnnotatedForm.romanizedSource` and `AnnotatedForm.segments`.
+    async fn orthographies(&self, language: Option<String>) -> Vec<dailp::RegisteredOrthography> {
+        dailp::OrthographyRegistry::global()
+            .all()
+            .filter(|(name, _)| {
+                language
+                    .as_ref()
+                    .is_none_or(|language| name.eq_ignore_ascii_case(language))
+            })
+            .map(|(name, o)| dailp::RegisteredOrthography::new(name, o))
+            .collect()
+    }
+
+    /// List of all the functional morpheme tags available in the language of
+    /// the given document, or in Cherokee without one
     async fn all_tags(
         &self,
         context: &Context<'_>,
         system: CherokeeOrthography,
+        document_id: Option<dailp::DocumentId>,
     ) -> FieldResult<Vec<MorphemeTag>> {
         Ok(context
             .data::<DataLoader<Database>>()?
             .loader()
-            .all_tags(system)
+            .all_tags(system.tag_system(), document_id)
             .await?)
     }
 
@@ -212,6 +234,277 @@ impl Query {
             .await?)
     }
 
+    /// Morpheme glosses linked to the given one, like "DF1975:catch", within
+    /// `depth` links, at most six, and the typed links between them. Nodes
+    /// are ordered by the date of their document, oldest first. Without a
+    /// document name, starts from the gloss in every document.
+    async fn etymology_graph(
+        &self,
+        context: &Context<'_>,
+        morpheme: String,
+        #[graphql(default = 3, validator(minimum = 0, maximum = 6))] depth: i32,
+    ) -> FieldResult<dailp::EtymologyGraph> {
+        let start = MorphemeId::parse(&morpheme)
+            .ok_or_else(|| anyhow::format_err!("Invalid morpheme: {}", morpheme))?;
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .etymology_graph(&start, depth)
+            .await?)
+    }
+
+    /// Browse dictionary entries in alphabetical order of their headwords.
+    /// Optionally only lists those in the dictionary with the given short
+    /// name, like "DF1975", with the given part of speech, or with headwords
+    /// starting with the given text.
+    async fn lexical_entries(
+        &self,
+        context: &Context<'_>,
+        dictionary: Option<String>,
+        part_of_speech: Option<dailp::PartOfSpeech>,
+        starts_with: Option<String>,
+        #[graphql(default = 0, validator(minimum = 0))] offset: i64,
+        #[graphql(default = 50, validator(minimum = 1, maximum = 500))] limit: i64,
+    ) -> FieldResult<Vec<dailp::LexicalEntry>> {
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .lexical_entries(
+                dictionary.as_deref(),
+                part_of_speech,
+                starts_with.as_deref(),
+                offset,
+                limit,
+            )
+            .await?)
+    }
+
+    /// The dictionary entry with the given ID, like "DF1975:catch"
+    async fn lexical_entry(
+        &self,
+        context: &Context<'_>,
+        id: String,
+    ) -> FieldResult<Option<dailp::LexicalEntry>> {
+        let id = MorphemeId::parse(&id)
+            .ok_or_else(|| anyhow::format_err!("Invalid lexical entry: {}", id))?;
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .lexical_entry(&id)
+            .await?)
+    }
+
+    /// Search dictionary entries by headword, gloss, shape, or English
+    /// translation. Exact matches of the headword or gloss come first.
+    async fn search_lexical_entries(
+        &self,
+        context: &Context<'_>,
+        query: String,
+        #[graphql(default = 20, validator(minimum = 1, maximum = 500))] limit: i64,
+    ) -> FieldResult<Vec<dailp::LexicalEntry>> {
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .search_lexical_entries(&query, limit)
+            .await?)
+    }
+
+    /// Lists every use of a word or morpheme in the paragraphs of documents
+    /// with up to `contextSize` words on either side from the same paragraph,
+    /// also known as keyword in context (KWIC). Words are matched by the gloss
+    /// of a morpheme, by how they sound, or by a piece of their syllabary.
+    /// Lines are sorted before skipping `offset` of them.
+    async fn concordance(
+        &self,
+        context: &Context<'_>,
+        search: dailp::ConcordanceQuery,
+        #[graphql(default = 5, validator(minimum = 0, maximum = 20))] context_size: i32,
+        #[graphql(default)] sort_by: dailp::ConcordanceSort,
+        #[graphql(default = 0, validator(minimum = 0))] offset: i64,
+        #[graphql(default = 100, validator(minimum = 0, maximum = 500))] limit: i64,
+    ) -> FieldResult<Vec<dailp::ConcordanceLine>> {
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .concordance(&search, context_size as usize, sort_by, offset, limit)
+            .await?)
+    }
+
+    /// Find Cherokee forms from English, like "catch" or "caught a fish".
+    /// Searches the senses of dictionary entries, the glosses of morphemes,
+    /// and the translations of words and paragraphs, matching any form of
+    /// each English word. Dictionary entries come first.
+    async fn search_by_english(
+        &self,
+        context: &Context<'_>,
+        query: String,
+        #[graphql(default = 20, validator(minimum = 1, maximum = 500))] limit: i64,
+    ) -> FieldResult<Vec<dailp::EnglishMatch>> {
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .search_by_english(&query, limit)
+            .await?)
+    }
+
+    /// Ranked full-text search over the syllabary, phonetics, gloss, and
+    /// commentary of every word, and the translation of every paragraph.
+    /// Supports quoted phrases, "or", and "-" to exclude a word. Pass the
+    /// cursor of the last result as `after` to get the next page.
+    async fn search(
+        &self,
+        context: &Context<'_>,
+        query: String,
+        after: Option<String>,
+        #[graphql(default = 20, validator(minimum = 1, maximum = 100))] first: i64,
+    ) -> FieldResult<Connection<OpaqueCursor<dailp::SearchCursor>, dailp::SearchHit>> {
+        let after = after
+            .map(|cursor| OpaqueCursor::<dailp::SearchCursor>::decode_cursor(&cursor))
+            .transpose()
+            .map_err(|_| anyhow::format_err!("Invalid search cursor"))?
+            .map(|cursor| cursor.0);
+        let mut hits = context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .full_text_search(&query, after, first + 1)
+            .await?;
+        let has_next_page = hits.len() as i64 > first;
+        hits.truncate(first as usize);
+        let mut connection = Connection::new(after.is_some(), has_next_page);
+        connection.edges.extend(
+            hits.into_iter()
+                .map(|hit| Edge::new(OpaqueCursor(hit.cursor), hit)),
+        );
+        Ok(connection)
+    }
+
+    /// Find words with a small query language, like
+    /// `gloss:catch doc:DF1975 year:1800..1850 has:audio -unresolved`.
+    /// Terms are separated by spaces and all must match. Plain text matches
+    /// any of the syllabary, phonetics, gloss, or commentary, while the
+    /// fields `source`, `phonetics`, `english`, `commentary`, `gloss`,
+    /// `morpheme`, `doc`, `collection`, `genre`, `keyword`, `year`, and `has`
+    /// (one of `audio`, `commentary`, `english`, or `segments`) each check one
+    /// thing. The bare keyword `unresolved` matches words with a "?" in a
+    /// morpheme or gloss, or in the source when there are no segments.
+    /// A leading "-" excludes words matching a term.
+    /// Syntax errors carry the `offset`, `length`, and `token` of the part of
+    /// the query that couldn't be read.
+    async fn advanced_search(
+        &self,
+        context: &Context<'_>,
+        query: String,
+        #[graphql(default = 0, validator(minimum = 0))] offset: i64,
+        #[graphql(default = 50, validator(minimum = 1, maximum = 500))] limit: i64,
+    ) -> FieldResult<Vec<dailp::AnnotatedForm>> {
+        let search = dailp::AdvancedSearch::parse(&query).map_err(|error| {
+            async_graphql::Error::new(error.to_string()).extend_with(|_, extensions| {
+                extensions.set("offset", error.offset as i32);
+                extensions.set("length", error.length() as i32);
+                extensions.set("token", error.token.clone());
+            })
+        })?;
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .advanced_search(&search, offset, limit)
+            .await?)
+    }
+
+    /// Clusters the shapes of a morpheme with the given gloss into likely
+    /// allomorphs, linking shapes that are at most `max_distance` consonant
+    /// clusters or vowels apart. Each cluster lists its variants and the
+    /// morphemes that most often come right before and after it.
+    async fn allomorphs(
+        &self,
+        context: &Context<'_>,
+        gloss: String,
+        #[graphql(desc = "Compare morpheme shapes in this orthography.
+                          Choosing a system without tone, like the learner orthography, merges shapes that only differ by tone.
+                         ")]
+        compare_by: Option<CherokeeOrthography>,
+        #[graphql(default = 1)] max_distance: usize,
+        #[graphql(default = 5)] example_count: usize,
+    ) -> FieldResult<Vec<dailp::AllomorphCluster>> {
+        let id = MorphemeId::parse(&gloss)
+            .ok_or_else(|| anyhow::format_err!("Invalid morpheme gloss: {}", gloss))?;
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .allomorphs(id, compare_by, max_distance, example_count)
+            .await?)
+    }
+
+    /// Conjugation table of a verb, with attested forms arranged by person,
+    /// number, and aspect. Takes either a root gloss like "catch", which
+    /// gathers forms from every document, or a lexical entry like
+    /// "DF1975:catch", which only uses the forms listed under that entry.
+    async fn verb_paradigm(
+        &self,
+        context: &Context<'_>,
+        root: String,
+    ) -> FieldResult<dailp::VerbParadigm> {
+        let root = MorphemeId::parse(&root)
+            .ok_or_else(|| anyhow::format_err!("Invalid root or lexical entry: {}", root))?;
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .verb_paradigm(root)
+            .await?)
+    }
+
+    /// Other morphemes that appear in the same words as the one with the given
+    /// internal gloss, most common first. For example, the prefixes that
+    /// appear with a root are those before it with the affix class.
+    async fn morpheme_cooccurrences(
+        &self,
+        context: &Context<'_>,
+        gloss: String,
+        #[graphql(default)] side: dailp::MorphemeSide,
+        class: Option<dailp::WordSegmentClass>,
+        #[graphql(default = 5)] example_count: i32,
+    ) -> FieldResult<Vec<dailp::MorphemeCooccurrence>> {
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .morpheme_cooccurrences(&gloss, side, class, example_count)
+            .await?)
+    }
+
+    /// How often the morpheme with the given internal gloss appears in each
+    /// document or decade, compared to all words in that group.
+    async fn morpheme_distribution(
+        &self,
+        context: &Context<'_>,
+        gloss: String,
+        #[graphql(default)] group_by: dailp::DistributionGrouping,
+        #[graphql(default = 5)] example_count: i32,
+    ) -> FieldResult<Vec<dailp::MorphemeDistributionBucket>> {
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .morpheme_distribution(&gloss, group_by, example_count)
+            .await?)
+    }
+
+    /// Functional morphemes that never appear in the same word as the one with
+    /// the given internal gloss, ignoring those attested in fewer than
+    /// `min_word_count` words. Fails if no word has the given gloss, which is
+    /// most likely misspelled.
+    async fn exclusive_morphemes(
+        &self,
+        context: &Context<'_>,
+        gloss: String,
+        #[graphql(default = 5)] min_word_count: i64,
+        #[graphql(default = 5)] example_count: i32,
+    ) -> FieldResult<Vec<dailp::MorphemeExclusion>> {
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .exclusive_morphemes(&gloss, min_word_count, example_count)
+            .await?)
+    }
+
     /// Lists all words containing a morpheme with the given gloss.
     /// Groups these words by the document containing them.
     async fn morphemes_by_document(
@@ -298,16 +591,18 @@ impl Query {
 
     /// Retrieve information for the morpheme that corresponds to the given tag
     /// string. For example, "3PL.B" is the standard string referring to a 3rd
-    /// person plural prefix.
+    /// person plural prefix. Tags are looked up in the language of the given
+    /// document, or in Cherokee without one.
     async fn morpheme_tag(
         &self,
         context: &Context<'_>,
         id: String,
         system: CherokeeOrthography,
+        document_id: Option<dailp::DocumentId>,
     ) -> FieldResult<Option<MorphemeTag>> {
         Ok(context
             .data::<DataLoader<Database>>()?
-            .load_one(dailp::TagId(id, system))
+            .load_one(dailp::TagId(id, system.tag_system(), document_id))
             .await?
             .unwrap_or_default()
             .into_iter()
@@ -315,19 +610,70 @@ impl Query {
     }
 
     /// Search for words that match any one of the given queries.
-    /// Each query may match against multiple fields of a word.
+    /// Each query may match against multiple fields of a word, or only against
+    /// how the word sounds with `mode: PHONETIC`.
+    /// Use `search` instead for ranked results a page at a time.
     async fn word_search(
         &self,
         context: &Context<'_>,
         query: String,
+        #[graphql(default)] mode: WordSearchMode,
     ) -> FieldResult<Vec<dailp::AnnotatedForm>> {
+        let db = context.data::<DataLoader<Database>>()?.loader();
+        Ok(match mode {
+            WordSearchMode::AnyField => db.search_words_any_field(query).await?,
+            WordSearchMode::Phonetic => db.search_words_by_key(&query).await?,
+        })
+    }
+
+    /// Check a morphemic segmentation and its glosses without saving them,
+    /// so that editors can fix unknown tags and mismatched segments first.
+    /// Both layers are written in the given system, and tags are checked
+    /// against those of the given document's language.
+    async fn gloss_warnings(
+        &self,
+        context: &Context<'_>,
+        morphemes: String,
+        glosses: String,
+        #[graphql(default_with = "CherokeeOrthography::Taoc.into()")] system: OrthographyId,
+        document_id: Option<dailp::DocumentId>,
+    ) -> FieldResult<Vec<dailp::GlossWarning>> {
         Ok(context
             .data::<DataLoader<Database>>()?
             .loader()
-            .search_words_any_field(query)
+            .gloss_warnings(&morphemes, &glosses, &system, document_id)
             .await?)
     }
 
+    /// Suggest morphemic segmentations for a word that hasn't been analyzed
+    /// yet, based on words and morpheme shapes that have been. Suggestions
+    /// copied from words that sound or look the same come first.
+    /// Morphemes are written in the given system, which is also what the
+    /// romanized source should be written in. Without a romanized source,
+    /// the source is read as syllabary.
+    async fn suggest_segmentations(
+        &self,
+        context: &Context<'_>,
+        source: String,
+        romanized_source: Option<String>,
+        #[graphql(default_with = "CherokeeOrthography::Taoc.into()")] system: OrthographyId,
+        #[graphql(default = 5)] limit: i32,
+    ) -> FieldResult<Vec<dailp::SegmentationSuggestion>> {
+        let (_, orthography) = system.resolve()?;
+        let phonetic = romanized_source
+            .map(|romanized| orthography.parse(&romanized))
+            .transpose()?;
+        let mut suggestions = context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .suggest_segmentations(&source, phonetic.as_deref(), limit.max(1) as usize)
+            .await?;
+        for segment in suggestions.iter_mut().flat_map(|s| &mut s.segments) {
+            segment.morpheme = orthography.render(&segment.morpheme);
+        }
+        Ok(suggestions)
+    }
+
     /// Get a single word given the word ID
     async fn word_by_id(
         &self,
@@ -354,17 +700,20 @@ impl Query {
             .await?)
     }
 
-    /// Search for words with the exact same syllabary string, or with very
-    /// similar looking characters.
+    /// Search for words containing the given syllabary string, allowing for
+    /// similar looking characters and a few missing or extra characters.
+    /// Results are ranked with the closest matches first, and include words
+    /// spelled like at most `limit` of the closest matches.
     async fn syllabary_search(
         &self,
         context: &Context<'_>,
         query: String,
+        #[graphql(default = 50, validator(minimum = 1, maximum = 200))] limit: i64,
     ) -> FieldResult<Vec<dailp::AnnotatedForm>> {
         Ok(context
             .data::<DataLoader<Database>>()?
             .loader()
-            .potential_syllabary_matches(&query)
+            .potential_syllabary_matches(&query, limit as usize)
             .await?)
     }
 
@@ -396,11 +745,12 @@ impl Query {
         &self,
         context: &Context<'_>,
         short_name: String,
+        document_id: Option<dailp::DocumentId>,
     ) -> FieldResult<Uuid> {
         Ok(context
             .data::<DataLoader<Database>>()?
             .loader()
-            .abbreviation_id_from_short_name(&short_name)
+            .abbreviation_id_from_short_name(&short_name, document_id)
             .await?)
     }
 
@@ -669,6 +1019,44 @@ impl Mutation {
             .ok_or_else(|| anyhow::format_err!("Failed to load document"))?)
     }
 
+    /// Link one morpheme gloss to another, like "WJ46:catch" to
+    /// "DF1975:catch". Replaces the kind of any existing link between them.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn add_morpheme_relation(
+        &self,
+        context: &Context<'_>,
+        from: String,
+        to: String,
+        #[graphql(default)] kind: dailp::MorphemeRelationKind,
+    ) -> FieldResult<bool> {
+        let link = dailp::LexicalConnection::parse(&from, &to)
+            .ok_or_else(|| anyhow::format_err!("Invalid link from {} to {}", from, to))?;
+        context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .insert_morpheme_relation(dailp::LexicalConnection { kind, ..link })
+            .await?;
+        Ok(true)
+    }
+
+    /// Remove the link from one morpheme gloss to another. Returns whether
+    /// there was a link to remove.
+    #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
+    async fn remove_morpheme_relation(
+        &self,
+        context: &Context<'_>,
+        from: String,
+        to: String,
+    ) -> FieldResult<bool> {
+        let link = dailp::LexicalConnection::parse(&from, &to)
+            .ok_or_else(|| anyhow::format_err!("Invalid link from {} to {}", from, to))?;
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .delete_morpheme_relation(link)
+            .await?)
+    }
+
     /// Decide if a piece of word audio should be included in edited collection
     #[graphql(guard = "GroupGuard::new(UserGroup::Editors)")]
     async fn curate_word_audio(
@@ -919,22 +1307,28 @@ impl Mutation {
     #[graphql(
         guard = "GroupGuard::new(UserGroup::Contributors).or(GroupGuard::new(UserGroup::Editors))"
     )]
+    /// Add a custom tag to the language of the given document, or to Cherokee
+    /// without one.
     async fn insert_custom_morpheme_tag(
         &self,
         context: &Context<'_>,
         tag: String,
         title: String,
         system: String,
+        document_id: Option<dailp::DocumentId>,
     ) -> FieldResult<bool> {
         //first get id of custom morpheme tag
         let abstract_id = context
             .data::<DataLoader<Database>>()?
             .loader()
-            .insert_custom_abstract_tag(AbstractMorphemeTag {
-                //TODO: can just make it CUS once we remove the unique constraint
-                id: "CUS:".to_string() + &title,
-                morpheme_type: "custom".to_string(),
-            })
+            .insert_custom_abstract_tag(
+                AbstractMorphemeTag {
+                    //TODO: can just make it CUS once we remove the unique constraint
+                    id: "CUS:".to_string() + &title,
+                    morpheme_type: "custom".to_string(),
+                },
+                document_id,
+            )
             .await?;
 
         //construct the morpheme tag
@@ -954,7 +1348,7 @@ impl Mutation {
         let system_id = context
             .data::<DataLoader<Database>>()?
             .loader()
-            .abbreviation_id_from_short_name("CUS")
+            .abbreviation_id_from_short_name("CUS", document_id)
             .await?;
 
         context
@@ -1121,6 +1515,17 @@ impl Mutation {
     }
 }
 
+/// How the query of a word search is matched against words.
+#[derive(async_graphql::Enum, Clone, Copy, PartialEq, Eq, Default)]
+enum WordSearchMode {
+    /// Match the query against the source, phonetics, and glosses of each word
+    #[default]
+    AnyField,
+    /// Match the query against how each word or morpheme sounds, ignoring
+    /// tone, vowel length, glottal stops, and d/t spelling
+    Phonetic,
+}
+
 #[derive(async_graphql::SimpleObject)]
 struct FormsInTime {
     start: Option<dailp::Date>,
@@ -139,11 +139,12 @@ async fn parse_early_vocab(
     let entries = sheet
         .values
         .into_iter()
+        .enumerate()
         // The first row is just a header.
         .skip(1)
-        .filter(move |row| row.len() >= (4 + meta.to_skip))
+        .filter(move |(_, row)| row.len() >= (4 + meta.to_skip))
         .enumerate()
-        .filter_map(|(index, row)| {
+        .filter_map(|(index, (row_index, row))| {
             let mut row = row.into_iter();
             let page_number = row.next()?;
             let id = row.next()?;
@@ -179,7 +180,21 @@ async fn parse_early_vocab(
                     row.next().filter(|s| !s.is_empty()),
                     row.next().filter(|s| !s.is_empty()),
                 ) {
-                    WordSegment::parse_many(&segs, &glosses)
+                    // A malformed segmentation only costs the form its segments.
+                    match WordSegment::parse_many(&segs, &glosses) {
+                        Ok(segments) => Some(segments),
+                        Err(e) => {
+                            eprintln!(
+                                "WARNING in document '{}', row {}: Malformed segmentation, skipping it: {}",
+                                doc_meta.short_name,
+                                row_index + 1,
+                                e
+                            );
+                            eprintln!("  Morphemic segmentation: {}", segs);
+                            eprintln!("  Morpheme gloss: {}", glosses);
+                            None
+                        }
+                    }
                 } else {
                     None
                 }
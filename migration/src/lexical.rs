@@ -2,7 +2,8 @@ use crate::spreadsheets::{LexicalEntryWithForms, SheetInterpretation};
 use anyhow::Result;
 use dailp::{
     convert_udb, seg_verb_surface_forms, AnnotatedForm, Contributor, Database, Date, DocumentId,
-    DocumentMetadata, LexicalConnection, MorphemeId, PositionInDocument, SheetResult, WordSegment,
+    DocumentMetadata, LexicalConnection, MorphemeId, NewLexicalEntry, PartOfSpeech,
+    PositionInDocument, SheetResult, WordSegment,
 };
 use itertools::Itertools;
 
@@ -298,9 +299,10 @@ async fn parse_appendix(db: &Database, sheet_id: &str, to_skip: usize) -> Result
     let forms = sheet
         .values
         .into_iter()
+        .enumerate()
         .skip(1)
-        .filter(|r| r.len() > 4 && !r[1].is_empty())
-        .filter_map(|row| {
+        .filter(|(_, r)| r.len() > 4 && !r[1].is_empty())
+        .filter_map(|(row_index, row)| {
             let mut values = row.into_iter();
             let index = values.next()?.parse().unwrap_or(1);
             let page_num = values.next()?;
@@ -314,7 +316,21 @@ async fn parse_appendix(db: &Database, sheet_id: &str, to_skip: usize) -> Result
             let phonemic = values.next();
             let morpheme_gloss = values.next()?;
             let morpheme_segments = values.next()?;
-            let segments = WordSegment::parse_many(&morpheme_segments, &morpheme_gloss)?;
+            // A malformed segmentation only costs the form its segments.
+            let segments = match WordSegment::parse_many(&morpheme_segments, &morpheme_gloss) {
+                Ok(segments) => Some(segments),
+                Err(e) => {
+                    eprintln!(
+                        "WARNING in document '{}', row {}: Malformed segmentation, skipping it: {}",
+                        meta.short_name,
+                        row_index + 1,
+                        e
+                    );
+                    eprintln!("  Morphemic segmentation: {}", morpheme_segments);
+                    eprintln!("  Morpheme gloss: {}", morpheme_gloss);
+                    None
+                }
+            };
             Some(AnnotatedForm {
                 id: None,
                 position,
@@ -323,7 +339,7 @@ async fn parse_appendix(db: &Database, sheet_id: &str, to_skip: usize) -> Result
                 simple_phonetics: None,
                 english_gloss: vec![translation],
                 phonemic,
-                segments: Some(segments),
+                segments,
                 line_break: None,
                 page_break: None,
                 commentary: None,
@@ -406,24 +422,27 @@ fn parse_new_df(
                     meta.has_numeric,
                     meta.has_comment,
                 ),
-                entry: AnnotatedForm {
-                    id: None,
-                    simple_phonetics: None,
-                    normalized_source: None,
-                    phonemic: None,
-                    commentary: None,
-                    line_break: None,
-                    page_break: None,
-                    english_gloss: glosses,
-                    segments: Some(vec![WordSegment::new(
-                        convert_udb(&root).into_dailp(),
-                        root_gloss.to_owned(),
-                        None,
-                    )]),
-                    date_recorded: Some(date),
-                    source: root,
-                    position: pos,
-                    ingested_audio_track: None,
+                entry: NewLexicalEntry {
+                    stem: AnnotatedForm {
+                        id: None,
+                        simple_phonetics: None,
+                        normalized_source: None,
+                        phonemic: None,
+                        commentary: None,
+                        line_break: None,
+                        page_break: None,
+                        english_gloss: glosses,
+                        segments: Some(vec![WordSegment::new(
+                            convert_udb(&root).into_dailp(),
+                            root_gloss.to_owned(),
+                            None,
+                        )]),
+                        date_recorded: Some(date),
+                        source: root,
+                        position: pos,
+                        ingested_audio_track: None,
+                    },
+                    part_of_speech: Some(PartOfSpeech::Verb),
                 },
             })
         })
@@ -0,0 +1,777 @@
+//! Exchange dictionaries with lexicon tools like FieldWorks and WeSay through
+//! LIFT, the Lexicon Interchange Format.
+
+use anyhow::Result;
+use dailp::{
+    AnnotatedForm, Database, Date, DocumentId, DocumentMetadata, LexicalConnection, LexicalEntry,
+    MorphemeId, MorphemeRelationKind, NewLexicalEntry, PartOfSpeech, PositionInDocument,
+    WordSegment,
+};
+use itertools::Itertools;
+use roxmltree::Node;
+use std::collections::HashMap;
+
+/// Language tag of text in Cherokee syllabary.
+const SYLLABARY: &str = "chr";
+/// Language tag of Cherokee text in any romanization, like headwords in DF1975.
+const ROMANIZED: &str = "chr-Latn";
+/// Language tag of Cherokee text in the DAILP internal orthography.
+const PHONEMIC: &str = "chr-Latn-x-dailp";
+/// Language tag of translations.
+const ENGLISH: &str = "en";
+
+/// Write the dictionary with the given short name to a LIFT file, including
+/// the forms listed under each entry and its links to other documents.
+pub async fn export_dictionary(db: &Database, short_name: &str, path: &str) -> Result<()> {
+    let document_id = db
+        .document_id_from_name(short_name)
+        .await?
+        .ok_or_else(|| anyhow::format_err!("No document named {}", short_name))?;
+    let entries = db
+        .lexical_entries(Some(short_name), None, None, 0, i64::MAX)
+        .await?;
+
+    // Each form goes under every entry whose gloss it contains, usually just
+    // the one for its root.
+    let mut forms: Vec<_> = db
+        .words_in_document(document_id, None, None)
+        .await?
+        .collect();
+    db.attach_segments(&mut forms).await?;
+    let mut forms_by_gloss = forms
+        .into_iter()
+        .flat_map(|form| {
+            let gloss_ids: Vec<_> = form
+                .segments
+                .iter()
+                .flatten()
+                .filter_map(|segment| segment.gloss_id)
+                .unique()
+                .collect();
+            gloss_ids.into_iter().map(move |id| (id, form.clone()))
+        })
+        .into_group_map();
+    let mut links_by_gloss = db
+        .document_morpheme_relations(short_name)
+        .await?
+        .into_iter()
+        .into_group_map_by(|link| link.left.gloss.clone());
+
+    let entries: Vec<_> = entries
+        .into_iter()
+        .map(|entry| {
+            let forms = forms_by_gloss.remove(&entry.gloss_id).unwrap_or_default();
+            let links = links_by_gloss.remove(&entry.gloss).unwrap_or_default();
+            LiftEntry::from_entry(entry, forms, links)
+        })
+        .collect();
+    println!(
+        "Writing {} entries of {} to {}...",
+        entries.len(),
+        short_name,
+        path
+    );
+    std::fs::write(path, write_lift(&entries))?;
+    Ok(())
+}
+
+/// Create a dictionary document from a LIFT file, replacing the contents of
+/// any dictionary with the same short name. Brings in each entry, the example
+/// forms under it, and its links to other entries in the file or to other
+/// documents, like "DF2003:catch". Homonyms that share a gloss are numbered
+/// apart, and the import fails if any entries still share one after that.
+pub async fn import_dictionary(
+    db: &Database,
+    path: &str,
+    short_name: &str,
+    title: &str,
+    year: Option<i32>,
+) -> Result<DocumentId> {
+    let mut entries = parse_lift(&std::fs::read_to_string(path)?)?;
+    number_homonyms(&mut entries);
+    let date = year.map(|year| Date::from_ymd(year, 1, 1));
+    let document_id = db
+        .insert_dictionary_document(&DocumentMetadata {
+            id: Default::default(),
+            short_name: short_name.to_owned(),
+            title: title.to_owned(),
+            sources: Vec::new(),
+            collection: Some("Lexical Resources".to_owned()),
+            contributors: None,
+            date: date.clone(),
+            genre_id: None,
+            keywords_ids: None,
+            languages_ids: None,
+            subject_headings_ids: None,
+            spatial_coverage_ids: None,
+            creators_ids: None,
+            format_id: None,
+            translation: None,
+            page_images: None,
+            is_reference: true,
+            audio_recording: None,
+            order_index: 0,
+        })
+        .await?;
+
+    let mut new_entries = Vec::new();
+    let mut forms = Vec::new();
+    let mut glosses = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let position = PositionInDocument::new(
+            document_id,
+            entry.page_number.clone().unwrap_or_default(),
+            index as i64 + 1,
+        );
+        let new_entry = entry.to_new_entry(position.clone(), date.clone());
+        if let Some(gloss) = new_entry.gloss() {
+            glosses.insert(&*entry.id, gloss);
+        }
+        forms.extend(
+            entry
+                .examples
+                .iter()
+                .map(|example| example.to_form(entry, position.clone(), date.clone())),
+        );
+        new_entries.push(new_entry);
+    }
+    // Entries are stored by gloss, so any that still share one would be
+    // merged together.
+    let collisions: Vec<_> = entries
+        .iter()
+        .zip(&new_entries)
+        .filter_map(|(entry, new_entry)| Some((new_entry.gloss()?, &*entry.id)))
+        .into_group_map()
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .map(|(gloss, ids)| format!("\"{}\" ({})", gloss, ids.join(", ")))
+        .sorted()
+        .collect();
+    if !collisions.is_empty() {
+        anyhow::bail!(
+            "Several entries have the same gloss: {}",
+            collisions.join("; ")
+        );
+    }
+
+    // Relations point at other entries by their ID within the file, or at
+    // glosses in other documents by morpheme ID.
+    let mut links = Vec::new();
+    for entry in &entries {
+        let from = match glosses.get(&*entry.id) {
+            Some(gloss) => MorphemeId::new(Some(short_name.to_owned()), None, gloss.clone()),
+            None => continue,
+        };
+        for relation in &entry.relations {
+            let to = match glosses.get(&*relation.target) {
+                Some(gloss) => Ok(MorphemeId::new(
+                    Some(short_name.to_owned()),
+                    None,
+                    gloss.clone(),
+                )),
+                None => MorphemeId::parse_reference(&relation.target),
+            };
+            match to {
+                Ok(to) => links.push(LexicalConnection::with_kind(
+                    from.clone(),
+                    to,
+                    relation.kind,
+                )),
+                Err(e) => eprintln!("WARNING in entry '{}': {}", entry.id, e),
+            }
+        }
+    }
+
+    println!(
+        "Pushing {} entries of {} to database...",
+        new_entries.len(),
+        short_name
+    );
+    db.insert_lexical_entries(document_id, new_entries, forms)
+        .await?;
+    db.insert_morpheme_relations(links).await?;
+    Ok(document_id)
+}
+
+/// One entry of a LIFT lexicon, halfway between XML and a dictionary entry.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct LiftEntry {
+    /// Identifier within the file, which relations refer to
+    id: String,
+    headword: String,
+    /// Shape of the stem in DAILP internal orthography
+    shape: Option<String>,
+    gloss: String,
+    /// Number telling apart entries with the same headword or gloss
+    homograph: Option<u32>,
+    part_of_speech: Option<PartOfSpeech>,
+    senses: Vec<String>,
+    page_number: Option<String>,
+    examples: Vec<LiftExample>,
+    relations: Vec<LiftRelation>,
+}
+
+/// An inflected form listed under an entry.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct LiftExample {
+    source: String,
+    simple_phonetics: Option<String>,
+    phonemic: Option<String>,
+    translation: Option<String>,
+    /// Morphemic segmentation, like "ji-hnoga"
+    morphemes: Option<String>,
+    /// Gloss of each morpheme, like "1SG.B-catch"
+    glosses: Option<String>,
+}
+
+/// A link from an entry to another one, in this file or another document.
+#[derive(Clone, Debug, PartialEq)]
+struct LiftRelation {
+    kind: MorphemeRelationKind,
+    target: String,
+}
+
+impl LiftEntry {
+    fn from_entry(
+        entry: LexicalEntry,
+        forms: Vec<AnnotatedForm>,
+        links: Vec<LexicalConnection>,
+    ) -> Self {
+        Self {
+            id: MorphemeId::new(Some(entry.source.short_name), None, entry.gloss.clone())
+                .to_string(),
+            headword: entry.headword,
+            shape: entry.shape,
+            gloss: entry.gloss,
+            homograph: None,
+            part_of_speech: entry.part_of_speech,
+            senses: entry.senses,
+            page_number: entry.page_number,
+            examples: forms
+                .into_iter()
+                .map(|form| LiftExample {
+                    morphemes: form.segments.as_ref().map(WordSegment::morpheme_layer),
+                    glosses: form.segments.as_ref().map(WordSegment::gloss_layer),
+                    source: form.source,
+                    simple_phonetics: form.simple_phonetics,
+                    phonemic: form.phonemic,
+                    translation: form.english_gloss.into_iter().next(),
+                })
+                .collect(),
+            relations: links
+                .into_iter()
+                .map(|link| LiftRelation {
+                    kind: link.kind,
+                    target: link.right.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn from_xml(node: Node) -> Self {
+        let senses: Vec<_> = children(node, "sense").collect();
+        let headword = child(node, "lexical-unit")
+            .and_then(first_form)
+            .unwrap_or_default();
+        let gloss = senses
+            .iter()
+            .flat_map(|sense| children(*sense, "gloss"))
+            .find_map(text)
+            .or_else(|| senses.iter().find_map(|sense| sense_text(*sense)))
+            .unwrap_or_else(|| headword.clone());
+        Self {
+            id: node
+                .attribute("id")
+                .or_else(|| node.attribute("guid"))
+                .unwrap_or_default()
+                .to_owned(),
+            shape: child(node, "pronunciation").and_then(|p| form_in(p, PHONEMIC)),
+            homograph: node.attribute("order").and_then(|order| order.parse().ok()),
+            part_of_speech: senses
+                .iter()
+                .filter_map(|sense| child(*sense, "grammatical-info"))
+                .find_map(|info| from_name(info.attribute("value")?)),
+            senses: senses
+                .iter()
+                .filter_map(|sense| sense_text(*sense))
+                .collect(),
+            page_number: children(node, "trait")
+                .find(|t| t.attribute("name") == Some("page-number"))
+                .and_then(|t| t.attribute("value"))
+                .map(str::to_owned),
+            examples: senses
+                .iter()
+                .flat_map(|sense| children(*sense, "example"))
+                .map(LiftExample::from_xml)
+                .collect(),
+            relations: children(node, "relation")
+                .chain(senses.iter().flat_map(|sense| children(*sense, "relation")))
+                .filter_map(|relation| {
+                    Some(LiftRelation {
+                        kind: relation
+                            .attribute("type")
+                            .and_then(from_name)
+                            .unwrap_or_default(),
+                        target: relation
+                            .attribute("ref")
+                            .filter(|r| !r.is_empty())?
+                            .to_owned(),
+                    })
+                })
+                .collect(),
+            headword,
+            gloss,
+        }
+    }
+
+    fn to_new_entry(&self, position: PositionInDocument, date: Option<Date>) -> NewLexicalEntry {
+        let shape = self.shape.clone().unwrap_or_else(|| self.headword.clone());
+        NewLexicalEntry {
+            stem: AnnotatedForm {
+                id: None,
+                source: self.headword.clone(),
+                normalized_source: None,
+                simple_phonetics: None,
+                phonemic: self.shape.clone(),
+                segments: Some(vec![WordSegment::new(shape, self.gloss.clone(), None)]),
+                english_gloss: self.senses.clone(),
+                commentary: None,
+                line_break: None,
+                page_break: None,
+                position,
+                date_recorded: date,
+                ingested_audio_track: None,
+            },
+            part_of_speech: self.part_of_speech,
+        }
+    }
+
+    fn write_xml(&self, out: &mut String) {
+        out.push_str(&format!("  <entry id=\"{}\">\n", escape(&self.id)));
+        out.push_str("    <lexical-unit>\n");
+        write_form(out, 6, cherokee_language(&self.headword), &self.headword);
+        out.push_str("    </lexical-unit>\n");
+        if let Some(shape) = &self.shape {
+            out.push_str("    <pronunciation>\n");
+            write_form(out, 6, PHONEMIC, shape);
+            out.push_str("    </pronunciation>\n");
+        }
+        if let Some(page) = &self.page_number {
+            out.push_str(&format!(
+                "    <trait name=\"page-number\" value=\"{}\"/>\n",
+                escape(page)
+            ));
+        }
+        for relation in &self.relations {
+            out.push_str(&format!(
+                "    <relation type=\"{:?}\" ref=\"{}\"/>\n",
+                relation.kind,
+                escape(&relation.target)
+            ));
+        }
+        // The gloss and examples go with the first sense, so there's always one.
+        let senses = if self.senses.is_empty() {
+            vec![None]
+        } else {
+            self.senses.iter().map(Some).collect()
+        };
+        for (index, sense) in senses.into_iter().enumerate() {
+            out.push_str("    <sense>\n");
+            if let Some(pos) = self.part_of_speech {
+                out.push_str(&format!("      <grammatical-info value=\"{:?}\"/>\n", pos));
+            }
+            if index == 0 {
+                out.push_str(&format!(
+                    "      <gloss lang=\"{}\"><text>{}</text></gloss>\n",
+                    ENGLISH,
+                    escape(&self.gloss)
+                ));
+            }
+            if let Some(sense) = sense {
+                out.push_str("      <definition>\n");
+                write_form(out, 8, ENGLISH, sense);
+                out.push_str("      </definition>\n");
+            }
+            if index == 0 {
+                for example in &self.examples {
+                    example.write_xml(out);
+                }
+            }
+            out.push_str("    </sense>\n");
+        }
+        out.push_str("  </entry>\n");
+    }
+}
+
+impl LiftExample {
+    fn from_xml(node: Node) -> Self {
+        let forms: Vec<_> = children(node, "form").collect();
+        let source = forms
+            .iter()
+            .position(|form| form.attribute("lang") != Some(PHONEMIC));
+        let field = |name: &str| {
+            children(node, "field")
+                .find(|field| field.attribute("type") == Some(name))
+                .and_then(first_form)
+        };
+        Self {
+            source: source.and_then(|idx| text(forms[idx])).unwrap_or_default(),
+            simple_phonetics: forms
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| Some(*idx) != source)
+                .find(|(_, form)| form.attribute("lang") == Some(ROMANIZED))
+                .and_then(|(_, form)| text(*form)),
+            phonemic: form_in(node, PHONEMIC),
+            translation: child(node, "translation").and_then(first_form),
+            morphemes: field("morphemes"),
+            glosses: field("glosses"),
+        }
+    }
+
+    fn to_form(
+        &self,
+        entry: &LiftEntry,
+        position: PositionInDocument,
+        date: Option<Date>,
+    ) -> AnnotatedForm {
+        // Without a segmentation, treat the whole form as the stem of its
+        // entry so that it stays listed there.
+        let segments = match (&self.morphemes, &self.glosses) {
+            (Some(morphemes), Some(glosses)) => match WordSegment::parse_many(morphemes, glosses) {
+                Ok(segments) => Some(segments),
+                Err(e) => {
+                    eprintln!(
+                        "WARNING in entry '{}': Malformed segmentation of example '{}', using the whole form instead: {}",
+                        entry.id, self.source, e
+                    );
+                    None
+                }
+            },
+            _ => None,
+        }
+        .unwrap_or_else(|| {
+            let shape = self.phonemic.as_ref().unwrap_or(&self.source);
+            vec![WordSegment::new(shape.clone(), entry.gloss.clone(), None)]
+        });
+        AnnotatedForm {
+            id: None,
+            source: self.source.clone(),
+            normalized_source: None,
+            simple_phonetics: self.simple_phonetics.clone(),
+            phonemic: self.phonemic.clone(),
+            segments: Some(segments),
+            english_gloss: self.translation.iter().cloned().collect(),
+            commentary: None,
+            line_break: None,
+            page_break: None,
+            position,
+            date_recorded: date,
+            ingested_audio_track: None,
+        }
+    }
+
+    fn write_xml(&self, out: &mut String) {
+        out.push_str("      <example>\n");
+        write_form(out, 8, cherokee_language(&self.source), &self.source);
+        if let Some(simple_phonetics) = &self.simple_phonetics {
+            write_form(out, 8, ROMANIZED, simple_phonetics);
+        }
+        if let Some(phonemic) = &self.phonemic {
+            write_form(out, 8, PHONEMIC, phonemic);
+        }
+        if let Some(translation) = &self.translation {
+            out.push_str("        <translation>\n");
+            write_form(out, 10, ENGLISH, translation);
+            out.push_str("        </translation>\n");
+        }
+        for (name, lang, value) in [
+            ("morphemes", PHONEMIC, &self.morphemes),
+            ("glosses", ENGLISH, &self.glosses),
+        ] {
+            if let Some(value) = value {
+                out.push_str(&format!("        <field type=\"{}\">\n", name));
+                write_form(out, 10, lang, value);
+                out.push_str("        </field>\n");
+            }
+        }
+        out.push_str("      </example>\n");
+    }
+}
+
+/// Give entries that share a gloss, like FieldWorks homonyms, their own
+/// glosses like "catch.1" and "catch.2", numbered by their homograph number
+/// or else their order in the file. Glosses are compared and numbered the way
+/// they're stored, so "catch (animate)" becomes "catch.animate.1". Glosses of
+/// their examples follow along, so that each example stays listed under its
+/// own entry.
+fn number_homonyms(entries: &mut [LiftEntry]) {
+    let homonyms = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| (NewLexicalEntry::normalize_gloss(&entry.gloss), index))
+        .into_group_map();
+    for (gloss, indices) in homonyms {
+        if indices.len() < 2 {
+            continue;
+        }
+        for (position, index) in indices.into_iter().enumerate() {
+            let entry = &mut entries[index];
+            let number = entry.homograph.unwrap_or(position as u32 + 1);
+            entry.gloss = format!("{}.{}", gloss, number);
+            for example in &mut entry.examples {
+                let (Some(morphemes), Some(glosses)) = (&example.morphemes, &example.glosses)
+                else {
+                    continue;
+                };
+                match WordSegment::parse_many(morphemes, glosses) {
+                    Ok(mut segments) => {
+                        for segment in &mut segments {
+                            if NewLexicalEntry::normalize_gloss(&segment.gloss) == gloss {
+                                segment.gloss = entry.gloss.clone();
+                            }
+                        }
+                        example.glosses = Some(WordSegment::gloss_layer(&segments));
+                    }
+                    Err(e) => eprintln!(
+                        "WARNING in entry '{}': Can't number the glosses of example '{}': {}",
+                        entry.id, example.source, e
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// Read every entry from a LIFT document, skipping those marked as deleted.
+fn parse_lift(input: &str) -> Result<Vec<LiftEntry>> {
+    let doc = roxmltree::Document::parse(input)?;
+    let root = doc.root_element();
+    if !root.has_tag_name("lift") {
+        anyhow::bail!(
+            "Expected <lift> at the top of the file, found <{}>",
+            root.tag_name().name()
+        );
+    }
+    Ok(children(root, "entry")
+        .filter(|entry| entry.attribute("dateDeleted").is_none())
+        .map(LiftEntry::from_xml)
+        .collect())
+}
+
+/// Write the given entries as a LIFT document.
+fn write_lift(entries: &[LiftEntry]) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<lift version=\"0.13\" producer=\"DAILP\">\n",
+    );
+    for entry in entries {
+        entry.write_xml(&mut out);
+    }
+    out.push_str("</lift>\n");
+    out
+}
+
+fn children<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(move |n| n.has_tag_name(name))
+}
+
+fn child<'a, 'input: 'a>(node: Node<'a, 'input>, name: &'static str) -> Option<Node<'a, 'input>> {
+    children(node, name).next()
+}
+
+/// All the text within the `<text>` of a node, which FieldWorks may split up
+/// with `<span>` elements.
+fn text(node: Node) -> Option<String> {
+    let text: String = child(node, "text")?
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect();
+    Some(text.trim().to_owned()).filter(|t| !t.is_empty())
+}
+
+fn first_form(node: Node) -> Option<String> {
+    children(node, "form").find_map(text)
+}
+
+fn form_in(node: Node, lang: &str) -> Option<String> {
+    children(node, "form")
+        .filter(|form| form.attribute("lang") == Some(lang))
+        .find_map(text)
+}
+
+/// Definition of a sense, or its glosses when it has none.
+fn sense_text(sense: Node) -> Option<String> {
+    child(sense, "definition").and_then(first_form).or_else(|| {
+        let glosses = children(sense, "gloss").filter_map(text).join("; ");
+        Some(glosses).filter(|g| !g.is_empty())
+    })
+}
+
+/// Read an enum variant from its name, like "Verb" or "OlderEntry".
+fn from_name<T: serde::de::DeserializeOwned>(name: &str) -> Option<T> {
+    serde_json::from_value(serde_json::Value::String(name.to_owned())).ok()
+}
+
+/// Language tag of a piece of Cherokee text, depending on whether it's written
+/// in syllabary.
+fn cherokee_language(text: &str) -> &'static str {
+    if text.chars().any(|c| ('\u{13A0}'..='\u{13FF}').contains(&c)) {
+        SYLLABARY
+    } else {
+        ROMANIZED
+    }
+}
+
+fn write_form(out: &mut String, indent: usize, lang: &str, text: &str) {
+    out.push_str(&format!(
+        "{:indent$}<form lang=\"{}\"><text>{}</text></form>\n",
+        "",
+        lang,
+        escape(text),
+        indent = indent
+    ));
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> LiftEntry {
+        LiftEntry {
+            id: "DF1975:catch".to_owned(),
+            headword: "hnoga".to_owned(),
+            shape: Some("hnoga".to_owned()),
+            gloss: "catch".to_owned(),
+            homograph: None,
+            part_of_speech: Some(PartOfSpeech::Verb),
+            senses: vec![
+                "he is catching it".to_owned(),
+                "he & I caught it".to_owned(),
+            ],
+            page_number: Some("42".to_owned()),
+            examples: vec![LiftExample {
+                source: "ᏥᏃᎦ".to_owned(),
+                simple_phonetics: Some("jinoga".to_owned()),
+                phonemic: None,
+                translation: Some("I am catching it".to_owned()),
+                morphemes: Some("ji-hnoga".to_owned()),
+                glosses: Some("1SG.B-catch".to_owned()),
+            }],
+            relations: vec![LiftRelation {
+                kind: MorphemeRelationKind::OlderEntry,
+                target: "DF2003:catch".to_owned(),
+            }],
+        }
+    }
+
+    #[test]
+    fn lift_round_trip() {
+        let entries = vec![entry()];
+        assert_eq!(parse_lift(&write_lift(&entries)).unwrap(), entries);
+    }
+
+    #[test]
+    fn read_fieldworks_entry() {
+        let entries = parse_lift(
+            r#"<lift version="0.13">
+              <entry id="hnoga_1">
+                <lexical-unit><form lang="chr"><text>Ꮵ<span>ᏃᎦ</span></text></form></lexical-unit>
+                <sense>
+                  <grammatical-info value="Noun"/>
+                  <gloss lang="en"><text>catch (animate)</text></gloss>
+                  <relation type="Synonyms" ref="sgwa_2"/>
+                </sense>
+              </entry>
+              <entry id="gone_3" dateDeleted="2020-01-01"/>
+            </lift>"#,
+        )
+        .unwrap();
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.headword, "ᏥᏃᎦ");
+        assert_eq!(entry.gloss, "catch (animate)");
+        assert_eq!(entry.senses, vec!["catch (animate)".to_owned()]);
+        assert_eq!(entry.part_of_speech, Some(PartOfSpeech::Noun));
+        assert_eq!(
+            entry.relations,
+            vec![LiftRelation {
+                kind: MorphemeRelationKind::Related,
+                target: "sgwa_2".to_owned(),
+            }]
+        );
+        let new_entry = entry.to_new_entry(
+            PositionInDocument::new(DocumentId(Default::default()), "1".to_owned(), 1),
+            None,
+        );
+        assert_eq!(new_entry.gloss().as_deref(), Some("catch.animate"));
+    }
+
+    #[test]
+    fn homonyms_keep_their_own_glosses() {
+        let mut entries = parse_lift(
+            r#"<lift version="0.13">
+              <entry id="hnoga_1" order="1">
+                <lexical-unit><form lang="chr"><text>ᏃᎦ</text></form></lexical-unit>
+                <sense><gloss lang="en"><text>catch</text></gloss></sense>
+              </entry>
+              <entry id="hnoga_2" order="2">
+                <lexical-unit><form lang="chr"><text>ᏃᎦ</text></form></lexical-unit>
+                <sense>
+                  <gloss lang="en"><text>catch</text></gloss>
+                  <example>
+                    <form lang="chr"><text>ᏥᏃᎦ</text></form>
+                    <field type="morphemes"><form lang="chr-Latn-x-dailp"><text>ji-hnoga</text></form></field>
+                    <field type="glosses"><form lang="en"><text>1SG.B-catch</text></form></field>
+                  </example>
+                </sense>
+              </entry>
+              <entry id="sgwa_3">
+                <lexical-unit><form lang="chr"><text>ᏍᏆ</text></form></lexical-unit>
+                <sense><gloss lang="en"><text>only</text></gloss></sense>
+              </entry>
+            </lift>"#,
+        )
+        .unwrap();
+        number_homonyms(&mut entries);
+        let glosses: Vec<_> = entries.iter().map(|entry| &*entry.gloss).collect();
+        assert_eq!(glosses, ["catch.1", "catch.2", "only"]);
+        // The example goes with the second entry rather than the first.
+        assert_eq!(
+            entries[1].examples[0].glosses.as_deref(),
+            Some("1SG.B-catch.2")
+        );
+
+        // Glosses are numbered the way they're stored.
+        let mut entries = parse_lift(
+            r#"<lift version="0.13">
+              <entry id="jinoga_1">
+                <lexical-unit><form lang="chr"><text>ᏥᏃᎦ</text></form></lexical-unit>
+                <sense><gloss lang="en"><text>catch (animate)</text></gloss></sense>
+              </entry>
+              <entry id="jinoga_2">
+                <lexical-unit><form lang="chr"><text>ᏥᏃᎦ</text></form></lexical-unit>
+                <sense><gloss lang="en"><text>catch.animate</text></gloss></sense>
+              </entry>
+            </lift>"#,
+        )
+        .unwrap();
+        number_homonyms(&mut entries);
+        let new_entry = entries[0].to_new_entry(
+            PositionInDocument::new(DocumentId(Default::default()), "1".to_owned(), 1),
+            None,
+        );
+        assert_eq!(new_entry.gloss().as_deref(), Some("catch.animate.1"));
+        assert_eq!(entries[1].gloss, "catch.animate.2");
+    }
+}
@@ -6,6 +6,7 @@ mod contributors;
 mod early_vocab;
 mod edited_collection;
 mod lexical;
+mod lift;
 mod menu;
 mod pages;
 mod spreadsheets;
@@ -14,7 +15,10 @@ mod translations;
 mod user_documents;
 
 use anyhow::Result;
-use dailp::{Database, LexicalConnection, SheetResult, Uuid};
+use dailp::{
+    check_gloss_tags, AnnotatedDoc, AnnotatedSeg, Database, LexicalConnection, SheetResult, Uuid,
+};
+use itertools::Itertools;
 use log::error;
 use std::time::Duration;
 
@@ -31,6 +35,26 @@ async fn main() -> Result<()> {
 
     let db = Database::connect(Some(1))?;
 
+    // Exchange a single dictionary with lexicon tools instead of migrating,
+    // like `dailp-migration lift-export DF1975 df1975.lift`, or migrate just
+    // the annotated documents with `dailp-migration documents`, which checks
+    // their glosses and morpheme links along the way.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match &args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["documents"] => {
+            return migrate_data(&db).await;
+        }
+        ["lift-export", short_name, path] => {
+            return lift::export_dictionary(&db, short_name, path).await;
+        }
+        ["lift-import", path, short_name, title, rest @ ..] => {
+            let year = rest.first().map(|year| year.parse()).transpose()?;
+            lift::import_dictionary(&db, path, short_name, title, year).await?;
+            return Ok(());
+        }
+        _ => {}
+    }
+
     //println!("Migrating Image Sources...");
     //migrate_image_sources(&db).await?;
 
@@ -64,6 +88,9 @@ async fn main() -> Result<()> {
     println!("Creating user documents collection...");
     user_documents::create_user_documents_collection(&db).await?;
 
+    println!("Backfilling search keys...");
+    db.backfill_search_keys().await?;
+
     Ok(())
 }
 
@@ -115,33 +142,75 @@ async fn migrate_data(db: &Database) -> Result<()> {
             {
                 morpheme_relations.append(&mut refs);
                 // spreadsheets::write_to_file(&doc)?;
-                document_contents.push(doc);
+                if let Some(doc) = doc {
+                    document_contents.push(doc);
+                }
             } else {
                 error!("Failed to process {}", sheet_id);
             }
         }
     }
 
+    // Point out unknown tags before they're saved as document-local glosses.
+    let known_tags = db.abstract_tag_glosses().await?;
+    for doc in &document_contents {
+        warn_about_glosses(doc, &known_tags);
+    }
+
     // Each document takes a bunch of operations to insert, so do them
     // sequentially instead of concurrently to avoid starving the tasks.
     for doc in document_contents {
         db.insert_document_contents(doc).await?;
     }
 
+    // Links can only be made between glosses that exist by now, so point out
+    // the rest before inserting. Re-inserting existing links is harmless.
+    let ids: Vec<_> = morpheme_relations
+        .iter()
+        .flat_map(|link| [link.left.clone(), link.right.clone()])
+        .unique()
+        .collect();
+    for id in db.unresolved_morphemes(&ids).await? {
+        eprintln!("WARNING: Can't link to unknown morpheme {}", id);
+    }
     db.insert_morpheme_relations(morpheme_relations).await?;
 
     Ok(())
 }
 
+/// Print a warning for each unknown or malformed tag in the given document.
+fn warn_about_glosses(doc: &AnnotatedDoc, known_tags: &[String]) {
+    let words = doc
+        .segments
+        .iter()
+        .flatten()
+        .flat_map(|page| &page.paragraphs)
+        .flat_map(|paragraph| &paragraph.source)
+        .filter_map(AnnotatedSeg::form);
+    for (index, word) in words.enumerate() {
+        let glosses = word.segments.iter().flatten().map(|s| &*s.gloss);
+        for warning in check_gloss_tags(glosses, known_tags) {
+            eprintln!(
+                "WARNING in document '{}', word {} ({}): {}",
+                doc.meta.short_name,
+                index + 1,
+                word.source,
+                warning
+            );
+        }
+    }
+}
+
 /// Fetch the contents of the sheet with the given ID, validating the first page as
 /// annotation lines and the "Metadata" page as [dailp::DocumentMetadata].
-/// Ignores documents already present in the database.
+/// Skips the contents of documents already present in the database, but still
+/// returns their morpheme references so links can be filled in again.
 async fn fetch_sheet(
     db: Option<&Database>,
     sheet_id: &str,
     collection_id: Uuid,
     order_index: i64,
-) -> Result<Option<(dailp::AnnotatedDoc, Vec<dailp::LexicalConnection>)>> {
+) -> Result<Option<(Option<dailp::AnnotatedDoc>, Vec<dailp::LexicalConnection>)>> {
     use crate::spreadsheets::AnnotatedLine;
 
     // Parse the metadata on the second page of each sheet.
@@ -162,43 +231,36 @@ async fn fetch_sheet(
         return Ok(None);
     }
 
+    // Parse references for this particular document.
+    println!("parsing references...");
+    let refs = SheetResult::from_sheet(sheet_id, Some(REFERENCES_SHEET_NAME)).await;
+    let refs: Vec<LexicalConnection> = if let Ok(refs) = refs {
+        SheetInterpretation { sheet: refs }
+            .into_references(&meta.short_name)
+            .into_iter()
+            .filter_map(|link| match link {
+                Ok(link) => Some(link),
+                Err(e) => {
+                    eprintln!("WARNING in document '{}': {}", meta.short_name, e);
+                    None
+                }
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
     // Check if this document exists in the database
     let db = db.unwrap();
     let doc_id_in_db = db.document_id_from_name(&meta.short_name).await?;
 
-    if doc_id_in_db.is_some() {
-        println!(
-            "{} already exists with ID {}.",
-            meta.short_name,
-            doc_id_in_db.unwrap().0
-        );
-        return Ok(None);
+    if let Some(doc_id) = doc_id_in_db {
+        println!("{} already exists with ID {}.", meta.short_name, doc_id.0);
+        return Ok(Some((None, refs)));
     }
 
     println!("---Processing document: {}---", meta.short_name);
 
-    // Parse references for this particular document.
-    println!("parsing references (skipping because broken currently)...");
-    let refs: Vec<LexicalConnection> = Vec::new();
-    // let refs = SheetResult::from_sheet(sheet_id, Some(REFERENCES_SHEET_NAME)).await;
-    // let refs = if let Ok(refs) = refs {
-    //     SheetInterpretation { sheet: refs }
-    //         .into_references(&meta.short_name)
-    //         .await
-    // } else {
-    //     Vec::new()
-    // };
-
-    let document_id = db
-        .insert_document(&meta, collection_id, order_index)
-        .await?;
-
-    // Fill in blank UUID.
-    let meta = dailp::DocumentMetadata {
-        id: document_id,
-        ..meta
-    };
-
     let page_count = meta
         .page_images
         .as_ref()
@@ -243,9 +305,21 @@ async fn fetch_sheet(
         all_lines.append(&mut lines);
         tokio::time::sleep(Duration::from_millis(1000)).await;
     }
+    // Parse every line before inserting the document, so that a failure
+    // doesn't leave behind an empty document that later runs would skip.
     let annotated = AnnotatedLine::many_from_semantic(&all_lines, &meta)?;
+
+    let document_id = db
+        .insert_document(&meta, collection_id, order_index)
+        .await?;
+
+    // Fill in blank UUID.
+    let meta = dailp::DocumentMetadata {
+        id: document_id,
+        ..meta
+    };
     let segments = AnnotatedLine::lines_into_segments(annotated, &document_id, &meta.date);
     let doc = dailp::AnnotatedDoc::new(meta, segments);
 
-    Ok(Some((doc, refs)))
+    Ok(Some((Some(doc), refs)))
 }
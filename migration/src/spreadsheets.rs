@@ -10,6 +10,7 @@ use dailp::collection::CollectionSection::Credit;
 use dailp::collection::CollectionSection::Intro;
 use dailp::ContributorRole;
 use dailp::Uuid;
+use dailp::{NewLexicalEntry, PartOfSpeech};
 use std::result::Result::Ok;
 
 use dailp::{
@@ -29,7 +30,7 @@ const BLOCK_START: &str = "{";
 const BLOCK_END: &str = "}";
 
 pub struct LexicalEntryWithForms {
-    pub entry: AnnotatedForm,
+    pub entry: NewLexicalEntry,
     pub forms: Vec<AnnotatedForm>,
 }
 
@@ -485,7 +486,13 @@ impl SheetInterpretation {
             };
 
             // Successfully created entry
-            results.push(LexicalEntryWithForms { forms, entry });
+            results.push(LexicalEntryWithForms {
+                forms,
+                entry: NewLexicalEntry {
+                    stem: entry,
+                    part_of_speech: Some(PartOfSpeech::Adjective),
+                },
+            });
         }
 
         // Report warnings about skipped data
@@ -723,7 +730,13 @@ impl SheetInterpretation {
                     ingested_audio_track: None,
                 };
 
-                Ok(LexicalEntryWithForms { forms, entry })
+                Ok(LexicalEntryWithForms {
+                    forms,
+                    entry: NewLexicalEntry {
+                        stem: entry,
+                        part_of_speech: Some(PartOfSpeech::Noun),
+                    },
+                })
             };
 
             // Add successful entries to results
@@ -757,22 +770,30 @@ impl SheetInterpretation {
         Ok(results)
     }
 
-    pub async fn into_references(self, doc_name: &str) -> Vec<dailp::LexicalConnection> {
+    /// Parse this sheet as a list of links from glosses in the given document
+    /// to glosses in other documents. Each row holds a local gloss followed by
+    /// the ID of its target, like "DF1975:catch". Rows that can't be read come
+    /// back as errors naming the row.
+    pub fn into_references(self, doc_name: &str) -> Vec<Result<dailp::LexicalConnection>> {
         self.sheet
             .values
             .into_iter()
-            // First column is the name of the field, useless when parsing so we ignore it.
+            .enumerate()
+            // First row is the header, useless when parsing so we ignore it.
             .skip(1)
-            .filter_map(|row| {
+            .filter(|(_, row)| row.iter().any(|cell| !cell.trim().is_empty()))
+            .map(|(index, row)| {
                 let mut row = row.into_iter();
-                Some(dailp::LexicalConnection::new(
-                    MorphemeId {
-                        document_name: Some(doc_name.to_owned()),
-                        gloss: row.next()?,
-                        index: None,
-                    },
-                    MorphemeId::parse(&row.next()?)?,
-                ))
+                let gloss = row.next().unwrap_or_default();
+                let target = row.next().unwrap_or_default();
+                let link = MorphemeId::parse_reference(&format!("{}:{}", doc_name, gloss.trim()))
+                    .and_then(|left| {
+                        Ok(dailp::LexicalConnection::new(
+                            left,
+                            MorphemeId::parse_reference(&target)?,
+                        ))
+                    });
+                link.map_err(|e| anyhow::format_err!("References row {}: {}", index + 1, e))
             })
             .collect()
     }
@@ -1262,6 +1283,25 @@ impl AnnotatedLine {
                         let glosses = gloss_row.items.get(i);
                         let translation = translation_row.items.get(i).map(|x| x.trim().to_owned());
 
+                        // Words without any segmentation are fine. A malformed
+                        // one should be fixed in the sheet, but only costs this
+                        // word its segments rather than the whole document.
+                        let segments = match (morphemes, glosses) {
+                            (Some(m), Some(g)) if !m.trim().is_empty() || !g.trim().is_empty() => {
+                                match WordSegment::parse_many(m, g) {
+                                    Ok(segments) => Some(segments),
+                                    Err(e) => {
+                                        eprintln!("WARNING in document '{}', line {}, word {}: Malformed segmentation, skipping it: {}",
+                                                meta.short_name, line_num, i + 1, e);
+                                        eprintln!("  Morphemic segmentation: {}", m);
+                                        eprintln!("  Morpheme gloss: {}", g);
+                                        None
+                                    }
+                                }
+                            }
+                            _ => None,
+                        };
+
                         let ingested_audio_track = if let Some(annotations) = meta
                             .audio_recording
                             .as_ref()
@@ -1298,11 +1338,7 @@ impl AnnotatedLine {
                                 .get(i)
                                 .map(|x| x.replace("ʔ", "'")),
                             phonemic: phonemic_row.items.get(i).map(|x| x.to_owned()),
-                            segments: if let (Some(m), Some(g)) = (morphemes, glosses) {
-                                WordSegment::parse_many(m, g)
-                            } else {
-                                None
-                            },
+                            segments,
                             english_gloss: vec![translation].into_iter().flatten().collect(),
                             commentary: commentary_row.items.get(i).map(|x| x.to_owned()),
                             page_break: pb.map(|i| i as i32),
@@ -1504,4 +1540,40 @@ mod tests {
         let url = "https://docs.google.com/document/d/13ELP_F95OUUW8exR2KvQzzgtcfO1w_b3wVgPQR8dggo";
         assert_eq!(SheetInterpretation::drive_url_to_id(url), id);
     }
+
+    #[test]
+    fn malformed_gloss_keeps_word() {
+        let meta: DocumentMetadata = serde_json::from_value(serde_json::json!({
+            "id": DocumentId::default(),
+            "short_name": "TEST",
+            "title": "Test",
+            "is_reference": false,
+        }))
+        .unwrap();
+        let row = |items: &[&str]| AnnotationRow {
+            title: String::new(),
+            items: items.iter().map(|s| s.to_string()).collect(),
+        };
+        let line = SemanticLine {
+            number: "1".to_owned(),
+            rows: vec![
+                row(&["ᎦᏙ", "ᎠᏍᎦᏯ"]),
+                row(&[]),
+                row(&["gado", "asgaya"]),
+                row(&["ga:do", "asgaya"]),
+                row(&["ga-do", "a-sgaya"]),
+                row(&["3SG.B-what", "man"]),
+                row(&["what", "man"]),
+                row(&[]),
+            ],
+            ends_page: true,
+        };
+        let lines = AnnotatedLine::many_from_semantic(&[line], &meta).unwrap();
+        let words = &lines[0].words;
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].segments.as_ref().map(Vec::len), Some(2));
+        // The bad gloss only costs its own word the segmentation.
+        assert_eq!(words[1].source, "ᎠᏍᎦᏯ");
+        assert!(words[1].segments.is_none());
+    }
 }
@@ -18,20 +18,29 @@ pub async fn migrate_tags(db: &Database) -> Result<()> {
     let glossary = parse_tag_glossary(glossary?)?;
 
     // Insert all of the internal tags that each system will convert from.
+    // Everything in our glossary spreadsheets is Cherokee.
     for tag in glossary {
-        db.insert_abstract_tag(tag).await?;
+        db.insert_abstract_tag(tag, "Cherokee").await?;
     }
 
     let crg = db
-        .insert_morpheme_system("CRG".into(), "Cherokee Reference Grammar".into())
+        .insert_morpheme_system(
+            "Cherokee",
+            "CRG".into(),
+            "Cherokee Reference Grammar".into(),
+        )
         .await?;
     sync_morpheme_system(db, "CRG Merged Glossary", crg).await?;
     let taoc = db
-        .insert_morpheme_system("TAOC".into(), "Tone and Accent in Oklahoma Cherokee".into())
+        .insert_morpheme_system(
+            "Cherokee",
+            "TAOC".into(),
+            "Tone and Accent in Oklahoma Cherokee".into(),
+        )
         .await?;
     sync_morpheme_system(db, "TAOC Glossary", taoc).await?;
     let learner = db
-        .insert_morpheme_system("LEARNER".into(), "Learner System".into())
+        .insert_morpheme_system("Cherokee", "LEARNER".into(), "Learner System".into())
         .await?;
     sync_morpheme_system(db, "Learner Glossary", learner).await?;
 
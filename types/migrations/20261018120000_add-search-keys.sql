@@ -0,0 +1,8 @@
+-- Loose search keys for phonetic fields, which ignore tone, vowel length,
+-- glottal stops, and the d/t versus t/th spelling of consonants.
+-- These are computed by the application whenever a word is written.
+alter table word add column if not exists search_key text;
+alter table word_segment add column if not exists search_key text;
+
+create index if not exists word_search_key on word (search_key);
+create index if not exists word_segment_search_key on word_segment (search_key);
@@ -0,0 +1,27 @@
+-- Whether each word segment is a root, an affix, or a clitic.
+-- This replaces guessing roots from the case of their gloss.
+create type word_segment_class as enum ('Root', 'Affix', 'Clitic');
+
+alter table word_segment add column if not exists class word_segment_class;
+
+-- Classify existing segments from the tag inventory. Anything that isn't a
+-- known tag is lexical, unless it hasn't been identified yet.
+update word_segment set class = coalesce(
+  (
+    select
+      case
+        when abstract_morpheme_tag.linguistic_type ilike '%clitic%' then 'Clitic'
+        else 'Affix'
+      end::word_segment_class
+    from morpheme_gloss
+      inner join abstract_morpheme_tag
+        on abstract_morpheme_tag.internal_gloss = morpheme_gloss.gloss
+    where morpheme_gloss.id = word_segment.gloss_id
+  ),
+  (
+    select 'Root'::word_segment_class
+    from morpheme_gloss
+    where morpheme_gloss.id = word_segment.gloss_id
+      and morpheme_gloss.gloss not like '%?%'
+  )
+);
@@ -0,0 +1,49 @@
+-- Scope functional morpheme tags and the systems that display them to a
+-- language, since different languages may use the same gloss for different
+-- meanings. Every existing tag and system belongs to Cherokee.
+insert into language (name, status)
+select 'Cherokee', 'approved'
+where not exists (select from language where name = 'Cherokee');
+
+alter table abbreviation_system
+add column language_id uuid references language (id);
+
+alter table abstract_morpheme_tag
+add column language_id uuid references language (id);
+
+-- Finds the language whose tags apply to the given document: the first of its
+-- languages that has a tag system, or Cherokee for documents without one.
+create or replace function tag_language(document uuid) returns uuid
+language sql stable as $$
+  select coalesce(
+    (
+      select document_language.language_id
+      from document_language
+        inner join language on language.id = document_language.language_id
+      where document_language.document_id = document
+        and exists (
+          select from abbreviation_system
+          where abbreviation_system.language_id = document_language.language_id
+        )
+      order by language.name
+      limit 1
+    ),
+    (select id from language where name = 'Cherokee' order by id limit 1)
+  )
+$$;
+
+update abbreviation_system set language_id = tag_language(null);
+update abstract_morpheme_tag set language_id = tag_language(null);
+
+-- Tags and systems inserted without a language belong to Cherokee.
+alter table abbreviation_system
+alter column language_id set default tag_language(null),
+alter column language_id set not null,
+drop constraint abbreviation_system_short_name_key,
+add constraint abbreviation_system_short_name_key unique (language_id, short_name);
+
+alter table abstract_morpheme_tag
+alter column language_id set default tag_language(null),
+alter column language_id set not null,
+drop constraint abstract_morpheme_tag_internal_gloss_key,
+add constraint abstract_morpheme_tag_internal_gloss_key unique (language_id, internal_gloss);
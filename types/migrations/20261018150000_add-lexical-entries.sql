@@ -0,0 +1,23 @@
+-- Parts of speech that dictionary headwords are listed under.
+create type part_of_speech as enum ('Verb', 'Noun', 'Adjective', 'Numeral', 'Particle');
+
+-- A headword in a dictionary, like DF1975, which describes one of the
+-- morpheme glosses defined in that dictionary.
+create table lexical_entry (
+  id autouuid primary key,
+  gloss_id uuid not null unique references morpheme_gloss (id) on delete cascade,
+  headword text not null,
+  part_of_speech part_of_speech,
+  senses text[] not null default '{}',
+  page_number text,
+  index_in_document bigint
+);
+
+-- Every gloss already defined in a dictionary is one of its entries, headed by
+-- the shape of the morpheme until the dictionary is migrated again.
+insert into lexical_entry (gloss_id, headword)
+select morpheme_gloss.id, coalesce(morpheme_gloss.example_shape, morpheme_gloss.gloss)
+from morpheme_gloss
+  inner join document on document.id = morpheme_gloss.document_id
+where document.is_reference
+on conflict (gloss_id) do nothing;
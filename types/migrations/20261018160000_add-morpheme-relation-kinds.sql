@@ -0,0 +1,9 @@
+-- How one morpheme gloss came from or led to another. Each relation points
+-- from its left gloss to its right gloss.
+create type morpheme_relation_kind as enum (
+  'Related', 'PhraseToWord', 'WordToEntry', 'OlderEntry', 'Merger'
+);
+
+-- Existing relations weren't typed, so they stay generic links.
+alter table morpheme_gloss_relation
+add column kind morpheme_relation_kind not null default 'Related';
@@ -0,0 +1,21 @@
+-- Stemmed English words in some translations, used to find Cherokee forms from
+-- English. Glosses like "catch.animate" are split into separate words first.
+create or replace function english_terms(variadic parts text[]) returns tsvector
+language sql immutable parallel safe as $$
+  select to_tsvector(
+    'english',
+    regexp_replace(coalesce(array_to_string(parts, ' '), ''), '[._+()\[\],;:]', ' ', 'g')
+  )
+$$;
+
+create index if not exists word_english_terms
+on word using gin (english_terms(english_gloss));
+
+create index if not exists morpheme_gloss_english_terms
+on morpheme_gloss using gin (english_terms(gloss));
+
+create index if not exists lexical_entry_english_terms
+on lexical_entry using gin (english_terms(variadic senses));
+
+create index if not exists paragraph_english_terms
+on paragraph using gin (english_terms(english_translation));
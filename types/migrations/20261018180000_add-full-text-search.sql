@@ -0,0 +1,35 @@
+-- Trigram indexes let substring searches over Cherokee text use an index
+-- instead of reading every word.
+create extension if not exists pg_trgm;
+
+-- Searchable text of a word, weighted so that matches in the Cherokee forms
+-- rank above matches in the translation, which rank above the commentary.
+-- Cherokee fields are split into whole words without stemming, while English
+-- fields are stemmed like `english_terms`, which is named with its schema so
+-- that restoring a dump can build the index.
+create or replace function word_search_terms(
+  source_text text,
+  simple_phonetics text,
+  phonemic text,
+  english_gloss text,
+  commentary text
+) returns tsvector
+language sql immutable parallel safe as $$
+  select
+    setweight(to_tsvector('simple', coalesce(source_text, '')), 'A')
+    || setweight(to_tsvector('simple', coalesce(simple_phonetics, '')), 'A')
+    || setweight(to_tsvector('simple', coalesce(phonemic, '')), 'B')
+    || setweight(public.english_terms(english_gloss), 'B')
+    || setweight(public.english_terms(commentary), 'D')
+$$;
+
+create index if not exists word_search_terms
+on word using gin (
+  word_search_terms(source_text, simple_phonetics, phonemic, english_gloss, commentary)
+);
+
+create index if not exists word_source_text_trigrams
+on word using gin (source_text gin_trgm_ops);
+
+create index if not exists word_simple_phonetics_trigrams
+on word using gin (simple_phonetics gin_trgm_ops);
@@ -1 +1 @@
-SELECT id FROM abbreviation_system WHERE short_name = $1;
\ No newline at end of file
+SELECT id FROM abbreviation_system WHERE short_name = $1 AND language_id = tag_language($2);
\ No newline at end of file
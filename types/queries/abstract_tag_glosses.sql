@@ -0,0 +1,6 @@
+-- Selects the internal gloss of every abstract tag, which is how functional
+-- morphemes are written in raw annotations. Raw annotations are all Cherokee.
+select internal_gloss
+from abstract_morpheme_tag
+where language_id = tag_language(null)
+order by internal_gloss
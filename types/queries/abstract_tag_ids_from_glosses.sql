@@ -2,3 +2,7 @@ select abstract_morpheme_tag.id as "id!"
 from unnest($1::text[]) as morpheme_gloss
   inner join
     abstract_morpheme_tag on abstract_morpheme_tag.internal_gloss = morpheme_gloss
+-- Only tags in the same language as the system they're displayed in.
+where abstract_morpheme_tag.language_id = (
+  select language_id from abbreviation_system where abbreviation_system.id = $2
+)
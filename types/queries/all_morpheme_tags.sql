@@ -13,8 +13,8 @@ with t as (
     inner join
       abstract_morpheme_tag on
         abstract_morpheme_tag.id = any(morpheme_tag.abstract_ids)
-  where abbreviation_system.short_name = $1
-  or abbreviation_system.short_name like 'CUS'
+  where abbreviation_system.language_id = tag_language($2)
+    and (abbreviation_system.short_name = $1 or abbreviation_system.short_name like 'CUS')
 )
 
 select *
@@ -0,0 +1,12 @@
+-- Segments of already analyzed words that either sound like the given search
+-- key or have the same source text.
+select
+  word.id as word_id,
+  word_segment.morpheme,
+  morpheme_gloss.gloss,
+  word_segment.role as "role: WordSegmentRole"
+from word
+  inner join word_segment on word_segment.word_id = word.id
+  inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
+where word.search_key = $1 or word.source_text = $2
+order by word.id, word_segment.index_in_word
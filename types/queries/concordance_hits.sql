@@ -0,0 +1,88 @@
+-- Finds words in paragraphs that match a concordance search, along with the
+-- paragraph and document each one is in. Words are matched by the gloss of
+-- one of their morphemes, by their phonetic search key, or by a piece of their
+-- syllabary source, whichever is given. Hits are sorted by $6, which is one of
+-- 'document', 'date', 'left' or 'right', then skip $7 of them and keep at most
+-- $8. Sorting by context compares the $9 words on that side of each hit,
+-- nearest first, where running out of words comes first.
+with hit as (
+  select
+    word.id as word_id,
+    word.source_text,
+    word.index_in_document,
+    paragraph.id as paragraph_id,
+    document.id as document_id,
+    document.short_name as document_name,
+    document.title as document_title,
+    document.written_at as document_date,
+    document.index_in_group as document_order_index
+  from word
+    inner join document on document.id = word.document_id
+    inner join paragraph on paragraph.page_id = word.page_id
+      -- Same as `words_in_paragraph`, so each word is in the paragraph it
+      -- starts in.
+      and word.character_range && paragraph.character_range
+      and word.character_range &> paragraph.character_range
+  where (
+      $1::text is null or exists (
+        select
+        from word_segment
+          inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
+          left join document as gloss_document on gloss_document.id = morpheme_gloss.document_id
+        where word_segment.word_id = word.id
+          and morpheme_gloss.gloss = $1
+          and ($2::text is null or gloss_document.short_name = $2)
+      )
+    )
+    and ($3::text is null or word.search_key = $3)
+    and ($4::text is null or word.source_text like '%' || $4 || '%')
+    and ($5::uuid is null or word.document_id = $5)
+),
+
+-- The words around each word of the paragraphs with a hit, only when sorting
+-- by context.
+context as (
+  select
+    word.id as word_id,
+    coalesce(array_agg(lower(word.source_text) collate "C") over before, '{}') as left_words,
+    coalesce(array_agg(lower(word.source_text) collate "C") over after, '{}') as right_words
+  from word
+    inner join paragraph on paragraph.page_id = word.page_id
+      and word.character_range && paragraph.character_range
+      and word.character_range &> paragraph.character_range
+  where $6::text in ('left', 'right')
+    and paragraph.id in (select hit.paragraph_id from hit)
+  window
+    before as (
+      partition by paragraph.id
+      order by word.character_range desc
+      rows between 1 following and $9 following
+    ),
+    after as (
+      partition by paragraph.id
+      order by word.character_range
+      rows between 1 following and $9 following
+    )
+)
+
+select
+  hit.word_id,
+  hit.paragraph_id,
+  hit.document_id,
+  hit.document_name,
+  hit.document_title,
+  hit.document_date as "document_date: Date",
+  hit.document_order_index
+from hit
+  left join context on context.word_id = hit.word_id
+order by
+  case when $6 = 'left' then context.left_words end,
+  case when $6 = 'right' then context.right_words end,
+  case when $6 in ('left', 'right') then lower(hit.source_text) collate "C" end,
+  case when $6 = 'left' then context.right_words end,
+  case when $6 = 'right' then context.left_words end,
+  case when $6 = 'date' then hit.document_date end nulls last,
+  hit.document_name,
+  hit.index_in_document
+offset $7
+limit $8
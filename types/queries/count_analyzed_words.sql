@@ -0,0 +1,9 @@
+-- Counts the words with the given gloss and all words with any segmentation.
+select
+  (
+    select count(distinct word_segment.word_id)
+    from word_segment
+      inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
+    where morpheme_gloss.gloss = $1
+  ) as "with_gloss!",
+  (select count(distinct word_id) from word_segment) as "analyzed!"
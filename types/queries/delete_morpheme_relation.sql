@@ -0,0 +1,12 @@
+-- Removes the link from the gloss defined in one document to the gloss
+-- defined in another.
+delete from morpheme_gloss_relation
+using
+  morpheme_gloss as left_gloss
+  inner join document as left_doc on left_doc.id = left_gloss.document_id,
+  morpheme_gloss as right_gloss
+  inner join document as right_doc on right_doc.id = right_gloss.document_id
+where morpheme_gloss_relation.left_gloss_id = left_gloss.id
+  and morpheme_gloss_relation.right_gloss_id = right_gloss.id
+  and left_doc.short_name = $1 and left_gloss.gloss = $2
+  and right_doc.short_name = $3 and right_gloss.gloss = $4
@@ -0,0 +1,14 @@
+-- Selects every link from a gloss defined in the given document to a gloss
+-- defined in any document.
+select
+  left_gloss.gloss as left_gloss,
+  right_doc.short_name as right_doc_name,
+  right_gloss.gloss as right_gloss,
+  morpheme_gloss_relation.kind as "kind: MorphemeRelationKind"
+from morpheme_gloss_relation
+  inner join morpheme_gloss as left_gloss on left_gloss.id = morpheme_gloss_relation.left_gloss_id
+  inner join document as left_doc on left_doc.id = left_gloss.document_id
+  inner join morpheme_gloss as right_gloss on right_gloss.id = morpheme_gloss_relation.right_gloss_id
+  inner join document as right_doc on right_doc.id = right_gloss.document_id
+where left_doc.short_name = $1
+order by left_gloss.gloss, right_doc.short_name, right_gloss.gloss
@@ -0,0 +1,36 @@
+-- Finds every morpheme gloss within $3 links of those with the given gloss,
+-- optionally only the one defined in the document with the given short name.
+-- The oldest documents come first.
+with recursive reachable (gloss_id, depth) as (
+  select morpheme_gloss.id::uuid, 0
+  from morpheme_gloss
+    left join document on document.id = morpheme_gloss.document_id
+  where morpheme_gloss.gloss = $1
+    and ($2::text is null or document.short_name = $2)
+
+  union
+
+  select
+    case
+      when relation.left_gloss_id = reachable.gloss_id then relation.right_gloss_id
+      else relation.left_gloss_id
+    end,
+    reachable.depth + 1
+  from reachable
+    inner join
+      morpheme_gloss_relation as relation on
+        relation.left_gloss_id = reachable.gloss_id
+        or relation.right_gloss_id = reachable.gloss_id
+  where reachable.depth < $3
+)
+
+select
+  morpheme_gloss.id,
+  morpheme_gloss.gloss,
+  morpheme_gloss.example_shape,
+  document.short_name as "document_name?",
+  document.written_at as "date?: Date"
+from morpheme_gloss
+  left join document on document.id = morpheme_gloss.document_id
+where morpheme_gloss.id in (select gloss_id from reachable)
+order by document.written_at nulls last, document.short_name, morpheme_gloss.gloss
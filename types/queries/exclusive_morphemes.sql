@@ -0,0 +1,24 @@
+-- Finds functional morphemes attested in at least $2 words that never appear
+-- in the same word as the morpheme with the given gloss.
+with target_words as (
+  select distinct word_segment.word_id
+  from word_segment
+    inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
+  where morpheme_gloss.gloss = $1
+)
+
+select
+  morpheme_gloss.gloss,
+  count(distinct word_segment.word_id) as "word_count!",
+  (array_agg(distinct word_segment.word_id))[1:$3] as "example_word_ids!"
+from word_segment
+  inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
+  inner join
+    abstract_morpheme_tag on
+      abstract_morpheme_tag.internal_gloss = morpheme_gloss.gloss
+      and abstract_morpheme_tag.language_id = tag_language(morpheme_gloss.document_id)
+where morpheme_gloss.gloss != $1
+group by morpheme_gloss.gloss
+having count(distinct word_segment.word_id) >= $2
+  and not bool_or(word_segment.word_id in (select word_id from target_words))
+order by "word_count!" desc, morpheme_gloss.gloss
@@ -0,0 +1,26 @@
+-- For each of the given glosses, in order, selects the internal glosses of the
+-- abstract tags it stands for in the given system. A gloss that isn't a known
+-- tag, like a custom gloss entered by the user, stands for itself. Only tags in
+-- the tag language of the given document count.
+select
+  coalesce(
+    (
+      select array_agg(abstract_morpheme_tag.internal_gloss order by abstract_index)
+      from unnest(concrete_tag.abstract_ids) with ordinality as abstract(id, abstract_index)
+        inner join abstract_morpheme_tag on abstract_morpheme_tag.id = abstract.id
+    ),
+    array[input_gloss.gloss]
+  ) as "internal_glosses!"
+from unnest($1::text[]) with ordinality as input_gloss(gloss, index)
+  left join lateral (
+    -- Prefer the simplest tag when one system reuses the same gloss.
+    select morpheme_tag.abstract_ids
+    from morpheme_tag
+      inner join abbreviation_system on abbreviation_system.id = morpheme_tag.system_id
+    where abbreviation_system.short_name = $2::text
+      and abbreviation_system.language_id = tag_language($3)
+      and morpheme_tag.gloss = input_gloss.gloss
+    order by array_length(morpheme_tag.abstract_ids, 1)
+    limit 1
+  ) as concrete_tag on true
+order by input_gloss.index
@@ -0,0 +1,104 @@
+-- Ranked full-text search over words and paragraph translations, one page at
+-- a time. Words match by whole Cherokee words, stemmed English words in their
+-- gloss or commentary, or any piece of their syllabary or simple phonetics.
+-- Pages continue after the result with score $2 and ID $3, and highlights are
+-- only built for the results on this page.
+with search as (
+  select
+    websearch_to_tsquery('english', $1) || websearch_to_tsquery('simple', $1) as query,
+    websearch_to_tsquery('english', $1) as english_query,
+    '%' || replace(replace(replace($1, '\', '\\'), '%', '\%'), '_', '\_') || '%' as pattern
+),
+
+hits as (
+  select
+    word.id::uuid as id,
+    false as is_paragraph,
+    word.document_id,
+    (
+      ts_rank(
+        word_search_terms(
+          word.source_text, word.simple_phonetics, word.phonemic, word.english_gloss, word.commentary
+        ),
+        search.query,
+        1
+      )
+      + greatest(
+        similarity(word.source_text, $1),
+        similarity(coalesce(word.simple_phonetics, ''), $1)
+      )
+    )::real as score
+  from search
+    cross join word
+  where word_search_terms(
+      word.source_text, word.simple_phonetics, word.phonemic, word.english_gloss, word.commentary
+    ) @@ search.query
+    or word.source_text ilike search.pattern
+    or word.simple_phonetics ilike search.pattern
+
+  union all
+
+  select
+    paragraph.id::uuid as id,
+    true as is_paragraph,
+    document_page.document_id,
+    ts_rank(english_terms(paragraph.english_translation), search.english_query, 1)::real as score
+  from search
+    cross join paragraph
+    inner join document_page on document_page.id = paragraph.page_id
+  where english_terms(paragraph.english_translation) @@ search.english_query
+),
+
+page as (
+  select * from hits
+  where $2::real is null
+    or hits.score < $2
+    or (hits.score = $2 and hits.id > $3::uuid)
+  order by hits.score desc, hits.id
+  limit $4
+),
+
+-- Highlighted text is wrapped in the control characters 2 and 3, which never
+-- appear in transcriptions.
+headline as (
+  select 'HighlightAll=true, StartSel=' || chr(2) || ', StopSel=' || chr(3) as whole,
+    'MaxFragments=2, MaxWords=20, MinWords=5, FragmentDelimiter=" … ", StartSel='
+      || chr(2) || ', StopSel=' || chr(3) as fragments
+)
+
+select
+  page.id as "id!",
+  page.is_paragraph as "is_paragraph!",
+  page.document_id as "document_id!",
+  page.score as "score!",
+  word.source_text as "source_text?",
+  word.simple_phonetics as "simple_phonetics?",
+  word.english_gloss as "english_gloss?",
+  -- Glosses like "catch.animate" are one token to the English parser, so
+  -- highlight a copy split into words. Each punctuation mark becomes a single
+  -- space, which lines up with the original gloss.
+  ts_headline(
+    'english',
+    regexp_replace(word.english_gloss, '[._+()\[\],;:]', ' ', 'g'),
+    search.english_query,
+    headline.whole
+  ) as "english_gloss_headline?",
+  case
+    when english_terms(word.commentary) @@ search.english_query then
+      ts_headline('english', word.commentary, search.english_query, headline.fragments)
+  end as "commentary_headline?",
+  paragraph.english_translation as "translation?",
+  ts_headline(
+    'english', paragraph.english_translation, search.english_query, headline.fragments
+  ) as "translation_headline?",
+  (
+    select count(*) + 1 from paragraph as earlier
+    where earlier.page_id = paragraph.page_id
+      and earlier.character_range < paragraph.character_range
+  ) as "paragraph_index?"
+from page
+  cross join search
+  cross join headline
+  left join word on word.id = page.id and not page.is_paragraph
+  left join paragraph on paragraph.id = page.id and page.is_paragraph
+order by page.score desc, page.id
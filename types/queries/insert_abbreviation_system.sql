@@ -1,5 +1,9 @@
-insert into abbreviation_system (short_name, title)
-values ($1, $2)
-on conflict (short_name) do update set
+insert into abbreviation_system (short_name, title, language_id)
+select $1, $2, language.id
+from language
+where language.name = $3
+order by language.id
+limit 1
+on conflict (language_id, short_name) do update set
 title = excluded.title
 returning id
@@ -1,5 +1,5 @@
-insert into abstract_morpheme_tag (internal_gloss, linguistic_type)
-values ($1, $2)
-on conflict (internal_gloss) do update set
+insert into abstract_morpheme_tag (internal_gloss, linguistic_type, language_id)
+values ($1, $2, tag_language($3))
+on conflict (language_id, internal_gloss) do update set
 linguistic_type = excluded.linguistic_type
 returning id
\ No newline at end of file
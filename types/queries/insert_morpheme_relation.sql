@@ -0,0 +1,12 @@
+-- Links the gloss defined in one document to the gloss defined in another,
+-- replacing the kind of any existing link between them.
+insert into morpheme_gloss_relation (left_gloss_id, right_gloss_id, kind)
+select left_gloss.id, right_gloss.id, $5
+from morpheme_gloss as left_gloss
+  inner join document as left_doc on left_doc.id = left_gloss.document_id
+  cross join morpheme_gloss as right_gloss
+  inner join document as right_doc on right_doc.id = right_gloss.document_id
+where left_doc.short_name = $1 and left_gloss.gloss = $2
+  and right_doc.short_name = $3 and right_gloss.gloss = $4
+on conflict (left_gloss_id, right_gloss_id)
+do update set kind = excluded.kind
@@ -1,11 +1,14 @@
-insert into morpheme_gloss_relation (left_gloss_id, right_gloss_id)
-select
+-- Each pair of glosses can only be linked once, so only one of any duplicate
+-- links is kept.
+insert into morpheme_gloss_relation (left_gloss_id, right_gloss_id, kind)
+select distinct on (left_gloss.id, right_gloss.id)
   left_gloss.id,
-  right_gloss.id
+  right_gloss.id,
+  input_relation.kind
 from
   unnest(
-    $1::text[], $2::text[], $3::text[], $4::text[]
-  ) as input_relation(left_doc_name, left_gloss, right_doc_name, right_gloss)
+    $1::text[], $2::text[], $3::text[], $4::text[], $5::morpheme_relation_kind[]
+  ) as input_relation(left_doc_name, left_gloss, right_doc_name, right_gloss, kind)
   inner join document as left_doc on left_doc.short_name = input_relation.left_doc_name
   inner join
     morpheme_gloss as left_gloss on
@@ -15,4 +18,5 @@ from
   inner join
     morpheme_gloss as right_gloss on
       right_gloss.gloss = input_relation.right_gloss and right_gloss.document_id = right_doc.id
-on conflict do nothing
+on conflict (left_gloss_id, right_gloss_id)
+do update set kind = excluded.kind
@@ -0,0 +1,26 @@
+-- Lists dictionary entries in alphabetical order of their headwords,
+-- optionally only those in one dictionary, with one part of speech, or with
+-- headwords starting with the given text.
+select
+  lexical_entry.id,
+  lexical_entry.gloss_id,
+  morpheme_gloss.gloss,
+  morpheme_gloss.example_shape as shape,
+  lexical_entry.headword,
+  lexical_entry.part_of_speech as "part_of_speech: PartOfSpeech",
+  lexical_entry.senses,
+  lexical_entry.page_number,
+  document.id as document_id,
+  document.short_name as document_name,
+  document.title as document_title,
+  document.written_at as "document_date: Date",
+  document.index_in_group as document_order_index
+from lexical_entry
+  inner join morpheme_gloss on morpheme_gloss.id = lexical_entry.gloss_id
+  inner join document on document.id = morpheme_gloss.document_id
+where ($1::text is null or document.short_name = $1)
+  and ($2::part_of_speech is null or lexical_entry.part_of_speech = $2)
+  and ($3::text is null or lexical_entry.headword ilike $3 || '%')
+order by lower(lexical_entry.headword), morpheme_gloss.gloss, document.short_name
+offset $4
+limit $5
@@ -0,0 +1,21 @@
+-- Finds the entry for the given gloss in the dictionary with the given short
+-- name.
+select
+  lexical_entry.id,
+  lexical_entry.gloss_id,
+  morpheme_gloss.gloss,
+  morpheme_gloss.example_shape as shape,
+  lexical_entry.headword,
+  lexical_entry.part_of_speech as "part_of_speech: PartOfSpeech",
+  lexical_entry.senses,
+  lexical_entry.page_number,
+  document.id as document_id,
+  document.short_name as document_name,
+  document.title as document_title,
+  document.written_at as "document_date: Date",
+  document.index_in_group as document_order_index
+from lexical_entry
+  inner join morpheme_gloss on morpheme_gloss.id = lexical_entry.gloss_id
+  inner join document on document.id = morpheme_gloss.document_id
+where document.short_name = $1
+  and morpheme_gloss.gloss = $2
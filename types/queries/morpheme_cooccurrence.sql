@@ -0,0 +1,25 @@
+-- Counts the words in which each other morpheme appears alongside the morpheme
+-- with the given gloss. Only counts morphemes before it when $2 is true, and
+-- after it when $3 is true, optionally limited to one class of morpheme.
+with target as (
+  select word_segment.word_id, word_segment.index_in_word
+  from word_segment
+    inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
+  where morpheme_gloss.gloss = $1
+)
+
+select
+  other_gloss.gloss,
+  count(distinct other.word_id) as "word_count!",
+  (array_agg(distinct other.word_id))[1:$5] as "example_word_ids!"
+from target
+  inner join word_segment as other on other.word_id = target.word_id
+  inner join morpheme_gloss as other_gloss on other_gloss.id = other.gloss_id
+where other_gloss.gloss != $1
+  and (
+    ($2 and other.index_in_word < target.index_in_word)
+    or ($3 and other.index_in_word > target.index_in_word)
+  )
+  and ($4::word_segment_class is null or other.class = $4)
+group by other_gloss.gloss
+order by "word_count!" desc, other_gloss.gloss
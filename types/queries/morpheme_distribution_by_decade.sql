@@ -0,0 +1,27 @@
+-- Counts the words containing the morpheme with the given gloss in documents
+-- written in each decade, along with how many words were written in that decade.
+with decade_totals as (
+  select
+    (extract(year from document.written_at)::int / 10) * 10 as decade,
+    count(*) as total_words
+  from word
+    inner join document on document.id = word.document_id
+  group by decade
+)
+
+select
+  decade_totals.decade,
+  count(distinct word_segment.word_id) as "word_count!",
+  min(decade_totals.total_words) as "total_words!",
+  (array_agg(distinct word_segment.word_id))[1:$2] as "example_word_ids!"
+from morpheme_gloss
+  inner join word_segment on word_segment.gloss_id = morpheme_gloss.id
+  inner join word on word.id = word_segment.word_id
+  inner join document on document.id = word.document_id
+  inner join
+    decade_totals on
+      decade_totals.decade
+      is not distinct from (extract(year from document.written_at)::int / 10) * 10
+where morpheme_gloss.gloss = $1
+group by decade_totals.decade
+order by decade_totals.decade nulls last
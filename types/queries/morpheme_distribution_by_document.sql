@@ -0,0 +1,17 @@
+-- Counts the words containing the morpheme with the given gloss in each
+-- document, along with how many words each document has in total.
+select
+  document.id as document_id,
+  document.short_name as document_name,
+  count(distinct word_segment.word_id) as "word_count!",
+  (
+    select count(*) from word as all_words where all_words.document_id = document.id
+  ) as "total_words!",
+  (array_agg(distinct word_segment.word_id))[1:$2] as "example_word_ids!"
+from morpheme_gloss
+  inner join word_segment on word_segment.gloss_id = morpheme_gloss.id
+  inner join word on word.id = word_segment.word_id
+  inner join document on document.id = word.document_id
+where morpheme_gloss.gloss = $1
+group by document.id
+order by "word_count!" desc, document.short_name
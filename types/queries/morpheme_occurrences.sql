@@ -0,0 +1,22 @@
+-- Each use of a morpheme with the given gloss, optionally only in the document
+-- with the given short name, along with the glosses of the morphemes right
+-- before and after it in the same word.
+select
+  word_segment.morpheme,
+  word_segment.word_id,
+  preceding_gloss.gloss as "preceding_gloss?",
+  following_gloss.gloss as "following_gloss?"
+from morpheme_gloss
+  left join document on document.id = morpheme_gloss.document_id
+  inner join word_segment on word_segment.gloss_id = morpheme_gloss.id
+  left join word_segment as preceding on
+    preceding.word_id = word_segment.word_id
+    and preceding.index_in_word = word_segment.index_in_word - 1
+  left join morpheme_gloss as preceding_gloss on preceding_gloss.id = preceding.gloss_id
+  left join word_segment as following on
+    following.word_id = word_segment.word_id
+    and following.index_in_word = word_segment.index_in_word + 1
+  left join morpheme_gloss as following_gloss on following_gloss.id = following.gloss_id
+where morpheme_gloss.gloss = $1
+  and ($2::text is null or document.short_name = $2)
+order by word_segment.morpheme
@@ -0,0 +1,7 @@
+-- Selects every link between two of the given morpheme glosses.
+select
+  left_gloss_id,
+  right_gloss_id,
+  kind as "kind: MorphemeRelationKind"
+from morpheme_gloss_relation
+where left_gloss_id = any($1) and right_gloss_id = any($1)
@@ -0,0 +1,15 @@
+-- Known morpheme shapes with one of the given search keys, which are every
+-- substring of the key being segmented, with how often each shape is attested
+-- with each gloss.
+select
+  word_segment.search_key as "search_key!",
+  morpheme_gloss.gloss,
+  mode() within group (order by word_segment.morpheme) as "morpheme!",
+  mode() within group (order by word_segment.role) as "role!: WordSegmentRole",
+  count(*) as "attestations!",
+  (array_agg(word_segment.word_id order by word_segment.word_id))[1:3] as "example_word_ids!"
+from word_segment
+  inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
+where word_segment.search_key = any($1)
+  and morpheme_gloss.gloss not like '%?%'
+group by word_segment.search_key, morpheme_gloss.gloss
@@ -9,7 +9,9 @@ select
   abstract_morpheme_tag.linguistic_type
 from morpheme_gloss
   inner join abstract_morpheme_tag on abstract_morpheme_tag.id = morpheme_gloss.tag_id
-  left join abbreviation_system on abbreviation_system.short_name = any($2)
+  left join abbreviation_system on
+    abbreviation_system.short_name = any($2)
+    and abbreviation_system.language_id = abstract_morpheme_tag.language_id
   inner join morpheme_tag on morpheme_tag.abstract_ids[1] = abstract_morpheme_tag.id
 where morpheme_gloss.id = any($1)
   and morpheme_tag.system_id = abbreviation_system.id
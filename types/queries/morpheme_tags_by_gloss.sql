@@ -1,8 +1,10 @@
+-- Finds the concrete tags for each given abstract gloss and system, within
+-- the tag language of the given document.
 select
-  morpheme_gloss.id as gloss_id,
+  tag_key.document_id,
   morpheme_gloss.example_shape,
   abbreviation_system.short_name as system_name,
-  morpheme_gloss.gloss as abstract_gloss,
+  abstract_morpheme_tag.internal_gloss as abstract_gloss,
   morpheme_tag.gloss as concrete_gloss,
   morpheme_tag.title,
   morpheme_tag.description,
@@ -12,10 +14,17 @@ select
     select abstract_morpheme_tag.internal_gloss
     from unnest(morpheme_tag.abstract_ids) as abstract_id
       inner join abstract_morpheme_tag on abstract_morpheme_tag.id = abstract_id) as internal_tags
-from morpheme_gloss
-  inner join abstract_morpheme_tag on abstract_morpheme_tag.id = morpheme_gloss.tag_id
-  left join abbreviation_system on abbreviation_system.short_name = any($2)
-  inner join morpheme_tag on morpheme_tag.abstract_ids[1] = abstract_morpheme_tag.id
-where morpheme_gloss.gloss = any($1)
-  and morpheme_tag.system_id = abbreviation_system.id
+from unnest($1::text[], $2::text[], $3::uuid[]) as tag_key(gloss, system_name, document_id)
+  inner join abstract_morpheme_tag on
+    abstract_morpheme_tag.internal_gloss = tag_key.gloss
+    and abstract_morpheme_tag.language_id = tag_language(tag_key.document_id)
+  inner join abbreviation_system on
+    abbreviation_system.short_name = tag_key.system_name
+    and abbreviation_system.language_id = abstract_morpheme_tag.language_id
+  inner join morpheme_tag on
+    morpheme_tag.abstract_ids[1] = abstract_morpheme_tag.id
+    and morpheme_tag.system_id = abbreviation_system.id
+  left join morpheme_gloss on
+    morpheme_gloss.document_id is null
+    and morpheme_gloss.tag_id = abstract_morpheme_tag.id
 order by array_length(morpheme_tag.abstract_ids, 1) desc
@@ -0,0 +1,123 @@
+-- Finds Cherokee forms whose English translation matches the given search,
+-- comparing stemmed words so that "catch" matches "catching". Dictionary
+-- entries come first, then glosses of morphemes used in words, then words
+-- grouped by their spelling and translation, then whole paragraphs.
+with search as (
+  select websearch_to_tsquery('english', $1) as query
+),
+
+matches as (
+  select
+    0 as source_rank,
+    lexical_entry.headword as cherokee,
+    morpheme_gloss.example_shape as romanized,
+    coalesce(nullif(array_to_string(lexical_entry.senses, '; '), ''), morpheme_gloss.gloss)
+      as english,
+    ts_rank(
+      english_terms(variadic lexical_entry.senses || morpheme_gloss.gloss), search.query, 2
+    ) as score,
+    (
+      select count(distinct word_segment.word_id)
+      from word_segment
+        inner join word on word.id = word_segment.word_id
+        inner join document on document.id = word.document_id
+      where not document.is_reference
+        and (
+          word_segment.gloss_id = lexical_entry.gloss_id
+          or word_segment.gloss_id in (
+            select right_gloss_id from morpheme_gloss_relation
+            where left_gloss_id = lexical_entry.gloss_id
+            union
+            select left_gloss_id from morpheme_gloss_relation
+            where right_gloss_id = lexical_entry.gloss_id
+          )
+        )
+    ) as attestation_count,
+    dictionary.short_name || ':' || morpheme_gloss.gloss as morpheme_id,
+    array[dictionary.id::uuid] as document_ids
+  from search
+    cross join lexical_entry
+    inner join morpheme_gloss on morpheme_gloss.id = lexical_entry.gloss_id
+    inner join document as dictionary on dictionary.id = morpheme_gloss.document_id
+  where english_terms(variadic lexical_entry.senses) @@ search.query
+    or english_terms(morpheme_gloss.gloss) @@ search.query
+
+  union all
+
+  -- Glosses of lexical morphemes that aren't already described by an entry.
+  select
+    1 as source_rank,
+    mode() within group (order by word_segment.morpheme) as cherokee,
+    morpheme_gloss.example_shape as romanized,
+    morpheme_gloss.gloss as english,
+    ts_rank(english_terms(morpheme_gloss.gloss), search.query, 2) as score,
+    count(distinct word_segment.word_id) as attestation_count,
+    coalesce(gloss_document.short_name || ':', '') || morpheme_gloss.gloss as morpheme_id,
+    array_agg(distinct word.document_id) as document_ids
+  from search
+    cross join morpheme_gloss
+    left join document as gloss_document on gloss_document.id = morpheme_gloss.document_id
+    inner join word_segment on word_segment.gloss_id = morpheme_gloss.id
+    inner join word on word.id = word_segment.word_id
+  where english_terms(morpheme_gloss.gloss) @@ search.query
+    and morpheme_gloss.tag_id is null
+    and not exists (
+      select from lexical_entry where lexical_entry.gloss_id = morpheme_gloss.id
+    )
+  group by morpheme_gloss.id, gloss_document.short_name, search.query
+
+  union all
+
+  select
+    2 as source_rank,
+    word.source_text as cherokee,
+    max(coalesce(word.simple_phonetics, word.phonemic)) as romanized,
+    word.english_gloss as english,
+    ts_rank(english_terms(word.english_gloss), search.query, 2) as score,
+    count(*) as attestation_count,
+    null as morpheme_id,
+    array_agg(distinct word.document_id) as document_ids
+  from search
+    cross join word
+    inner join document on document.id = word.document_id
+  -- Dictionary headwords are already matched as entries above.
+  where english_terms(word.english_gloss) @@ search.query
+    and not document.is_reference
+  group by word.source_text, word.english_gloss, search.query
+
+  union all
+
+  select
+    3 as source_rank,
+    (
+      select string_agg(word.source_text, ' ' order by word.character_range)
+      from word
+      where word.page_id = paragraph.page_id
+        and word.character_range && paragraph.character_range
+        and word.character_range &> paragraph.character_range
+    ) as cherokee,
+    null as romanized,
+    paragraph.english_translation as english,
+    ts_rank(english_terms(paragraph.english_translation), search.query, 2) as score,
+    1 as attestation_count,
+    null as morpheme_id,
+    array[document_page.document_id] as document_ids
+  from search
+    cross join paragraph
+    inner join document_page on document_page.id = paragraph.page_id
+  where english_terms(paragraph.english_translation) @@ search.query
+)
+
+select
+  source_rank as "source_rank!",
+  cherokee as "cherokee!",
+  romanized,
+  english as "english!",
+  score as "score!",
+  attestation_count as "attestation_count!",
+  morpheme_id,
+  document_ids as "document_ids!"
+from matches
+where cherokee is not null
+order by source_rank, score desc, attestation_count desc, cherokee
+limit $2
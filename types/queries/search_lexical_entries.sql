@@ -0,0 +1,36 @@
+-- Finds dictionary entries whose headword, gloss, shape, or senses contain the
+-- given text. Exact matches of the headword or gloss come first, followed by
+-- those starting with the text.
+select
+  lexical_entry.id,
+  lexical_entry.gloss_id,
+  morpheme_gloss.gloss,
+  morpheme_gloss.example_shape as shape,
+  lexical_entry.headword,
+  lexical_entry.part_of_speech as "part_of_speech: PartOfSpeech",
+  lexical_entry.senses,
+  lexical_entry.page_number,
+  document.id as document_id,
+  document.short_name as document_name,
+  document.title as document_title,
+  document.written_at as "document_date: Date",
+  document.index_in_group as document_order_index
+from lexical_entry
+  inner join morpheme_gloss on morpheme_gloss.id = lexical_entry.gloss_id
+  inner join document on document.id = morpheme_gloss.document_id
+where lexical_entry.headword ilike '%' || $1 || '%'
+  or morpheme_gloss.gloss ilike '%' || $1 || '%'
+  or morpheme_gloss.example_shape ilike '%' || $1 || '%'
+  or exists (
+    select from unnest(lexical_entry.senses) as sense
+    where sense ilike '%' || $1 || '%'
+  )
+order by
+  case
+    when lower(lexical_entry.headword) = lower($1) or lower(morpheme_gloss.gloss) = lower($1) then 0
+    when lexical_entry.headword ilike $1 || '%' or morpheme_gloss.gloss ilike $1 || '%' then 1
+    else 2
+  end,
+  lower(lexical_entry.headword),
+  document.short_name
+limit $2
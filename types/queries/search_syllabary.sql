@@ -22,4 +22,4 @@ from word
   left join media_resource on media_resource.id = media_slice.resource_id
   left join dailp_user contributor on contributor.id = media_resource.recorded_by
   left join dailp_user editor on editor.id = word.audio_edited_by
-where source_text like any($1)
+where source_text = any($1)
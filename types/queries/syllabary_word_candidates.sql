@@ -0,0 +1,5 @@
+-- Distinct source strings matching a pattern that every near match of a
+-- syllabary search must contain, which can use the trigram index on
+-- `word.source_text`.
+select distinct source_text from word
+where source_text ~ $1
@@ -0,0 +1,13 @@
+-- Positions of the given glosses that aren't defined in the named document.
+select input.position as "position!"
+from
+  unnest($1::text[], $2::text[]) with ordinality
+    as input(doc_name, gloss, position)
+where not exists (
+  select 1
+  from morpheme_gloss
+    inner join document on document.id = morpheme_gloss.document_id
+  where document.short_name = input.doc_name
+    and morpheme_gloss.gloss = input.gloss
+)
+order by input.position
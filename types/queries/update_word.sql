@@ -18,6 +18,13 @@ update word set
         case
             when $5::text[] != '{}' and $5[1] is not null then $5[1]
             else english_gloss
+        end,
+    -- The search key comes from the phonemic layer when there is one, which
+    -- this query never changes.
+    search_key =
+        case
+            when phonemic is null and $3::text[] != '{}' and $3[1] is not null then $6
+            else search_key
         end
 
 where id = $1
@@ -0,0 +1,3 @@
+update word set search_key = input_data.search_key
+from unnest($1::uuid[], $2::text[]) as input_data(id, search_key)
+where word.id = input_data.id
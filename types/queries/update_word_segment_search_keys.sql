@@ -0,0 +1,4 @@
+update word_segment set search_key = input_data.search_key
+from unnest($1::uuid[], $2::bigint[], $3::text[]) as input_data(word_id, index_in_word, search_key)
+where word_segment.word_id = input_data.word_id
+  and word_segment.index_in_word = input_data.index_in_word
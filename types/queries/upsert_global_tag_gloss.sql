@@ -0,0 +1,11 @@
+-- Gives an abstract tag the gloss that raw annotations use for it in any
+-- document. Languages may share a gloss, which keeps pointing at the tag of
+-- the language that defined it first.
+insert into morpheme_gloss (document_id, gloss, example_shape, tag_id)
+values (null, $1, null, $2)
+on conflict (coalesce(document_id, uuid_nil()), gloss)
+do update set example_shape = excluded.example_shape,
+tag_id = excluded.tag_id
+where morpheme_gloss.tag_id is null
+  or (select language_id from abstract_morpheme_tag where id = morpheme_gloss.tag_id)
+  = (select language_id from abstract_morpheme_tag where id = excluded.tag_id)
@@ -0,0 +1,26 @@
+-- Describes the dictionary entry for each of the given glosses, which must
+-- already be defined in the given document. Senses are separated by newlines.
+insert into lexical_entry (
+  gloss_id, headword, part_of_speech, senses, page_number, index_in_document
+)
+select
+  morpheme_gloss.id,
+  input_entry.headword,
+  input_entry.part_of_speech,
+  string_to_array(input_entry.senses, E'\n'),
+  input_entry.page_number,
+  input_entry.index
+from
+  unnest(
+    $2::text[], $3::text[], $4::part_of_speech[], $5::text[], $6::text[], $7::bigint[]
+  ) as input_entry(gloss, headword, part_of_speech, senses, page_number, index)
+  inner join
+    morpheme_gloss on
+      morpheme_gloss.document_id = $1 and morpheme_gloss.gloss = input_entry.gloss
+on conflict (gloss_id)
+do update set
+headword = excluded.headword,
+part_of_speech = excluded.part_of_speech,
+senses = excluded.senses,
+page_number = excluded.page_number,
+index_in_document = excluded.index_in_document
@@ -1,15 +1,33 @@
-insert into word_segment (gloss_id, word_id, index_in_word, morpheme, role)
+insert into word_segment (gloss_id, word_id, index_in_word, morpheme, role, search_key, class)
 -- Fill in glosses that weren't inserted with their global match.
 select
   coalesce(inserted_gloss.id, global_gloss.id),
   word_id,
   index,
   morpheme,
-  role
+  role,
+  search_key,
+  -- Classify segments from the tag inventory unless told otherwise. Anything
+  -- that isn't a known tag is lexical, unless it hasn't been identified yet.
+  coalesce(
+    input_data.class,
+    (
+      select
+        case
+          when abstract_morpheme_tag.linguistic_type ilike '%clitic%' then 'Clitic'
+          else 'Affix'
+        end::word_segment_class
+      from abstract_morpheme_tag
+      where abstract_morpheme_tag.internal_gloss = input_data.gloss
+        and abstract_morpheme_tag.language_id = tag_language(input_data.document_id)
+    ),
+    case when input_data.gloss like '%?%' then null else 'Root'::word_segment_class end
+  )
 from
   unnest(
-    $1::uuid[], $2::text[], $3::uuid[], $4::bigint[], $5::text[], $6::word_segment_role[]
-  ) as input_data(document_id, gloss, word_id, index, morpheme, role)
+    $1::uuid[], $2::text[], $3::uuid[], $4::bigint[], $5::text[], $6::word_segment_role[], $7::text[],
+    $8::word_segment_class[]
+  ) as input_data(document_id, gloss, word_id, index, morpheme, role, search_key, class)
   left join
     morpheme_gloss as inserted_gloss on
       inserted_gloss.document_id = input_data.document_id and inserted_gloss.gloss = input_data.gloss
@@ -20,4 +38,6 @@ on conflict (word_id, index_in_word)
 do update set
 morpheme = excluded.morpheme,
 gloss_id = excluded.gloss_id,
-role = excluded.role
+role = excluded.role,
+search_key = excluded.search_key,
+class = excluded.class
@@ -1,5 +1,9 @@
-insert into abstract_morpheme_tag (internal_gloss, linguistic_type)
-values ($1, $2)
-on conflict (internal_gloss) do update set
+insert into abstract_morpheme_tag (internal_gloss, linguistic_type, language_id)
+select $1, $2, language.id
+from language
+where language.name = $3
+order by language.id
+limit 1
+on conflict (language_id, internal_gloss) do update set
 linguistic_type = excluded.linguistic_type
 returning id
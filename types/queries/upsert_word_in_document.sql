@@ -16,8 +16,9 @@ inserted_audio_slice as (
 
 insert into word (
   source_text, simple_phonetics, phonemic, english_gloss, recorded_at, commentary,
-  document_id, page_number, index_in_document, page_id, character_range, audio_slice_id)
-select $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, inserted_audio_slice.id
+  document_id, page_number, index_in_document, page_id, character_range, audio_slice_id,
+  search_key)
+select $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, inserted_audio_slice.id, $15
 from (values (1)) as t
   left join inserted_audio_slice on true
 returning id
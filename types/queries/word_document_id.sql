@@ -0,0 +1,3 @@
+select document_id
+from word
+where id = $1
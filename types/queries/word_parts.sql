@@ -4,7 +4,8 @@ select
   word_segment.morpheme,
   word_segment.gloss_id,
   morpheme_gloss.gloss,
-  word_segment.role as "role: WordSegmentRole"
+  word_segment.role as "role: WordSegmentRole",
+  word_segment.class as "class: WordSegmentClass"
 from word_segment
   inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id
 where word_segment.word_id = any($1)
@@ -0,0 +1,4 @@
+-- Words that don't have a search key yet.
+select id, simple_phonetics, phonemic
+from word
+where search_key is null
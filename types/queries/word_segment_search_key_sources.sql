@@ -0,0 +1,4 @@
+-- Word segments that don't have a search key yet.
+select word_id, index_in_word, morpheme
+from word_segment
+where search_key is null
@@ -0,0 +1,578 @@
+//! A small query language for finding words by their text, analysis, and
+//! document, like `gloss:catch doc:DF1975 year:1800..1850 has:audio -unresolved`.
+//!
+//! A query is a list of terms separated by spaces, all of which must match.
+//! Each term is either a `field:value` filter or plain text to look for in
+//! any field of a word, except for the bare keyword `unresolved`, which
+//! matches words with a segment that hasn't been identified yet. Starting a
+//! term with "-" excludes words that match it instead, and quotes keep spaces
+//! within a single term, like `commentary:"not sure"`.
+
+use crate::MorphemeId;
+use nom::{
+    branch::alt,
+    bytes::complete::is_not,
+    character::complete::char,
+    combinator::{opt, recognize},
+    multi::many1_count,
+    sequence::delimited,
+    IResult,
+};
+use sqlx::{Postgres, QueryBuilder};
+use std::fmt;
+
+/// Fields that a search term can filter on, as written before the colon.
+const FIELDS: &[&str] = &[
+    "source",
+    "phonetics",
+    "english",
+    "commentary",
+    "gloss",
+    "morpheme",
+    "doc",
+    "collection",
+    "genre",
+    "keyword",
+    "year",
+    "has",
+];
+
+/// Values allowed in a `has:` term.
+const FEATURES: &[&str] = &["audio", "commentary", "english", "segments"];
+
+/// Something that a word may or may not have.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WordFeature {
+    /// An audio recording, either from the edition or contributed by a user
+    Audio,
+    /// Commentary from an editor
+    Commentary,
+    /// An English gloss
+    English,
+    /// A morphemic segmentation
+    Segments,
+}
+
+/// A condition that a word must meet to match a search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SearchFilter {
+    /// Any of the syllabary, simple phonetics, English gloss, or commentary
+    /// contains this text.
+    Text(String),
+    /// The syllabary source contains this text.
+    Source(String),
+    /// The simple phonetics contain this text.
+    Phonetics(String),
+    /// The English gloss contains this word, in any form.
+    English(String),
+    /// The commentary contains this text.
+    Commentary(String),
+    /// One of the word's segments has this morpheme gloss, optionally one
+    /// defined by a particular document.
+    Gloss(MorphemeId),
+    /// One of the word's segments has exactly this shape.
+    Morpheme(String),
+    /// The word is in the document with this short name.
+    Document(String),
+    /// The word is in a document in the collection with this slug.
+    Collection(String),
+    /// The word's document has this genre.
+    Genre(String),
+    /// The word's document has this keyword.
+    Keyword(String),
+    /// The word's document was written within these years, inclusive.
+    Year(Option<i32>, Option<i32>),
+    /// The word has this feature.
+    Has(WordFeature),
+    /// The word has a "?" in one of its morphemes or glosses, or in its
+    /// source text if it has no segments, like [`crate::AnnotatedForm::is_unresolved`].
+    Unresolved,
+}
+
+/// One term of a search, which words must match, or must not match if the
+/// term is negated.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchTerm {
+    /// Whether the term started with "-", excluding words that match it
+    pub negated: bool,
+    /// Condition to check against each word
+    pub filter: SearchFilter,
+}
+
+/// A parsed advanced search query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdvancedSearch {
+    /// Terms that words must match, all of which apply at once
+    pub terms: Vec<SearchTerm>,
+}
+
+/// What was wrong with a token of an advanced search query.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchSyntaxProblem {
+    /// A quote was opened but never closed.
+    UnclosedQuote,
+    /// A "-" wasn't followed by anything to exclude.
+    MissingTerm,
+    /// The part before the colon isn't a field we can filter on.
+    UnknownField,
+    /// Nothing was written after the colon.
+    MissingValue,
+    /// A `gloss:` value couldn't be read as a morpheme gloss.
+    InvalidGloss,
+    /// A year or range of years couldn't be read, or named a year before 1
+    /// or after 9999.
+    InvalidYear,
+    /// A `has:` term named something that words can't have.
+    UnknownFeature,
+}
+
+/// An advanced search query that couldn't be read, pointing at the token
+/// that caused the problem. Offsets count characters from the start of the
+/// query, starting at zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchSyntaxError {
+    /// What was wrong with the token
+    pub problem: SearchSyntaxProblem,
+    /// The token that couldn't be read, exactly as written
+    pub token: String,
+    /// Where the token starts
+    pub offset: usize,
+}
+
+impl SearchSyntaxError {
+    /// Number of characters in the token that couldn't be read.
+    pub fn length(&self) -> usize {
+        self.token.chars().count()
+    }
+}
+
+impl fmt::Display for SearchSyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let column = self.offset + 1;
+        match self.problem {
+            SearchSyntaxProblem::UnclosedQuote => {
+                write!(f, "Unclosed quote at character {}", column)
+            }
+            SearchSyntaxProblem::MissingTerm => {
+                write!(f, "Expected a term to exclude after \"-\" at character {}", column)
+            }
+            SearchSyntaxProblem::UnknownField => write!(
+                f,
+                "Unknown field \"{}\" at character {}, expected one of {}",
+                self.token,
+                column,
+                FIELDS.join(", ")
+            ),
+            SearchSyntaxProblem::MissingValue => {
+                write!(f, "Expected a value after \"{}\" at character {}", self.token, column)
+            }
+            SearchSyntaxProblem::InvalidGloss => write!(
+                f,
+                "Invalid gloss \"{}\" at character {}, expected a gloss like catch or DF1975:catch",
+                self.token, column
+            ),
+            SearchSyntaxProblem::InvalidYear => write!(
+                f,
+                "Invalid year \"{}\" at character {}, expected a year from {} to {} like 1835 or a range like 1800..1850",
+                self.token, column, MIN_YEAR, MAX_YEAR
+            ),
+            SearchSyntaxProblem::UnknownFeature => write!(
+                f,
+                "Unknown feature \"{}\" at character {}, expected one of {}",
+                self.token,
+                column,
+                FEATURES.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SearchSyntaxError {}
+
+/// A run of quoted text, which may contain spaces.
+fn quoted(input: &str) -> IResult<&str, &str> {
+    recognize(delimited(char('"'), opt(is_not("\"")), char('"')))(input)
+}
+
+/// A single term, running until the next space outside of quotes.
+fn token(input: &str) -> IResult<&str, &str> {
+    recognize(many1_count(alt((quoted, is_not(" \t\r\n\"")))))(input)
+}
+
+impl AdvancedSearch {
+    /// Read a query, or point out the first token that couldn't be read.
+    pub fn parse(input: &str) -> Result<Self, SearchSyntaxError> {
+        let mut terms = Vec::new();
+        let mut rest = input.trim_start();
+        while !rest.is_empty() {
+            let unclosed_quote = |rest: &str| SearchSyntaxError {
+                problem: SearchSyntaxProblem::UnclosedQuote,
+                token: rest.to_owned(),
+                offset: input[..input.len() - rest.len()].chars().count(),
+            };
+            let (next, text) = token(rest).map_err(|_| unclosed_quote(rest))?;
+            // Tokens only stop early at a quote if it's never closed.
+            if next.starts_with('"') {
+                return Err(unclosed_quote(next));
+            }
+            terms.push(parse_term(input, input.len() - rest.len(), text)?);
+            rest = next.trim_start();
+        }
+        Ok(Self { terms })
+    }
+
+    /// Build a query for the IDs of matching words, ordered by document and
+    /// position within it. Every value from the search is passed as a bound
+    /// parameter.
+    pub(crate) fn word_ids_query(&self, offset: i64, limit: i64) -> QueryBuilder<'_, Postgres> {
+        let mut builder = QueryBuilder::new(
+            "select word.id::uuid from word inner join document on document.id = word.document_id where true",
+        );
+        for term in &self.terms {
+            // Conditions on missing fields are null, which should count as
+            // not matching, even when the term is negated.
+            builder.push(if term.negated {
+                " and not coalesce(("
+            } else {
+                " and coalesce(("
+            });
+            push_filter(&mut builder, &term.filter);
+            builder.push("), false)");
+        }
+        builder.push(" order by document.short_name, word.index_in_document offset ");
+        builder.push_bind(offset);
+        builder.push(" limit ");
+        builder.push_bind(limit);
+        builder
+    }
+}
+
+/// Read the token starting at byte `start` of the whole query.
+fn parse_term(input: &str, start: usize, text: &str) -> Result<SearchTerm, SearchSyntaxError> {
+    let error = |problem, from: usize, token: &str| SearchSyntaxError {
+        problem,
+        token: token.to_owned(),
+        offset: input[..start + from].chars().count(),
+    };
+    let (negated, body, body_start) = match text.strip_prefix('-') {
+        Some("") => return Err(error(SearchSyntaxProblem::MissingTerm, 0, text)),
+        Some(body) => (true, body, 1),
+        None => (false, text, 0),
+    };
+    // A colon inside quotes is part of the text rather than a field name.
+    let field_end = body
+        .find([':', '"'])
+        .filter(|&idx| body[idx..].starts_with(':'));
+    let filter = match field_end {
+        None if body.eq_ignore_ascii_case("unresolved") => SearchFilter::Unresolved,
+        None => SearchFilter::Text(unquote(body)),
+        Some(colon) => {
+            let field = &body[..colon];
+            let raw_value = &body[colon + 1..];
+            let value_start = body_start + colon + 1;
+            let value = unquote(raw_value);
+            if !FIELDS.contains(&&*field.to_lowercase()) {
+                return Err(error(SearchSyntaxProblem::UnknownField, body_start, field));
+            }
+            if value.trim().is_empty() {
+                return Err(error(SearchSyntaxProblem::MissingValue, 0, text));
+            }
+            match &*field.to_lowercase() {
+                "source" => SearchFilter::Source(value),
+                "phonetics" => SearchFilter::Phonetics(value),
+                "english" => SearchFilter::English(value),
+                "commentary" => SearchFilter::Commentary(value),
+                "gloss" => SearchFilter::Gloss(MorphemeId::parse(&value).ok_or_else(|| {
+                    error(SearchSyntaxProblem::InvalidGloss, value_start, raw_value)
+                })?),
+                "morpheme" => SearchFilter::Morpheme(value),
+                "doc" => SearchFilter::Document(value),
+                "collection" => SearchFilter::Collection(value),
+                "genre" => SearchFilter::Genre(value),
+                "keyword" => SearchFilter::Keyword(value),
+                "year" => {
+                    let (from, to) = parse_years(&value).ok_or_else(|| {
+                        error(SearchSyntaxProblem::InvalidYear, value_start, raw_value)
+                    })?;
+                    SearchFilter::Year(from, to)
+                }
+                "has" => SearchFilter::Has(match &*value.to_lowercase() {
+                    "audio" => WordFeature::Audio,
+                    "commentary" => WordFeature::Commentary,
+                    "english" => WordFeature::English,
+                    "segments" => WordFeature::Segments,
+                    _ => {
+                        return Err(error(
+                            SearchSyntaxProblem::UnknownFeature,
+                            value_start,
+                            raw_value,
+                        ))
+                    }
+                }),
+                _ => unreachable!("every field is listed in FIELDS"),
+            }
+        }
+    };
+    Ok(SearchTerm { negated, filter })
+}
+
+/// Remove the quotes from a value, keeping the text between them.
+fn unquote(value: &str) -> String {
+    value.replace('"', "")
+}
+
+/// Earliest year that a `year:` term can name.
+const MIN_YEAR: i32 = 1;
+/// Latest year that a `year:` term can name, which keeps the dates built from
+/// it within what the database can represent.
+const MAX_YEAR: i32 = 9999;
+
+/// Read a single year like "1835", or an inclusive range like "1800..1850",
+/// "1800..", or "..1850".
+fn parse_years(value: &str) -> Option<(Option<i32>, Option<i32>)> {
+    let year = |s: &str| -> Option<i32> {
+        s.parse()
+            .ok()
+            .filter(|year| (MIN_YEAR..=MAX_YEAR).contains(year))
+    };
+    let bound = |s: &str| -> Option<Option<i32>> {
+        if s.is_empty() {
+            Some(None)
+        } else {
+            year(s).map(Some)
+        }
+    };
+    let (from, to) = match value.split_once("..") {
+        Some((from, to)) => (bound(from)?, bound(to)?),
+        None => {
+            let single = Some(year(value)?);
+            (single, single)
+        }
+    };
+    match (from, to) {
+        (None, None) => None,
+        (Some(from), Some(to)) if from > to => None,
+        range => Some(range),
+    }
+}
+
+/// Escape a value for use within an `ilike` pattern, matching it anywhere.
+fn contains_pattern(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+fn push_filter<'a>(builder: &mut QueryBuilder<'a, Postgres>, filter: &'a SearchFilter) {
+    match filter {
+        SearchFilter::Text(text) => {
+            let pattern = contains_pattern(text);
+            builder.push("word.source_text ilike ");
+            builder.push_bind(pattern.clone());
+            builder.push(" or word.simple_phonetics ilike ");
+            builder.push_bind(pattern.clone());
+            builder.push(" or word.english_gloss ilike ");
+            builder.push_bind(pattern.clone());
+            builder.push(" or word.commentary ilike ");
+            builder.push_bind(pattern);
+        }
+        SearchFilter::Source(text) => {
+            builder.push("word.source_text ilike ");
+            builder.push_bind(contains_pattern(text));
+        }
+        SearchFilter::Phonetics(text) => {
+            builder.push("word.simple_phonetics ilike ");
+            builder.push_bind(contains_pattern(text));
+        }
+        SearchFilter::English(text) => {
+            builder.push("english_terms(word.english_gloss) @@ plainto_tsquery('english', ");
+            builder.push_bind(text);
+            builder.push(")");
+        }
+        SearchFilter::Commentary(text) => {
+            builder.push("word.commentary ilike ");
+            builder.push_bind(contains_pattern(text));
+        }
+        SearchFilter::Gloss(id) => {
+            builder.push(
+                "exists (select from word_segment \
+                 inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id \
+                 left join document as gloss_document on gloss_document.id = morpheme_gloss.document_id \
+                 where word_segment.word_id = word.id and morpheme_gloss.gloss = ",
+            );
+            builder.push_bind(&id.gloss);
+            if let Some(document_name) = &id.document_name {
+                builder.push(" and gloss_document.short_name = ");
+                builder.push_bind(document_name);
+            }
+            builder.push(")");
+        }
+        SearchFilter::Morpheme(shape) => {
+            builder.push(
+                "exists (select from word_segment where word_segment.word_id = word.id \
+                 and lower(word_segment.morpheme) = lower(",
+            );
+            builder.push_bind(shape);
+            builder.push("))");
+        }
+        SearchFilter::Document(name) => {
+            builder.push("lower(document.short_name) = lower(");
+            builder.push_bind(name);
+            builder.push(")");
+        }
+        SearchFilter::Collection(slug) => {
+            builder.push(
+                "exists (select from document_group where document_group.id = document.group_id \
+                 and lower(document_group.slug) = lower(",
+            );
+            builder.push_bind(slug);
+            builder.push("))");
+        }
+        SearchFilter::Genre(name) => {
+            builder.push(
+                "exists (select from genre where genre.id = document.genre_id \
+                 and lower(genre.name) = lower(",
+            );
+            builder.push_bind(name);
+            builder.push("))");
+        }
+        SearchFilter::Keyword(name) => {
+            builder.push(
+                "exists (select from document_keyword \
+                 inner join keyword on keyword.id = document_keyword.keyword_id \
+                 where document_keyword.document_id = document.id \
+                 and lower(keyword.name) = lower(",
+            );
+            builder.push_bind(name);
+            builder.push("))");
+        }
+        SearchFilter::Year(from, to) => {
+            builder.push("true");
+            if let Some(from) = from {
+                builder.push(" and document.written_at >= make_date(");
+                builder.push_bind(*from);
+                builder.push(", 1, 1)");
+            }
+            if let Some(to) = to {
+                builder.push(" and document.written_at < make_date(");
+                builder.push_bind(*to);
+                builder.push(" + 1, 1, 1)");
+            }
+        }
+        SearchFilter::Has(WordFeature::Audio) => {
+            builder.push(
+                "word.audio_slice_id is not null \
+                 or exists (select from word_user_media where word_user_media.word_id = word.id)",
+            );
+        }
+        SearchFilter::Has(WordFeature::Commentary) => {
+            builder.push("trim(word.commentary) <> ''");
+        }
+        SearchFilter::Has(WordFeature::English) => {
+            builder.push("trim(word.english_gloss) <> ''");
+        }
+        SearchFilter::Has(WordFeature::Segments) => {
+            builder.push("exists (select from word_segment where word_segment.word_id = word.id)");
+        }
+        SearchFilter::Unresolved => {
+            builder.push(
+                "case when exists (select from word_segment where word_segment.word_id = word.id) \
+                 then exists (select from word_segment \
+                 inner join morpheme_gloss on morpheme_gloss.id = word_segment.gloss_id \
+                 where word_segment.word_id = word.id \
+                 and (word_segment.morpheme like '%?%' or morpheme_gloss.gloss like '%?%')) \
+                 else word.source_text like '%?%' end",
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_query() {
+        let search =
+            AdvancedSearch::parse("gloss:DF1975:catch  year:1800..1850 has:audio -unresolved")
+                .unwrap();
+        assert_eq!(
+            search.terms,
+            vec![
+                SearchTerm {
+                    negated: false,
+                    filter: SearchFilter::Gloss(MorphemeId::new(
+                        Some("DF1975".to_owned()),
+                        None,
+                        "catch".to_owned()
+                    )),
+                },
+                SearchTerm {
+                    negated: false,
+                    filter: SearchFilter::Year(Some(1800), Some(1850)),
+                },
+                SearchTerm {
+                    negated: false,
+                    filter: SearchFilter::Has(WordFeature::Audio),
+                },
+                SearchTerm {
+                    negated: true,
+                    filter: SearchFilter::Unresolved,
+                },
+            ]
+        );
+        // Quoting the keyword searches for the text instead.
+        assert_eq!(
+            AdvancedSearch::parse("\"unresolved\"").unwrap().terms[0].filter,
+            SearchFilter::Text("unresolved".to_owned())
+        );
+        let quoted = AdvancedSearch::parse("-commentary:\"not: sure\" \"a: b\"").unwrap();
+        assert_eq!(
+            quoted.terms[0].filter,
+            SearchFilter::Commentary("not: sure".to_owned())
+        );
+        assert!(quoted.terms[0].negated);
+        assert_eq!(
+            quoted.terms[1].filter,
+            SearchFilter::Text("a: b".to_owned())
+        );
+
+        let sql = search.word_ids_query(0, 10).into_sql();
+        assert!(sql.contains("gloss_document.short_name = $2"));
+        assert!(sql.contains("and not coalesce((case when exists"));
+    }
+
+    #[test]
+    fn point_at_bad_tokens() {
+        let error = |query| AdvancedSearch::parse(query).unwrap_err();
+
+        let unknown = error("doc:DF1975 yr:1800");
+        assert_eq!(unknown.problem, SearchSyntaxProblem::UnknownField);
+        assert_eq!((unknown.offset, unknown.token.as_str()), (11, "yr"));
+
+        let year = error("ᏥᏃᎦ -year:1850..1800");
+        assert_eq!(year.problem, SearchSyntaxProblem::InvalidYear);
+        assert_eq!((year.offset, year.length()), (10, 10));
+
+        let feature = error("has:video");
+        assert_eq!(feature.problem, SearchSyntaxProblem::UnknownFeature);
+        assert_eq!(feature.offset, 4);
+
+        let quote = error("gloss:catch commentary:\"not sure");
+        assert_eq!(quote.problem, SearchSyntaxProblem::UnclosedQuote);
+        assert_eq!(quote.offset, 23);
+
+        let years = error("year:..2147483647");
+        assert_eq!(years.problem, SearchSyntaxProblem::InvalidYear);
+        assert_eq!(error("year:0").problem, SearchSyntaxProblem::InvalidYear);
+
+        let gloss = error("english:catch gloss:\"catch\nthis\"");
+        assert_eq!(gloss.problem, SearchSyntaxProblem::InvalidGloss);
+        assert_eq!((gloss.offset, gloss.length()), (20, 12));
+
+        assert_eq!(error("doc:").problem, SearchSyntaxProblem::MissingValue);
+        assert_eq!(error("a - b").offset, 2);
+    }
+}
@@ -0,0 +1,260 @@
+//! Group the shapes of a morpheme into clusters of similar sounding variants,
+//! which are likely allomorphs of each other.
+
+use crate::{CherokeeOrthography, PhonemicString};
+use itertools::Itertools;
+use sqlx::types::Uuid;
+use std::collections::HashMap;
+
+/// One use of a morpheme in a word, along with its neighbors.
+#[derive(Clone, Debug)]
+pub struct MorphemeOccurrence {
+    /// Shape of the morpheme in DAILP internal orthography
+    pub shape: String,
+    /// Word containing this morpheme
+    pub word_id: Uuid,
+    /// Gloss of the morpheme right before this one in the word
+    pub preceding_gloss: Option<String>,
+    /// Gloss of the morpheme right after this one in the word
+    pub following_gloss: Option<String>,
+}
+
+/// How often a neighboring morpheme appears next to the variants of a cluster.
+#[derive(async_graphql::SimpleObject, Clone, Debug, PartialEq, Eq)]
+pub struct MorphemeContext {
+    /// Gloss of the neighboring morpheme, missing at the edge of a word
+    pub gloss: Option<String>,
+    /// Number of occurrences next to this morpheme
+    pub count: i64,
+}
+
+/// One distinct shape of a morpheme.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct AllomorphVariant {
+    /// Shape of the morpheme, written in the orthography shapes were compared in
+    pub shape: String,
+    /// Number of occurrences of this shape
+    pub count: i64,
+    /// A few of the words containing this shape
+    pub example_word_ids: Vec<Uuid>,
+}
+
+/// Shapes of a morpheme that sound alike, which are likely allomorphs of each
+/// other.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct AllomorphCluster {
+    /// Most common shape in this cluster
+    pub representative: String,
+    /// Every shape in this cluster, most common first
+    pub variants: Vec<AllomorphVariant>,
+    /// Number of occurrences of all shapes in this cluster
+    pub total_count: i64,
+    /// Most common morphemes right before this one, with `gloss` missing for
+    /// the start of a word
+    pub preceding: Vec<MorphemeContext>,
+    /// Most common morphemes right after this one, with `gloss` missing for
+    /// the end of a word
+    pub following: Vec<MorphemeContext>,
+}
+
+impl AllomorphCluster {
+    /// Group the given occurrences of a morpheme by shape, then link together
+    /// shapes that are at most `max_distance` consonant clusters or vowels
+    /// apart. Shapes are compared in the given orthography, so a system that
+    /// doesn't write tone puts variants differing only by tone together.
+    pub fn build_all(
+        occurrences: Vec<MorphemeOccurrence>,
+        compare_by: Option<CherokeeOrthography>,
+        max_distance: usize,
+        example_count: usize,
+    ) -> Vec<Self> {
+        let occurrences: Vec<_> = occurrences
+            .into_iter()
+            .map(|o| {
+                let shape = match compare_by {
+                    Some(system) => system.convert(&o.shape),
+                    None => o.shape.clone(),
+                };
+                (shape, o)
+            })
+            .collect();
+
+        // Most common shapes first, so that they represent their cluster.
+        let shapes: Vec<(String, Vec<&MorphemeOccurrence>)> = occurrences
+            .iter()
+            .map(|(shape, o)| (shape.clone(), o))
+            .into_group_map()
+            .into_iter()
+            .sorted_by(|(a, a_uses), (b, b_uses)| b_uses.len().cmp(&a_uses.len()).then(a.cmp(b)))
+            .collect();
+
+        let parsed: Vec<_> = shapes
+            .iter()
+            .map(|(shape, uses)| {
+                // Compare the phonemes that the orthography writes, which
+                // come back out of it the same way.
+                let internal = compare_by
+                    .and_then(|system| system.to_dailp(shape).ok())
+                    .unwrap_or_else(|| uses[0].shape.clone());
+                PhonemicString::parse_dailp(&internal)
+            })
+            .collect();
+
+        // Link every pair of close enough shapes into the same cluster.
+        let mut cluster_of: Vec<usize> = (0..shapes.len()).collect();
+        for a in 0..shapes.len() {
+            for b in (a + 1)..shapes.len() {
+                if segment_distance(&parsed[a], &parsed[b]) <= max_distance {
+                    let (from, to) = (find_root(&mut cluster_of, b), find_root(&mut cluster_of, a));
+                    // Keep the more common shape as the root.
+                    let (from, to) = if from < to { (to, from) } else { (from, to) };
+                    cluster_of[from] = to;
+                }
+            }
+        }
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); shapes.len()];
+        for idx in 0..shapes.len() {
+            let root = find_root(&mut cluster_of, idx);
+            members[root].push(idx);
+        }
+
+        members
+            .into_iter()
+            .filter(|members| !members.is_empty())
+            .map(|members| {
+                let uses = || members.iter().flat_map(|&idx| &shapes[idx].1);
+                Self {
+                    representative: shapes[members[0]].0.clone(),
+                    variants: members
+                        .iter()
+                        .map(|&idx| AllomorphVariant {
+                            shape: shapes[idx].0.clone(),
+                            count: shapes[idx].1.len() as i64,
+                            example_word_ids: shapes[idx]
+                                .1
+                                .iter()
+                                .map(|o| o.word_id)
+                                .unique()
+                                .take(example_count)
+                                .collect(),
+                        })
+                        .collect(),
+                    total_count: uses().count() as i64,
+                    preceding: top_contexts(
+                        uses().map(|o| o.preceding_gloss.clone()),
+                        example_count,
+                    ),
+                    following: top_contexts(
+                        uses().map(|o| o.following_gloss.clone()),
+                        example_count,
+                    ),
+                }
+            })
+            .sorted_by(|a, b| b.total_count.cmp(&a.total_count))
+            .collect()
+    }
+}
+
+/// Number of consonant clusters and vowels that must be inserted, removed, or
+/// replaced to turn one phonemic string into the other. Vowels that differ
+/// only in tone or length count as different.
+pub fn segment_distance(a: &PhonemicString, b: &PhonemicString) -> usize {
+    let a = a.segments();
+    let b = b.segments();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, x) in a.iter().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, y) in b.iter().enumerate() {
+            let cost = if x == y { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+fn find_root(parents: &mut [usize], idx: usize) -> usize {
+    let mut root = idx;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[idx] = root;
+    root
+}
+
+fn top_contexts(
+    glosses: impl Iterator<Item = Option<String>>,
+    limit: usize,
+) -> Vec<MorphemeContext> {
+    let counts: HashMap<Option<String>, i64> = glosses.fold(HashMap::new(), |mut counts, g| {
+        *counts.entry(g).or_default() += 1;
+        counts
+    });
+    counts
+        .into_iter()
+        .map(|(gloss, count)| MorphemeContext { gloss, count })
+        .sorted_by(|a, b| b.count.cmp(&a.count).then_with(|| a.gloss.cmp(&b.gloss)))
+        .take(limit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn occurrence(shape: &str, preceding: Option<&str>) -> MorphemeOccurrence {
+        MorphemeOccurrence {
+            shape: shape.to_owned(),
+            word_id: Uuid::new_v4(),
+            preceding_gloss: preceding.map(str::to_owned),
+            following_gloss: None,
+        }
+    }
+
+    #[test]
+    fn distance_between_segments() {
+        let parse = PhonemicString::parse_dailp;
+        assert_eq!(segment_distance(&parse("hnoga"), &parse("hnoga")), 0);
+        // A change of tone replaces one vowel.
+        assert_eq!(segment_distance(&parse("hnoga"), &parse("hnóga")), 1);
+        assert_eq!(segment_distance(&parse("hnoga"), &parse("hnog")), 1);
+        assert_eq!(segment_distance(&parse("hnoga"), &parse("sgwa")), 3);
+    }
+
+    #[test]
+    fn cluster_similar_shapes() {
+        let clusters = AllomorphCluster::build_all(
+            vec![
+                occurrence("hnoga", Some("3SG.A")),
+                occurrence("hnoga", Some("3SG.A")),
+                occurrence("hnóga", Some("1SG.A")),
+                occurrence("sgwa", None),
+            ],
+            None,
+            1,
+            5,
+        );
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].representative, "hnoga");
+        assert_eq!(clusters[0].total_count, 3);
+        assert_eq!(clusters[0].variants.len(), 2);
+        assert_eq!(
+            clusters[0].preceding[0],
+            MorphemeContext {
+                gloss: Some("3SG.A".to_owned()),
+                count: 2
+            }
+        );
+        assert_eq!(clusters[1].following[0].gloss, None);
+
+        // Without tone, both variants are the same shape.
+        let clusters = AllomorphCluster::build_all(
+            vec![occurrence("hnoga", None), occurrence("hnóga", None)],
+            Some(CherokeeOrthography::Learner),
+            0,
+            5,
+        );
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].variants.len(), 1);
+    }
+}
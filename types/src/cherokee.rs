@@ -1,5 +1,4 @@
 use crate::*;
-use lazy_static::lazy_static;
 use sqlx::postgres::*;
 
 /// One representation of Cherokee phonology.
@@ -14,12 +13,18 @@ pub enum CherokeeOrthography {
     Taoc,
     /// The d/t system for transcribing the Cherokee syllabary.
     /// This orthography is favored by speakers.
-    /// TODO Option for /ts/ instead of /j/
-    /// TODO Option for /qu/ instead of /gw/ or /kw/
+    /// Spellings like /ts/ for /j/ or /qu/ for /gw/ can be chosen with an
+    /// [`OrthographyProfile`] based on [`OrthographyProfile::CRG`].
     Crg,
     /// Simplified system that uses d/t without tones, a compromise intended for
     /// language learners. qu and ts
     Learner,
+    /// The Cherokee syllabary, with one character for each consonant-vowel
+    /// syllable. Tone, vowel length, and glottal stops are not written.
+    Syllabary,
+    /// The International Phonetic Alphabet, for linguists outside the Cherokee
+    /// community. Tones are written with Chao tone letters.
+    Ipa,
 }
 
 impl PgHasArrayType for CherokeeOrthography {
@@ -42,70 +47,315 @@ impl CherokeeOrthography {
             Taoc => ast.into_dailp(),
             Crg => ast.into_crg(),
             Learner => ast.into_learner(),
+            Syllabary => ast.into_syllabary(),
+            Ipa => ast.into_ipa(),
         }
     }
 
-    /// Return a list of possible misspellings of the given syllabary string,
-    /// based on which characters look the most similar.
-    pub fn similar_syllabary_strings(original: &str) -> Vec<String> {
-        // For each character of the string, list out similar looking characters.
-        // This yields from "GFT" => [['G', 'C', 'O'], ['F', 'E'], ['T', 'I']]
-        let potential_chars: Vec<Vec<char>> = original
-            .chars()
-            .map(Self::similar_syllabary_chars)
-            .collect();
+    /// Read the input string written in this orthography back into DAILP
+    /// internal orthography, the reverse of [`Self::convert`]. Anything this
+    /// system doesn't write, like tone in the syllabary, is lost.
+    pub fn to_dailp(&self, input: &str) -> anyhow::Result<String> {
+        use CherokeeOrthography::*;
+        Ok(match self {
+            Taoc => input.to_owned(),
+            Crg => PhonemicString::parse_crg(input).into_dailp(),
+            Learner => PhonemicString::parse_learner(input).into_dailp(),
+            Syllabary => PhonemicString::parse_syllabary(input).into_dailp(),
+            Ipa => anyhow::bail!("Reading Cherokee from IPA is not supported"),
+        })
+    }
 
-        // Now produce the list of combinations.
-        // [['G', 'C', 'O'], ['F', 'E'], ['T', 'I']] => ["GFT", "CFT", "OFT", ...]
-        let mut results: Vec<String> = Vec::new();
-        for group in potential_chars {
-            if results.is_empty() {
-                results = group.into_iter().map(|c| c.to_string()).collect();
-            } else {
-                // For each string in the list, make a new version for each next character.
-                // ["G", "C", "O"] => ["GF", "GE", "CF", "CE", "OF", "OE"]
-                results = results
-                    .into_iter()
-                    .flat_map(|in_progress| {
-                        group.iter().map(move |c| {
-                            let mut new_thing = in_progress.clone();
-                            new_thing.push(*c);
-                            new_thing
-                        })
-                    })
-                    .collect();
+    /// The orthography whose morpheme tags should be displayed alongside this
+    /// one. The syllabary and IPA have no glossing conventions of their own, so
+    /// they borrow the TAOC tags.
+    pub fn tag_system(&self) -> Self {
+        match self {
+            CherokeeOrthography::Syllabary | CherokeeOrthography::Ipa => CherokeeOrthography::Taoc,
+            other => *other,
+        }
+    }
+
+    /// The spelling choices that make up this orthography, if it's written with
+    /// the Latin alphabet.
+    pub fn profile(&self) -> Option<OrthographyProfile> {
+        use CherokeeOrthography::*;
+        match self {
+            Taoc => Some(OrthographyProfile::TAOC),
+            Crg => Some(OrthographyProfile::CRG),
+            Learner => Some(OrthographyProfile::LEARNER),
+            Syllabary | Ipa => None,
+        }
+    }
+
+    /// The spelling choices of this orthography with the given options
+    /// swapped in. Fails for systems not written with the Latin alphabet.
+    pub fn profile_with(&self, options: &SpellingOptions) -> anyhow::Result<OrthographyProfile> {
+        self.profile()
+            .map(|profile| profile.with_options(options))
+            .ok_or_else(|| anyhow::format_err!("Spelling options can't be applied to {:?}", self))
+    }
+
+    /// Weighted edit distance between a syllabary query and the closest
+    /// matching part of the target string. Swapping characters that look alike
+    /// costs much less than any other edit, so that a search can tolerate
+    /// misreadings of a blurry manuscript.
+    pub fn syllabary_distance(query: &str, target: &str) -> f32 {
+        const INDEL_COST: f32 = 1.0;
+        let query: Vec<char> = query.chars().collect();
+        let target: Vec<char> = target.chars().collect();
+
+        // Matching may start anywhere in the target for free, so the first row
+        // stays at zero.
+        let mut prev = vec![0.0; target.len() + 1];
+        for (i, q) in query.iter().enumerate() {
+            let mut curr = vec![(i + 1) as f32 * INDEL_COST; target.len() + 1];
+            for (j, t) in target.iter().enumerate() {
+                curr[j + 1] = (prev[j] + Self::substitution_cost(*q, *t))
+                    .min(prev[j + 1] + INDEL_COST)
+                    .min(curr[j] + INDEL_COST);
             }
+            prev = curr;
         }
+        // Matching may also end anywhere in the target.
+        prev.into_iter().fold(f32::INFINITY, f32::min)
+    }
 
-        results
+    /// Largest [`Self::syllabary_distance`] at which a target still matches
+    /// the given query. Queries of three characters or fewer only tolerate
+    /// look-alike swaps, since a single full edit would let them match almost
+    /// any word. Longer queries tolerate about one edit per three characters.
+    pub fn max_syllabary_distance(query: &str) -> f32 {
+        let len = query.chars().count();
+        if len <= 3 {
+            0.75
+        } else {
+            len as f32 / 3.0
+        }
     }
 
-    /// Return all syllabary characters that look similar to the given one.
-    fn similar_syllabary_chars(c: char) -> Vec<char> {
+    /// Regular expression that every target within
+    /// [`Self::max_syllabary_distance`] of the query must match, so that the
+    /// database can skip most words before measuring the distance to each.
+    /// The query is split into one more piece than the number of full edits
+    /// allowed, so at least one piece must appear in the target with only
+    /// look-alike swaps.
+    pub fn syllabary_pattern(query: &str) -> String {
+        let query: Vec<char> = query.chars().collect();
+        let edits = Self::max_syllabary_distance(&query.iter().collect::<String>()) as usize;
+        let piece_len = (query.len() / (edits + 1)).max(1);
+        query
+            .chunks(piece_len)
+            .map(|piece| piece.iter().map(|c| Self::look_alike_pattern(*c)).collect())
+            .collect::<Vec<String>>()
+            .join("|")
+    }
+
+    /// Regular expression matching the given character or any character that
+    /// looks like it.
+    fn look_alike_pattern(c: char) -> String {
         let group = CherokeeSyllabaryVisualGroups::from_char(c);
-        if let Some(chars) = CHEROKEE_FALSE_FRIENDS.get(&group) {
-            chars.to_vec()
+        if group != CherokeeSyllabaryVisualGroups::Other {
+            // Alternatives rather than a bracket expression, which would match
+            // single bytes in a database that isn't encoded as UTF-8.
+            let similar: Vec<String> = ('\u{13A0}'..='\u{13F5}')
+                .filter(|other| CherokeeSyllabaryVisualGroups::from_char(*other) == group)
+                .map(String::from)
+                .collect();
+            format!("(?:{})", similar.join("|"))
+        } else if c.is_alphanumeric() {
+            c.to_string()
         } else {
-            vec![c]
+            format!("\\{}", c)
         }
     }
-}
 
-use std::collections::HashMap;
-
-lazy_static! {
-    static ref CHEROKEE_FALSE_FRIENDS: HashMap<CherokeeSyllabaryVisualGroups, Vec<char>> = {
-        let mut m = HashMap::new();
-        let all_syllabary_chars = 'Ꭰ'..'Ᏽ';
-        for c in all_syllabary_chars {
-            let group = CherokeeSyllabaryVisualGroups::from_char(c);
-            if group != CherokeeSyllabaryVisualGroups::Other {
-                let items: &mut Vec<char> = m.entry(group).or_default();
-                items.push(c);
+    /// Rank the given targets by their distance from a syllabary query,
+    /// keeping at most `limit` of those close enough to match, closest first.
+    pub fn rank_syllabary_matches(
+        query: &str,
+        targets: impl IntoIterator<Item = String>,
+        limit: usize,
+    ) -> Vec<String> {
+        let max_distance = Self::max_syllabary_distance(query);
+        let mut ranked: Vec<(f32, String)> = targets
+            .into_iter()
+            .filter_map(|target| {
+                let distance = Self::syllabary_distance(query, &target);
+                (distance <= max_distance).then_some((distance, target))
+            })
+            .collect();
+        // Ties go to the shorter target, which is closer to the whole query.
+        ranked.sort_by(|a, b| {
+            a.0.total_cmp(&b.0)
+                .then_with(|| a.1.chars().count().cmp(&b.1.chars().count()))
+                .then_with(|| a.1.cmp(&b.1))
+        });
+        ranked
+            .into_iter()
+            .take(limit)
+            .map(|(_, target)| target)
+            .collect()
+    }
+
+    /// Cost of reading one syllabary character as another, which is cheap when
+    /// the two look similar.
+    fn substitution_cost(a: char, b: char) -> f32 {
+        if a == b {
+            0.0
+        } else {
+            let group = CherokeeSyllabaryVisualGroups::from_char(a);
+            if group != CherokeeSyllabaryVisualGroups::Other
+                && group == CherokeeSyllabaryVisualGroups::from_char(b)
+            {
+                0.25
+            } else {
+                1.0
             }
         }
-        m
+    }
+}
+
+/// A set of spelling choices for writing Cherokee phonology with the Latin
+/// alphabet. Community classes use many different conventions, so each of our
+/// romanized orthographies is one preset of these options.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct OrthographyProfile {
+    /// How to distinguish plain stops from aspirated ones.
+    pub stops: StopSpelling,
+    /// How to spell the affricate /c/.
+    pub affricate: AffricateSpelling,
+    /// How to spell the labiovelar stops /kw/ and /kwh/.
+    pub labiovelar: LabiovelarSpelling,
+    /// Which tone diacritics to write on vowels.
+    pub tone: ToneMarking,
+    /// Whether long vowels are written differently from short ones.
+    pub vowel_length: VowelLength,
+    /// How to write the glottal stop.
+    pub glottal_stop: GlottalStopSpelling,
+}
+
+impl OrthographyProfile {
+    /// DAILP internal orthography, which is similar to TAOC.
+    pub const TAOC: Self = Self {
+        stops: StopSpelling::Tth,
+        affricate: AffricateSpelling::J,
+        labiovelar: LabiovelarSpelling::Kw,
+        tone: ToneMarking::Taoc,
+        vowel_length: VowelLength::Doubled,
+        glottal_stop: GlottalStopSpelling::Letter,
+    };
+
+    /// The orthography of the Cherokee Reference Grammar.
+    pub const CRG: Self = Self {
+        stops: StopSpelling::Dt,
+        affricate: AffricateSpelling::J,
+        labiovelar: LabiovelarSpelling::Kw,
+        tone: ToneMarking::Crg,
+        vowel_length: VowelLength::Doubled,
+        glottal_stop: GlottalStopSpelling::Letter,
+    };
+
+    /// Simplified orthography for language learners.
+    pub const LEARNER: Self = Self {
+        stops: StopSpelling::Dt,
+        affricate: AffricateSpelling::Ts,
+        labiovelar: LabiovelarSpelling::Qu,
+        tone: ToneMarking::Unmarked,
+        vowel_length: VowelLength::Single,
+        glottal_stop: GlottalStopSpelling::Apostrophe,
     };
+
+    /// This profile with any spelling choices given in `options` swapped in.
+    pub fn with_options(self, options: &SpellingOptions) -> Self {
+        Self {
+            stops: options.stops.unwrap_or(self.stops),
+            affricate: options.affricate.unwrap_or(self.affricate),
+            labiovelar: options.labiovelar.unwrap_or(self.labiovelar),
+            tone: options.tone.unwrap_or(self.tone),
+            vowel_length: options.vowel_length.unwrap_or(self.vowel_length),
+            glottal_stop: options.glottal_stop.unwrap_or(self.glottal_stop),
+        }
+    }
+}
+
+/// Spelling choices that replace those of a romanized orthography, so that
+/// readers can match the conventions of their own community, like ts for j or
+/// qu for gw. Choices that are left out keep the spelling of the orthography.
+#[derive(async_graphql::InputObject, Clone, Copy, Default, Debug)]
+pub struct SpellingOptions {
+    /// How to distinguish plain stops from aspirated ones
+    pub stops: Option<StopSpelling>,
+    /// How to spell the affricate /c/
+    pub affricate: Option<AffricateSpelling>,
+    /// How to spell the labiovelar stops /kw/ and /kwh/
+    pub labiovelar: Option<LabiovelarSpelling>,
+    /// Which tone diacritics to write on vowels
+    pub tone: Option<ToneMarking>,
+    /// Whether long vowels are written differently from short ones
+    pub vowel_length: Option<VowelLength>,
+    /// How to write the glottal stop
+    pub glottal_stop: Option<GlottalStopSpelling>,
+}
+
+/// How to distinguish plain stops from aspirated ones.
+#[derive(async_graphql::Enum, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum StopSpelling {
+    /// Plain stops are t and k, aspirated stops are th and kh.
+    Tth,
+    /// Plain stops are d and g, aspirated stops are t and k.
+    Dt,
+}
+
+/// How to spell the affricate /c/.
+#[derive(async_graphql::Enum, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum AffricateSpelling {
+    /// A single letter: c in the t/th system or j in the d/t system.
+    J,
+    /// The digraph ts.
+    Ts,
+}
+
+/// How to spell the labiovelar stops /kw/ and /kwh/.
+#[derive(async_graphql::Enum, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum LabiovelarSpelling {
+    /// Follow the stop spelling: kw and kwh in the t/th system or gw and kw in
+    /// the d/t system.
+    Kw,
+    /// Always qu, as in the Worcester tradition.
+    Qu,
+}
+
+/// Which tone diacritics to write on vowels.
+#[derive(async_graphql::Enum, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum ToneMarking {
+    /// Mark every tone distinction, including superhigh with a double acute.
+    /// Colons are kept as they are.
+    Taoc,
+    /// Mark tone as in the Cherokee Reference Grammar, which merges superhigh
+    /// into high. Colons are written as "xx".
+    Crg,
+    /// Leave tone unmarked and drop colons.
+    Unmarked,
+}
+
+/// Whether long vowels are written differently from short ones.
+#[derive(async_graphql::Enum, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum VowelLength {
+    /// Long vowels are doubled.
+    Doubled,
+    /// Long vowels are written the same as short ones.
+    Single,
+}
+
+/// How to write the glottal stop.
+#[derive(async_graphql::Enum, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum GlottalStopSpelling {
+    /// Always write the letter ʔ.
+    Letter,
+    /// Write an apostrophe, leaving out glottal stops at the start or end of a
+    /// string.
+    Apostrophe,
 }
 
 #[derive(Hash, PartialEq, Eq, Clone, Copy)]
@@ -217,6 +467,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reverse_conversion() {
+        let orig = "ùùnatoótákwààskvv̋ʔi";
+        assert_eq!(
+            CherokeeOrthography::Crg
+                .to_dailp(&CherokeeOrthography::Crg.convert(orig))
+                .unwrap(),
+            orig
+        );
+        // Learner spelling drops tone and length, so only consonants survive.
+        assert_eq!(
+            CherokeeOrthography::Learner
+                .to_dailp("unadodaquasgv'i")
+                .unwrap(),
+            "unatotakwaskvʔi"
+        );
+        assert!(CherokeeOrthography::Ipa.to_dailp("a").is_err());
+    }
+
     #[test]
     fn colons() {
         assert_eq!(
@@ -226,23 +495,143 @@ mod tests {
     }
 
     #[test]
-    fn false_friends() {
+    fn profiles() {
+        let orig = "ùùnatoótákwààskvv̋ʔi";
+        // The TAOC preset should leave our internal orthography alone.
+        for word in [orig, "t-uu-alihthat-:iinvvʔs-ééʔi", "uwa–:ciískáhlvv̋ʔi"] {
+            assert_eq!(
+                PhonemicString::parse_dailp(word).render(&OrthographyProfile::TAOC),
+                word
+            );
+        }
+
+        let crg_with_qu = OrthographyProfile {
+            affricate: AffricateSpelling::Ts,
+            labiovelar: LabiovelarSpelling::Qu,
+            ..OrthographyProfile::CRG
+        };
         assert_eq!(
-            CherokeeOrthography::similar_syllabary_strings("ᎤᎾᏗ"),
-            &[
-                "ᎤᎾᏗ",
-                "ᎤᎾᏘ",
-                "ᎤᏫᏗ",
-                "ᎤᏫᏘ",
-                "ᏅᎾᏗ",
-                "ᏅᎾᏘ",
-                "ᏅᏫᏗ",
-                "ᏅᏫᏘ",
-                "ᏛᎾᏗ",
-                "ᏛᎾᏘ",
-                "ᏛᏫᏗ",
-                "ᏛᏫᏘ"
-            ]
-        )
+            PhonemicString::parse_dailp(orig).render(&crg_with_qu),
+            "uùnadoódáquaàsgv́v́ʔi"
+        );
+        assert_eq!(
+            PhonemicString::parse_dailp("uwa–:ciískáhlvv̋ʔi").render(&crg_with_qu),
+            "uwa–xxtsiísgáhlv́v́ʔi"
+        );
+
+        let learner_with_tone = CherokeeOrthography::Learner
+            .profile_with(&SpellingOptions {
+                tone: Some(ToneMarking::Crg),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(
+            PhonemicString::parse_dailp(orig).render(&learner_with_tone),
+            "ùnadódáquàsgv́'i"
+        );
+        assert!(CherokeeOrthography::Syllabary
+            .profile_with(&SpellingOptions::default())
+            .is_err());
+    }
+
+    #[test]
+    fn ipa() {
+        assert_eq!(
+            CherokeeOrthography::Ipa.convert("ùùnatoótákwààskvv̋ʔi"),
+            "uː˨˩na˨toː˨˧ta˧kʷaː˨˩skə̃ː˧˦ʔi˨"
+        );
+        assert_eq!(
+            CherokeeOrthography::Ipa.convert("t-uu-alihthat-:iinvvʔs-ééʔi"),
+            "t-uː˨-a˨li˨htʰa˨t-iː˨nə̃ː˨ʔs-eː˧ʔi˨"
+        );
+        assert_eq!(
+            CherokeeOrthography::Ipa.convert("tlhiichiya"),
+            "t͡ɬʰiː˨t͡sʰi˨ja˨"
+        );
+    }
+
+    #[test]
+    fn syllabary() {
+        let orig = "ùùnatoótákwààskvv̋ʔi";
+        assert_eq!(CherokeeOrthography::Syllabary.convert(orig), "ᎤᎾᏙᏓᏆᏍᎬᎢ");
+        assert_eq!(
+            CherokeeOrthography::Syllabary.convert("Ø-ali-sul(v)-hvsk-vv̋ʔi=hnoo"),
+            "Ø-ᎠᎵ-Ꮡ(Ꭵ)-ᎲᏍ-ᎥᎢ=Ꮓ"
+        );
+        assert_eq!(CherokeeOrthography::Syllabary.convert("thiihla"), "ᏘᎳ");
+        assert_eq!(CherokeeOrthography::Syllabary.convert(""), "");
+
+        // Reading the syllabary back loses tone and length, but should
+        // otherwise round-trip.
+        let parsed = PhonemicString::parse_syllabary("ᎤᎾᏙᏓᏆᏍᎬᎢ");
+        assert_eq!(parsed.clone().into_dailp(), "unatotakwaskvi");
+        assert_eq!(parsed.into_syllabary(), "ᎤᎾᏙᏓᏆᏍᎬᎢ");
+        assert_eq!(
+            PhonemicString::parse_syllabary("ᏣᎳᎩ ᎦᏬᏂᎯᏍᏗ").into_dailp(),
+            "calaki kawonihisti"
+        );
+    }
+
+    #[test]
+    fn false_friends() {
+        use CherokeeOrthography as C;
+        assert_eq!(C::syllabary_distance("ᎤᎾᏗ", "ᎤᎾᏗ"), 0.0);
+        // Look-alike characters are cheap to swap.
+        for similar in ["ᎤᎾᏘ", "ᎤᏫᏗ", "ᏅᎾᏗ", "ᏛᏫᏘ"] {
+            assert!(C::syllabary_distance("ᎤᎾᏗ", similar) < 1.0);
+        }
+        assert_eq!(C::syllabary_distance("ᎤᎾᏗ", "ᏛᏫᏘ"), 0.75);
+        // Other characters cost a full edit.
+        assert_eq!(C::syllabary_distance("ᎤᎾᏗ", "ᎤᎦᏗ"), 1.0);
+        // Missing or extra characters are allowed.
+        assert_eq!(C::syllabary_distance("ᎤᎾᏗ", "ᎤᏗ"), 1.0);
+        assert_eq!(C::syllabary_distance("ᎤᎾᏗ", "ᎤᎾᎾᏗ"), 1.0);
+        // The query may match any part of the target.
+        assert_eq!(C::syllabary_distance("ᎤᎾᏗ", "ᎠᏂᎤᎾᏗᏍᎩ"), 0.0);
+    }
+
+    #[test]
+    fn syllabary_thresholds() {
+        use CherokeeOrthography as C;
+        // Short queries only tolerate look-alike characters.
+        for query in ["Ꭴ", "ᎤᎾ", "ᎤᎾᏗ"] {
+            assert!(C::max_syllabary_distance(query) < 1.0);
+        }
+        let ranked = |query, targets: &[&str]| {
+            C::rank_syllabary_matches(query, targets.iter().map(|t| t.to_string()), 10)
+        };
+        assert_eq!(ranked("Ꭴ", &["ᏅᎾ", "ᎦᏙ", "ᎠᏂ"]), ["ᏅᎾ"]);
+        assert_eq!(ranked("ᎤᎾᏗ", &["ᎤᎦᏗ", "ᎤᏗ", "ᏛᏫᏘ"]), ["ᏛᏫᏘ"]);
+        // Longer queries allow about one edit per three characters.
+        assert_eq!(C::max_syllabary_distance("ᎤᎾᏗᏍᎩ"), 5.0 / 3.0);
+        assert_eq!(ranked("ᎤᎾᏗᏍᎩ", &["ᎤᎦᏗᏍᎩ", "ᎤᎦᏗᏍᏆ"]), ["ᎤᎦᏗᏍᎩ"]);
+    }
+
+    #[test]
+    fn syllabary_ranking() {
+        use CherokeeOrthography as C;
+        let targets = ["ᎤᎦᏗᏍᎩ", "ᎠᏂᎤᎾᏗᏍᎩ", "ᎤᎾᏗᏍᎩ", "ᏛᎾᏗᏍᎩ", "ᎦᏙ"];
+        let ranked = C::rank_syllabary_matches("ᎤᎾᏗᏍᎩ", targets.iter().map(|t| t.to_string()), 10);
+        // Exact matches first, shorter ones before longer ones, then look-alike
+        // swaps, then full edits. Words too far away are left out.
+        assert_eq!(ranked, ["ᎤᎾᏗᏍᎩ", "ᎠᏂᎤᎾᏗᏍᎩ", "ᏛᎾᏗᏍᎩ", "ᎤᎦᏗᏍᎩ"]);
+        let first_two =
+            C::rank_syllabary_matches("ᎤᎾᏗᏍᎩ", targets.iter().map(|t| t.to_string()), 2);
+        assert_eq!(first_two, ["ᎤᎾᏗᏍᎩ", "ᎠᏂᎤᎾᏗᏍᎩ"]);
+    }
+
+    #[test]
+    fn syllabary_patterns() {
+        use CherokeeOrthography as C;
+        let query = "ᎤᎾᏗᏍᎩ";
+        let pattern = regex::Regex::new(&C::syllabary_pattern(query)).unwrap();
+        // Every word close enough to match has to get past the pattern.
+        for target in ["ᎤᎾᏗᏍᎩ", "ᏛᏫᏘᏍᎩ", "ᎤᎦᏗᏍᏆ", "ᎠᎤᎾᏗ.ᏍᎩ", "ᏍᎩ ᎤᎾ"]
+        {
+            let close = C::syllabary_distance(query, target) <= C::max_syllabary_distance(query);
+            assert!(!close || pattern.is_match(target), "{}", target);
+        }
+        assert!(!pattern.is_match("ᎦᏙ"));
+        assert_eq!(C::syllabary_pattern("a.b"), "a\\.b");
     }
 }
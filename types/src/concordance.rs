@@ -0,0 +1,131 @@
+//! Concordances list every use of a word or morpheme along with the words
+//! around it, also known as keyword in context (KWIC).
+
+use crate::{AnnotatedForm, DocumentId, DocumentReference};
+
+/// What a concordance search compares against each word.
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ConcordanceMatch {
+    /// Words containing a morpheme with the given gloss, like "catch" or
+    /// "DF1975:catch" for only the glosses that a document defines
+    #[default]
+    Gloss,
+    /// Words that sound like the given romanized form, ignoring tone, vowel
+    /// length, glottal stops, and d/t spelling
+    Surface,
+    /// Words whose syllabary source contains the given text
+    Syllabary,
+}
+
+/// Which words to list in a concordance.
+#[derive(async_graphql::InputObject, Clone, Debug)]
+pub struct ConcordanceQuery {
+    /// Gloss, romanized form, or piece of syllabary to look for
+    pub query: String,
+    /// What to compare the query against in each word
+    #[graphql(default)]
+    pub match_by: ConcordanceMatch,
+    /// Only list words in this document
+    pub document_id: Option<DocumentId>,
+}
+
+/// How to order the lines of a concordance.
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ConcordanceSort {
+    /// By the words right before each match, starting with the nearest one
+    LeftContext,
+    /// By the words right after each match
+    RightContext,
+    /// Oldest documents first, with undated documents last
+    Date,
+    /// By document short name, then by position in the document
+    #[default]
+    Document,
+}
+
+/// One use of a word, along with the words around it in its paragraph.
+#[derive(async_graphql::SimpleObject, Clone)]
+pub struct ConcordanceLine {
+    /// Words right before the match in its paragraph, in reading order
+    pub left: Vec<AnnotatedForm>,
+    /// The matching word
+    pub word: AnnotatedForm,
+    /// Words right after the match in its paragraph, in reading order
+    pub right: Vec<AnnotatedForm>,
+    /// Document that the match is in
+    pub document: DocumentReference,
+}
+
+impl ConcordanceLine {
+    /// Make a line for the word at `index` of a paragraph, with at most
+    /// `context_size` words on either side. Returns nothing if the index is
+    /// past the end of the paragraph.
+    pub fn from_paragraph(
+        words: &[AnnotatedForm],
+        index: usize,
+        context_size: usize,
+        document: DocumentReference,
+    ) -> Option<Self> {
+        Some(Self {
+            word: words.get(index)?.clone(),
+            left: words[index.saturating_sub(context_size)..index].to_vec(),
+            right: words[index + 1..words.len().min(index + 1 + context_size)].to_vec(),
+            document,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PositionInDocument;
+
+    fn word(source: &str, index: i64) -> AnnotatedForm {
+        AnnotatedForm {
+            id: None,
+            source: source.to_owned(),
+            normalized_source: None,
+            simple_phonetics: None,
+            phonemic: None,
+            segments: None,
+            english_gloss: Vec::new(),
+            commentary: None,
+            line_break: None,
+            page_break: None,
+            position: PositionInDocument::new(
+                DocumentId(Default::default()),
+                "1".to_owned(),
+                index,
+            ),
+            date_recorded: None,
+            ingested_audio_track: None,
+        }
+    }
+
+    fn document() -> DocumentReference {
+        DocumentReference {
+            id: Default::default(),
+            short_name: "LET1".to_owned(),
+            title: "Letter".to_owned(),
+            date: None,
+            order_index: 0,
+        }
+    }
+
+    #[test]
+    fn context_around_match() {
+        let words: Vec<_> = ["a", "c", "x", "b", "x", "d"]
+            .iter()
+            .enumerate()
+            .map(|(idx, source)| word(source, idx as i64))
+            .collect();
+        let first = ConcordanceLine::from_paragraph(&words, 2, 1, document()).unwrap();
+        assert_eq!(first.left.len(), 1);
+        assert_eq!(first.left[0].source, "c");
+        assert_eq!(first.right[0].source, "b");
+        let last = ConcordanceLine::from_paragraph(&words, 4, 3, document()).unwrap();
+        assert_eq!(last.left.len(), 3);
+        assert_eq!(last.right.len(), 1);
+        assert!(ConcordanceLine::from_paragraph(&words, 6, 3, document()).is_none());
+    }
+}
@@ -314,22 +314,163 @@ impl Database {
         Ok(())
     }
 
-    pub async fn potential_syllabary_matches(&self, syllabary: &str) -> Result<Vec<AnnotatedForm>> {
-        let alternate_spellings: Vec<_> = CherokeeOrthography::similar_syllabary_strings(syllabary)
-            .into_iter()
-            // Convert into "LIKE"-compatible format
-            .map(|x| format!("%{}%", x))
-            .collect();
+    /// Find words whose source text contains something close to the given
+    /// syllabary string, ranked from closest to furthest. Look-alike
+    /// characters are tolerated, and queries longer than three characters
+    /// may also have roughly one insertion, deletion, or other swap per three
+    /// characters. Returns words for at most `limit` distinct source strings.
+    pub async fn potential_syllabary_matches(
+        &self,
+        syllabary: &str,
+        limit: usize,
+    ) -> Result<Vec<AnnotatedForm>> {
+        let pattern = CherokeeOrthography::syllabary_pattern(syllabary);
+        let candidates = query_file_scalar!("queries/syllabary_word_candidates.sql", pattern)
+            .fetch_all(&self.client)
+            .await?;
+        let ranks: HashMap<String, usize> =
+            CherokeeOrthography::rank_syllabary_matches(syllabary, candidates, limit)
+                .into_iter()
+                .enumerate()
+                .map(|(rank, source)| (source, rank))
+                .collect();
+
+        let sources: Vec<_> = ranks.keys().cloned().collect();
+        let mut items = query_file_as!(BasicWord, "queries/search_syllabary.sql", &sources)
+            .fetch_all(&self.client)
+            .await?;
+        items.sort_by_key(|word| {
+            word.source_text
+                .as_ref()
+                .and_then(|s| ranks.get(s))
+                .copied()
+        });
+        Ok(items.into_iter().map(Into::into).collect())
+    }
+
+    /// Find words that sound like the given phonetic string, regardless of
+    /// tone, vowel length, glottal stops, or d/t spelling. Matches either part
+    /// of a whole word or a single morpheme exactly.
+    pub async fn search_words_by_key(&self, query: &str) -> Result<Vec<AnnotatedForm>> {
+        let key = phonetic_search_key(query);
+        if key.is_empty() {
+            return Ok(Vec::new());
+        }
         let items = query_file_as!(
             BasicWord,
-            "queries/search_syllabary.sql",
-            &alternate_spellings
+            "queries/search_words_by_key.sql",
+            format!("%{}%", key),
+            key
         )
         .fetch_all(&self.client)
         .await?;
         Ok(items.into_iter().map(Into::into).collect())
     }
 
+    /// Fill in search keys for any words and morphemes that don't have one,
+    /// such as those added before search keys existed.
+    pub async fn backfill_search_keys(&self) -> Result<()> {
+        let words = query_file!("queries/word_search_key_sources.sql")
+            .fetch_all(&self.client)
+            .await?;
+        let (word_id, search_key): (Vec<_>, Vec<_>) = words
+            .into_iter()
+            .map(|w| {
+                let key = w
+                    .phonemic
+                    .as_deref()
+                    .or(w.simple_phonetics.as_deref())
+                    .map(phonetic_search_key);
+                (w.id, key)
+            })
+            .unzip();
+        query_file!(
+            "queries/update_word_search_keys.sql",
+            &*word_id,
+            &*search_key as _
+        )
+        .execute(&self.client)
+        .await?;
+
+        let segments = query_file!("queries/word_segment_search_key_sources.sql")
+            .fetch_all(&self.client)
+            .await?;
+        let (word_id, index, search_key): (Vec<_>, Vec<_>, Vec<_>) = segments
+            .into_iter()
+            .map(|s| (s.word_id, s.index_in_word, phonetic_search_key(&s.morpheme)))
+            .multiunzip();
+        query_file!(
+            "queries/update_word_segment_search_keys.sql",
+            &*word_id,
+            &*index,
+            &*search_key
+        )
+        .execute(&self.client)
+        .await?;
+        Ok(())
+    }
+
+    /// Suggest ways to segment a word from the words and morphemes that have
+    /// already been analyzed. The phonetic form should be written in DAILP
+    /// internal orthography. Without it, we read the source as syllabary.
+    pub async fn suggest_segmentations(
+        &self,
+        source: &str,
+        phonetic: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<SegmentationSuggestion>> {
+        let key = match phonetic {
+            Some(phonetic) => phonetic_search_key(phonetic),
+            None => PhonemicString::parse_syllabary(source).into_search_key(),
+        };
+        if key.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let matching_words = query_file!("queries/analyzed_words_like.sql", key, source)
+            .fetch_all(&self.client)
+            .await?
+            .into_iter()
+            .group_by(|row| row.word_id)
+            .into_iter()
+            .map(|(word_id, segments)| {
+                (
+                    word_id,
+                    segments
+                        .map(|row| (row.morpheme, row.gloss, row.role))
+                        .collect(),
+                )
+            })
+            .collect();
+        // Look up each substring of the key exactly so that the search key
+        // index applies.
+        let chars: Vec<char> = key.chars().collect();
+        let substrings: Vec<String> = (0..chars.len())
+            .flat_map(|start| (start + 1..=chars.len()).map(move |end| (start, end)))
+            .map(|(start, end)| chars[start..end].iter().collect())
+            .unique()
+            .collect();
+        let shapes: Vec<_> = query_file!("queries/morpheme_shapes_within.sql", &substrings)
+            .fetch_all(&self.client)
+            .await?
+            .into_iter()
+            .map(|row| MorphemeShape {
+                key: row.search_key,
+                morpheme: row.morpheme,
+                gloss: row.gloss,
+                role: row.role,
+                attestations: row.attestations,
+                example_word_ids: row.example_word_ids,
+            })
+            .collect();
+        Ok(SegmentationSuggestion::rank(
+            &key,
+            matching_words,
+            &shapes,
+            limit,
+        ))
+    }
+
     pub async fn connected_forms(
         &self,
         document_id: Option<DocumentId>,
@@ -346,6 +487,472 @@ impl Database {
         Ok(items.into_iter().map(Into::into).collect())
     }
 
+    /// Every word containing a morpheme with the given gloss. With a document
+    /// name, only uses the gloss defined in that document, like the forms
+    /// listed under a dictionary entry.
+    pub async fn forms_with_root(
+        &self,
+        gloss: &str,
+        document_name: Option<&str>,
+    ) -> Result<Vec<AnnotatedForm>> {
+        let words = query_file_as!(
+            BasicWord,
+            "queries/forms_with_root.sql",
+            gloss,
+            document_name
+        )
+        .fetch_all(&self.client)
+        .await?;
+        Ok(words.into_iter().map(Into::into).collect())
+    }
+
+    /// Dictionary entries in alphabetical order of their headwords, optionally
+    /// only those in the dictionary with the given short name, with the given
+    /// part of speech, or with headwords starting with the given text.
+    pub async fn lexical_entries(
+        &self,
+        document_name: Option<&str>,
+        part_of_speech: Option<PartOfSpeech>,
+        starts_with: Option<&str>,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<LexicalEntry>> {
+        let entries = query_file_as!(
+            BasicLexicalEntry,
+            "queries/lexical_entries.sql",
+            document_name,
+            part_of_speech as _,
+            starts_with,
+            offset,
+            limit
+        )
+        .fetch_all(&self.client)
+        .await?;
+        Ok(entries.into_iter().map(Into::into).collect())
+    }
+
+    /// The entry for a gloss defined in a dictionary, like "DF1975:catch".
+    pub async fn lexical_entry(&self, id: &MorphemeId) -> Result<Option<LexicalEntry>> {
+        let document_name = id
+            .document_name
+            .as_ref()
+            .ok_or_else(|| anyhow::format_err!("Dictionary entries need a document: {}", id))?;
+        let entry = query_file_as!(
+            BasicLexicalEntry,
+            "queries/lexical_entry.sql",
+            document_name,
+            id.gloss
+        )
+        .fetch_optional(&self.client)
+        .await?;
+        Ok(entry.map(Into::into))
+    }
+
+    /// Dictionary entries whose headword, gloss, shape, or senses contain the
+    /// given text, with the closest matches first.
+    pub async fn search_lexical_entries(
+        &self,
+        query: &str,
+        limit: i64,
+    ) -> Result<Vec<LexicalEntry>> {
+        let entries = query_file_as!(
+            BasicLexicalEntry,
+            "queries/search_lexical_entries.sql",
+            query,
+            limit
+        )
+        .fetch_all(&self.client)
+        .await?;
+        Ok(entries.into_iter().map(Into::into).collect())
+    }
+
+    /// Uses of a word matching the given query in the paragraphs of
+    /// documents, along with at most `context_size` words on either side of
+    /// it from the same paragraph. Lines are sorted in the database before
+    /// skipping `offset` of them and keeping at most `limit`, so only the
+    /// paragraphs of lines on the page are loaded.
+    pub async fn concordance(
+        &self,
+        search: &ConcordanceQuery,
+        context_size: usize,
+        sort_by: ConcordanceSort,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<ConcordanceLine>> {
+        let query = search.query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let (gloss, gloss_document, search_key, syllabary) = match search.match_by {
+            ConcordanceMatch::Gloss => {
+                let id = MorphemeId::parse(query)
+                    .ok_or_else(|| anyhow::format_err!("Invalid morpheme gloss: {}", query))?;
+                (Some(id.gloss), id.document_name, None, None)
+            }
+            ConcordanceMatch::Surface => (None, None, Some(phonetic_search_key(query)), None),
+            ConcordanceMatch::Syllabary => (None, None, None, Some(query.to_owned())),
+        };
+        let sort = match sort_by {
+            ConcordanceSort::Document => "document",
+            ConcordanceSort::Date => "date",
+            ConcordanceSort::LeftContext => "left",
+            ConcordanceSort::RightContext => "right",
+        };
+        let hits = query_file!(
+            "queries/concordance_hits.sql",
+            gloss,
+            gloss_document,
+            search_key,
+            syllabary,
+            search.document_id.map(|id| id.0),
+            sort,
+            offset,
+            limit,
+            context_size as i64
+        )
+        .fetch_all(&self.client)
+        .await?;
+
+        // Take the context of each word from its paragraph, in reading order.
+        let keys: Vec<_> = hits
+            .iter()
+            .map(|hit| WordsInParagraph(hit.paragraph_id))
+            .unique()
+            .collect();
+        let paragraphs: HashMap<_, Vec<AnnotatedForm>> =
+            Loader::<WordsInParagraph>::load(self, &keys)
+                .await
+                .map_err(|e| anyhow::anyhow!("{}", e))?
+                .into_iter()
+                .map(|(key, segments)| {
+                    let words = segments.iter().filter_map(AnnotatedSeg::form).cloned();
+                    (key.0, words.collect())
+                })
+                .collect();
+        hits.into_iter()
+            .map(|hit| {
+                let words = paragraphs.get(&hit.paragraph_id);
+                words
+                    .and_then(|words| {
+                        let index = words.iter().position(|w| w.id == Some(hit.word_id))?;
+                        ConcordanceLine::from_paragraph(
+                            words,
+                            index,
+                            context_size,
+                            DocumentReference {
+                                id: hit.document_id,
+                                short_name: hit.document_name,
+                                title: hit.document_title,
+                                date: hit.document_date,
+                                order_index: hit.document_order_index,
+                            },
+                        )
+                    })
+                    .ok_or_else(|| {
+                        anyhow::format_err!(
+                            "Word {} is missing from paragraph {}",
+                            hit.word_id,
+                            hit.paragraph_id
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    /// Cherokee forms whose English translation matches the given search,
+    /// comparing stemmed words so that "catch" also finds "catching". Entries
+    /// of dictionaries come first, then the glosses of morphemes, then words,
+    /// then paragraphs, with the closest matches first within each.
+    pub async fn search_by_english(&self, query: &str, limit: i64) -> Result<Vec<EnglishMatch>> {
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let matches = query_file!("queries/search_by_english.sql", query, limit)
+            .fetch_all(&self.client)
+            .await?;
+        Ok(matches
+            .into_iter()
+            .map(|m| EnglishMatch {
+                source: EnglishMatchSource::from_rank(m.source_rank),
+                cherokee: m.cherokee,
+                romanized: m.romanized,
+                english: m.english,
+                score: m.score.into(),
+                attestation_count: m.attestation_count,
+                morpheme_id: m.morpheme_id,
+                document_ids: m.document_ids,
+            })
+            .collect())
+    }
+
+    /// One page of words and paragraphs matching a full-text search, from
+    /// most to least relevant, starting after the given result.
+    pub async fn full_text_search(
+        &self,
+        query: &str,
+        after: Option<SearchCursor>,
+        limit: i64,
+    ) -> Result<Vec<SearchHit>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let rows = query_file!(
+            "queries/full_text_search.sql",
+            query,
+            after.map(|c| c.score),
+            after.map(|c| c.id),
+            limit
+        )
+        .fetch_all(&self.client)
+        .await?;
+        let word_ids: Vec<_> = rows
+            .iter()
+            .filter(|row| !row.is_paragraph)
+            .map(|row| row.id)
+            .collect();
+        let mut words: HashMap<_, _> =
+            query_file_as!(BasicWord, "queries/words_by_id.sql", &word_ids)
+                .fetch_all(&self.client)
+                .await?
+                .into_iter()
+                .filter_map(|w| Some((w.id?, AnnotatedForm::from(w))))
+                .collect();
+        let terms = search_terms(query);
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let highlights = [
+                    row.source_text.and_then(|text| {
+                        SearchHighlight::from_terms(SearchField::Source, &text, &terms)
+                    }),
+                    row.simple_phonetics.and_then(|text| {
+                        SearchHighlight::from_terms(SearchField::SimplePhonetics, &text, &terms)
+                    }),
+                    row.english_gloss_headline.zip(row.english_gloss).and_then(
+                        |(marked, gloss)| {
+                            SearchHighlight::from_marked_copy(
+                                SearchField::EnglishGloss,
+                                &marked,
+                                &gloss,
+                            )
+                        },
+                    ),
+                    row.commentary_headline.and_then(|marked| {
+                        SearchHighlight::from_marked(SearchField::Commentary, &marked)
+                    }),
+                    row.translation_headline.and_then(|marked| {
+                        SearchHighlight::from_marked(SearchField::Translation, &marked)
+                    }),
+                ];
+                SearchHit {
+                    word: words.remove(&row.id),
+                    paragraph: row.translation.map(|translation| DocumentParagraph {
+                        id: row.id,
+                        translation,
+                        index: row.paragraph_index.unwrap_or(1),
+                    }),
+                    score: row.score.into(),
+                    highlights: highlights.into_iter().flatten().collect(),
+                    document_id: DocumentId(row.document_id),
+                    cursor: SearchCursor {
+                        score: row.score,
+                        id: row.id,
+                    },
+                }
+            })
+            .collect())
+    }
+
+    /// Words matching every term of an advanced search, ordered by document
+    /// short name and then by position within each document.
+    pub async fn advanced_search(
+        &self,
+        search: &AdvancedSearch,
+        offset: i64,
+        limit: i64,
+    ) -> Result<Vec<AnnotatedForm>> {
+        if search.terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        let word_ids: Vec<Uuid> = search
+            .word_ids_query(offset, limit)
+            .build_query_scalar()
+            .fetch_all(&self.client)
+            .await?;
+        let mut words: HashMap<_, _> =
+            query_file_as!(BasicWord, "queries/words_by_id.sql", &word_ids)
+                .fetch_all(&self.client)
+                .await?
+                .into_iter()
+                .filter_map(|w| Some((w.id?, AnnotatedForm::from(w))))
+                .collect();
+        Ok(word_ids.iter().filter_map(|id| words.remove(id)).collect())
+    }
+
+    /// Dictionary entries related to the morpheme gloss with the given ID.
+    pub async fn lexical_cross_references(&self, gloss_id: Uuid) -> Result<Vec<LexicalEntry>> {
+        let entries = query_file_as!(
+            BasicLexicalEntry,
+            "queries/lexical_cross_references.sql",
+            gloss_id
+        )
+        .fetch_all(&self.client)
+        .await?;
+        Ok(entries.into_iter().map(Into::into).collect())
+    }
+
+    /// Fill in the segments of the given words, which queries for words don't
+    /// include on their own.
+    pub async fn attach_segments(&self, forms: &mut [AnnotatedForm]) -> Result<()> {
+        let keys: Vec<_> = forms
+            .iter()
+            .filter_map(|form| form.id.map(PartsOfWord))
+            .collect();
+        let mut parts = Loader::<PartsOfWord>::load(self, &keys)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        for form in forms {
+            form.segments = form.id.and_then(|id| parts.remove(&PartsOfWord(id)));
+        }
+        Ok(())
+    }
+
+    /// Conjugation table of the verb with the given root. With a document
+    /// name, like "DF1975:catch", only uses the forms listed under that entry.
+    pub async fn verb_paradigm(&self, root: MorphemeId) -> Result<VerbParadigm> {
+        let mut forms = self
+            .forms_with_root(&root.gloss, root.document_name.as_deref())
+            .await?;
+
+        self.attach_segments(&mut forms).await?;
+        forms.sort_by_key(|form| (form.position.document_id.0, form.position.index));
+
+        Ok(VerbParadigm::build(root.gloss, forms))
+    }
+
+    /// Other morphemes that appear in the same words as the one with the given
+    /// gloss, most common first.
+    pub async fn morpheme_cooccurrences(
+        &self,
+        gloss: &str,
+        side: MorphemeSide,
+        class: Option<WordSegmentClass>,
+        example_count: i32,
+    ) -> Result<Vec<MorphemeCooccurrence>> {
+        let (before, after) = side.includes();
+        let rows = query_file!(
+            "queries/morpheme_cooccurrence.sql",
+            gloss,
+            before,
+            after,
+            class as _,
+            example_count
+        )
+        .fetch_all(&self.client)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| MorphemeCooccurrence {
+                gloss: row.gloss,
+                word_count: row.word_count,
+                example_word_ids: row.example_word_ids,
+            })
+            .collect())
+    }
+
+    /// How often the morpheme with the given gloss appears in each document
+    /// or decade.
+    pub async fn morpheme_distribution(
+        &self,
+        gloss: &str,
+        grouping: DistributionGrouping,
+        example_count: i32,
+    ) -> Result<Vec<MorphemeDistributionBucket>> {
+        Ok(match grouping {
+            DistributionGrouping::Document => query_file!(
+                "queries/morpheme_distribution_by_document.sql",
+                gloss,
+                example_count
+            )
+            .fetch_all(&self.client)
+            .await?
+            .into_iter()
+            .map(|row| MorphemeDistributionBucket {
+                document_id: Some(DocumentId(row.document_id)),
+                document_name: Some(row.document_name),
+                decade: None,
+                relative_frequency: MorphemeDistributionBucket::frequency(
+                    row.word_count,
+                    row.total_words,
+                ),
+                word_count: row.word_count,
+                total_words: row.total_words,
+                example_word_ids: row.example_word_ids,
+            })
+            .collect(),
+            DistributionGrouping::Decade => query_file!(
+                "queries/morpheme_distribution_by_decade.sql",
+                gloss,
+                example_count
+            )
+            .fetch_all(&self.client)
+            .await?
+            .into_iter()
+            .map(|row| MorphemeDistributionBucket {
+                document_id: None,
+                document_name: None,
+                decade: row.decade,
+                relative_frequency: MorphemeDistributionBucket::frequency(
+                    row.word_count,
+                    row.total_words,
+                ),
+                word_count: row.word_count,
+                total_words: row.total_words,
+                example_word_ids: row.example_word_ids,
+            })
+            .collect(),
+        })
+    }
+
+    /// Functional morphemes attested in at least `min_word_count` words that
+    /// never appear in the same word as the one with the given gloss. Fails
+    /// if no word has that gloss, since every other morpheme would trivially
+    /// qualify.
+    pub async fn exclusive_morphemes(
+        &self,
+        gloss: &str,
+        min_word_count: i64,
+        example_count: i32,
+    ) -> Result<Vec<MorphemeExclusion>> {
+        let totals = query_file!("queries/count_analyzed_words.sql", gloss)
+            .fetch_one(&self.client)
+            .await?;
+        if totals.with_gloss == 0 {
+            anyhow::bail!("No words have the gloss \"{}\"", gloss);
+        }
+        let rows = query_file!(
+            "queries/exclusive_morphemes.sql",
+            gloss,
+            min_word_count,
+            example_count
+        )
+        .fetch_all(&self.client)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| MorphemeExclusion {
+                gloss: row.gloss,
+                expected_cooccurrences: MorphemeDistributionBucket::frequency(
+                    row.word_count,
+                    totals.analyzed,
+                ) * totals.with_gloss as f64,
+                word_count: row.word_count,
+                example_word_ids: row.example_word_ids,
+            })
+            .collect())
+    }
+
     pub async fn morphemes(
         &self,
         morpheme_id: MorphemeId,
@@ -393,6 +1000,38 @@ impl Database {
             .collect())
     }
 
+    /// Shapes of the morpheme with the given gloss clustered into likely
+    /// allomorphs, with the most common morphemes around each cluster.
+    pub async fn allomorphs(
+        &self,
+        morpheme_id: MorphemeId,
+        compare_by: Option<CherokeeOrthography>,
+        max_distance: usize,
+        example_count: usize,
+    ) -> Result<Vec<AllomorphCluster>> {
+        let occurrences = query_file!(
+            "queries/morpheme_occurrences.sql",
+            morpheme_id.gloss,
+            morpheme_id.document_name
+        )
+        .fetch_all(&self.client)
+        .await?
+        .into_iter()
+        .map(|row| MorphemeOccurrence {
+            shape: row.morpheme,
+            word_id: row.word_id,
+            preceding_gloss: row.preceding_gloss,
+            following_gloss: row.following_gloss,
+        })
+        .collect();
+        Ok(AllomorphCluster::build_all(
+            occurrences,
+            compare_by,
+            max_distance,
+            example_count,
+        ))
+    }
+
     pub async fn word_contributor_audio(&self, word_id: &Uuid) -> Result<Vec<AudioSlice>> {
         let contributor_audio = query_file_as!(
             BasicAudioSlice,
@@ -666,16 +1305,27 @@ impl Database {
         Ok(iiif::Manifest::from_document(self, doc, url).await)
     }
 
-    pub async fn all_tags(&self, system: CherokeeOrthography) -> Result<Vec<MorphemeTag>> {
+    /// Every functional tag in the given system and the custom tags, within
+    /// the tag language of the given document. Without a document, lists the
+    /// Cherokee tags.
+    pub async fn all_tags(
+        &self,
+        system: CherokeeOrthography,
+        document_id: Option<DocumentId>,
+    ) -> Result<Vec<MorphemeTag>> {
         use async_graphql::Value;
         let system_name = if let Value::Enum(s) = system.to_value() {
             s
         } else {
             unreachable!()
         };
-        let results = query_file!("queries/all_morpheme_tags.sql", system_name.as_str())
-            .fetch_all(&self.client)
-            .await?;
+        let results = query_file!(
+            "queries/all_morpheme_tags.sql",
+            system_name.as_str(),
+            document_id.map(|id| id.0)
+        )
+        .fetch_all(&self.client)
+        .await?;
         Ok(results
             .into_iter()
             .map(|tag| MorphemeTag {
@@ -691,6 +1341,51 @@ impl Database {
             .collect())
     }
 
+    /// Glosses of every functional tag known in the given system, including
+    /// custom tags, within the tag language of the given document.
+    pub async fn tag_glosses(
+        &self,
+        system_name: &str,
+        document_id: Option<DocumentId>,
+    ) -> Result<Vec<String>> {
+        Ok(query_file!(
+            "queries/all_morpheme_tags.sql",
+            system_name,
+            document_id.map(|id| id.0)
+        )
+        .fetch_all(&self.client)
+        .await?
+        .into_iter()
+        .map(|tag| tag.gloss)
+        .collect())
+    }
+
+    /// Internal glosses of every abstract tag, which raw annotations use for
+    /// functional morphemes.
+    pub async fn abstract_tag_glosses(&self) -> Result<Vec<String>> {
+        Ok(query_file_scalar!("queries/abstract_tag_glosses.sql")
+            .fetch_all(&self.client)
+            .await?)
+    }
+
+    /// Check a segmentation written in the given system without saving it,
+    /// against the tags of the given document's language.
+    pub async fn gloss_warnings(
+        &self,
+        morpheme_layer: &str,
+        gloss_layer: &str,
+        system: &OrthographyId,
+        document_id: Option<DocumentId>,
+    ) -> Result<Vec<GlossWarning>> {
+        let (_, orthography) = system.resolve()?;
+        let tag_system = match system.cherokee() {
+            Some(cherokee) => cherokee.tag_system().id().to_owned(),
+            None => orthography.id().to_owned(),
+        };
+        let known_tags = self.tag_glosses(&tag_system, document_id).await?;
+        Ok(check_gloss_layers(morpheme_layer, gloss_layer, &known_tags))
+    }
+
     pub async fn search_words_any_field(&self, query: String) -> Result<Vec<AnnotatedForm>> {
         let like_query = format!("%{}%", query);
         let results = query_file_as!(BasicWord, "queries/search_words_any_field.sql", like_query)
@@ -788,16 +1483,82 @@ impl Database {
     }
 
     pub async fn update_word(&self, word: AnnotatedFormUpdate) -> Result<Uuid> {
+        // Reject malformed segments before touching anything else.
+        if let MaybeUndefined::Value(segments) = &word.segments {
+            MorphemeSegmentUpdate::check_many(segments)?;
+        }
+
         let mut tx = self.client.begin().await?;
 
+        // Keep the stored segments around to compare the new ones against.
+        let stored_segments: Vec<WordSegment> =
+            query_file!("queries/word_parts.sql", &[word.id][..])
+                .fetch_all(&mut *tx)
+                .await?
+                .into_iter()
+                .map(|part| WordSegment {
+                    system: None,
+                    morpheme: part.morpheme,
+                    gloss: part.gloss,
+                    gloss_id: part.gloss_id,
+                    role: part.role,
+                    class: part.class,
+                    matching_tag: None,
+                })
+                .collect();
+
+        // Unknown functional tags are most likely typos, which would otherwise
+        // be saved as glosses local to the document. Tags the word already had
+        // are only flagged, so that words with custom tags stay editable.
+        if let MaybeUndefined::Value(segments) = &word.segments {
+            if let Some(first) = segments.first() {
+                let system = first.system.unwrap_or(CherokeeOrthography::Taoc);
+                let document_id = query_file_scalar!("queries/word_document_id.sql", word.id)
+                    .fetch_one(&mut *tx)
+                    .await?;
+                let known_tags = self
+                    .tag_glosses(system.tag_system().id(), Some(DocumentId(document_id)))
+                    .await?;
+                let warnings: Vec<_> =
+                    check_gloss_tags(segments.iter().map(|s| &*s.gloss), &known_tags)
+                        .into_iter()
+                        .filter(|warning| {
+                            !stored_segments
+                                .iter()
+                                .any(|seg| Some(&seg.gloss) == warning.gloss.as_ref())
+                        })
+                        .collect();
+                if !warnings.is_empty() {
+                    return Err(anyhow::anyhow!("{}", warnings.iter().join("; ")));
+                }
+            }
+        }
+
         let source = word.source.into_vec();
-        let simple_phonetics = word.romanized_source.into_vec();
+        // The romanized source comes in whichever system the editor was
+        // viewing, so only read it back if it was actually changed.
+        let simple_phonetics: Vec<Option<String>> = match word.romanized_source.take() {
+            Some(romanized) => {
+                let stored: AnnotatedForm =
+                    query_file_as!(BasicWord, "queries/words_by_id.sql", &[word.id][..])
+                        .fetch_one(&mut *tx)
+                        .await?
+                        .into();
+                let system = word.system.unwrap_or(CherokeeOrthography::Taoc);
+                vec![stored.simple_phonetics_from(system, &romanized)]
+            }
+            None => Vec::new(),
+        };
         let commentary = word.commentary.into_vec();
         let english_gloss_owned: Vec<String> = match word.english_gloss.into_vec().pop().flatten() {
             Some(glosses) => glosses.split(',').map(|s| s.trim().to_string()).collect(),
             None => Vec::new(),
         };
         let english_gloss: Vec<&str> = english_gloss_owned.iter().map(|s| s.as_str()).collect();
+        let search_key = simple_phonetics
+            .first()
+            .and_then(|s| s.as_deref())
+            .map(phonetic_search_key);
 
         let document_id = query_file!(
             "queries/update_word.sql",
@@ -805,7 +1566,8 @@ impl Database {
             &source as _,
             &simple_phonetics as _,
             &commentary as _,
-            &english_gloss as _
+            &english_gloss as _,
+            search_key
         )
         .fetch_one(&mut *tx)
         .await?
@@ -833,45 +1595,62 @@ impl Database {
             .execute(&mut *tx)
             .await?;
 
-        let system_name: Option<CherokeeOrthography> = segments[0].system;
+        // Segments come in whichever system the editor was viewing, so read
+        // their tags back into abstract tags and any changed morphemes back
+        // into DAILP internal orthography.
+        let system = segments[0].system.unwrap_or(CherokeeOrthography::Taoc);
+        let tag_system = system.tag_system();
+        let concrete_glosses: Vec<_> = segments.iter().map(|s| s.gloss.clone()).collect();
+        let abstract_glosses = query_file_scalar!(
+            "queries/find_abstract_tags.sql",
+            &*concrete_glosses,
+            tag_system.id(),
+            document_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut abstract_segments = Vec::new();
+        for (segment, glosses) in segments.into_iter().zip(abstract_glosses) {
+            let morphemes = segment.dailp_morphemes(system, &glosses, &stored_segments)?;
+            for (index, (gloss, morpheme)) in glosses.into_iter().zip(morphemes).enumerate() {
+                let role = if index == 0 {
+                    segment.role
+                } else {
+                    WordSegmentRole::Morpheme
+                };
+                let class = if index == 0 { segment.class } else { None };
+                abstract_segments.push((gloss, morpheme, role, class));
+            }
+        }
 
-        let (doc_id, gloss, word_id, index, morpheme, role): (
+        let (doc_id, internal_glosses, word_id, index, morpheme, role, search_key, class): (
+            Vec<_>,
+            Vec<_>,
             Vec<_>,
             Vec<_>,
             Vec<_>,
             Vec<_>,
             Vec<_>,
             Vec<_>,
-        ) = segments
+        ) = abstract_segments
             .into_iter()
             .enumerate()
-            .map(move |(index, segment)| {
+            .map(move |(index, (gloss, morpheme, role, class))| {
+                let search_key = phonetic_search_key(&morpheme);
                 (
                     document_id,
-                    segment.gloss,
+                    gloss,
                     word.id,
                     index as i64, // index of the segment in the word
-                    segment.morpheme,
-                    segment.role,
+                    morpheme,
+                    role,
+                    search_key,
+                    class,
                 )
             })
             .multiunzip();
 
-        // Convert the given glosses if they have an internal for to add to the database.
-        let internal_glosses = query_file_scalar!(
-            "queries/find_internal_glosses.sql",
-            &*gloss,
-            match system_name {
-                Some(CherokeeOrthography::Taoc) => "TAOC",
-                _ =>
-                    return Err(anyhow::anyhow!(
-                        "Other Cherokee systems are currently not supported"
-                    )),
-            }
-        )
-        .fetch_all(&mut *tx)
-        .await?;
-
         // Add any newly created local glosses into morpheme gloss table.
         query_file!(
             "queries/upsert_local_morpheme_glosses.sql",
@@ -888,7 +1667,9 @@ impl Database {
             &*word_id,
             &*index,
             &*morpheme,
-            &*role as _
+            &*role as _,
+            &*search_key,
+            &*class as _
         )
         .execute(&mut *tx)
         .await?;
@@ -2074,7 +2855,7 @@ impl Database {
     pub async fn insert_lexical_entries(
         &self,
         document_id: DocumentId,
-        stems: Vec<AnnotatedForm>,
+        entries: Vec<NewLexicalEntry>,
         surface_forms: Vec<AnnotatedForm>,
     ) -> Result<()> {
         let mut tx = self.client.begin().await?;
@@ -2084,22 +2865,31 @@ impl Database {
             .execute(&mut *tx)
             .await?;
 
-        // Convert the list of stems into a list for each field to prepare for a
-        // bulk DB insertion.
-        let (glosses, shapes): (Vec<_>, Vec<_>) = stems
+        // Convert the list of entries into a list for each field to prepare
+        // for a bulk DB insertion.
+        let (glosses, shapes, headwords, parts_of_speech, senses, page_numbers, indices): (
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+        ) = entries
             .into_iter()
-            .map(|stem| {
+            .filter_map(|entry| Some((entry.gloss()?, entry)))
+            .unique_by(|(gloss, _)| gloss.clone())
+            .map(|(gloss, entry)| {
                 (
-                    stem.segments.as_ref().unwrap()[0]
-                        .gloss
-                        .replace(&[',', '+', '(', ')', '[', ']'] as &[char], " ")
-                        .split_whitespace()
-                        .join("."),
-                    stem,
+                    gloss,
+                    entry.stem.segments.as_ref().unwrap()[0].morpheme.clone(),
+                    entry.stem.source,
+                    entry.part_of_speech,
+                    entry.stem.english_gloss.join("\n"),
+                    entry.stem.position.page_number,
+                    entry.stem.position.index,
                 )
             })
-            .unique_by(|(gloss, _)| gloss.clone())
-            .map(|(gloss, stem)| (gloss, stem.segments.as_ref().unwrap()[0].morpheme.clone()))
             .multiunzip();
 
         // Insert all the morpheme glosses from this dictionary at once.
@@ -2112,6 +2902,20 @@ impl Database {
         .execute(&mut *tx)
         .await?;
 
+        // Then describe each of them as a headword.
+        query_file!(
+            "queries/upsert_lexical_entries.sql",
+            document_id.0,
+            &*glosses,
+            &*headwords,
+            &parts_of_speech as _,
+            &*senses,
+            &*page_numbers,
+            &*indices
+        )
+        .execute(&mut *tx)
+        .await?;
+
         // TODO When we end up referring to morpheme glosses by ID, pass that in.
         self.insert_lexical_words(&mut tx, surface_forms).await?;
 
@@ -2152,6 +2956,7 @@ impl Database {
             commentary,
             page_number,
             index_in_document,
+            search_key,
         ): (
             Vec<_>,
             Vec<_>,
@@ -2162,6 +2967,7 @@ impl Database {
             Vec<_>,
             Vec<_>,
             Vec<_>,
+            Vec<_>,
         ) = forms
             .iter()
             .map(|form| {
@@ -2175,6 +2981,7 @@ impl Database {
                     form.commentary.as_deref(),
                     &*form.position.page_number,
                     form.position.index,
+                    form.search_key(),
                 )
             })
             .multiunzip();
@@ -2189,12 +2996,15 @@ impl Database {
             &*recorded_at as _,
             &*commentary as _,
             &*page_number as _,
-            &*index_in_document
+            &*index_in_document,
+            &*search_key as _
         )
         .fetch_all(&mut *tx)
         .await?;
 
-        let (doc_id, gloss, word_id, index, morpheme, role): (
+        let (doc_id, gloss, word_id, index, morpheme, role, search_key, class): (
+            Vec<_>,
+            Vec<_>,
             Vec<_>,
             Vec<_>,
             Vec<_>,
@@ -2215,6 +3025,7 @@ impl Database {
                                 .replace(&[',', '+', '(', ')', '[', ']'] as &[char], " ")
                                 .split_whitespace()
                                 .join(".");
+                            let search_key = phonetic_search_key(&segment.morpheme);
 
                             (
                                 form.position.document_id.0,
@@ -2223,6 +3034,8 @@ impl Database {
                                 index as i64,
                                 segment.morpheme,
                                 segment.role,
+                                search_key,
+                                segment.class,
                             )
                         })
                 })
@@ -2245,7 +3058,9 @@ impl Database {
             &*word_id,
             &*index,
             &*morpheme,
-            &*role as _
+            &*role as _,
+            &*search_key,
+            &*class as _
         )
         .execute(&mut *tx)
         .await?;
@@ -2276,6 +3091,7 @@ impl Database {
             .as_ref()
             .and_then(|t| t.end_time)
             .map(i64::from);
+        let search_key = form.search_key();
         let word_id: Uuid = query_file_scalar!(
             "queries/upsert_word_in_document.sql",
             form.source,
@@ -2291,13 +3107,16 @@ impl Database {
             char_range,
             form.ingested_audio_track.map(|t| t.resource_url),
             audio_start,
-            audio_end
+            audio_end,
+            search_key
         )
         .fetch_one(&mut *tx)
         .await?;
 
         if let Some(segments) = form.segments {
-            let (document_id, gloss, word_id, index, morpheme, role): (
+            let (document_id, gloss, word_id, index, morpheme, role, search_key, class): (
+                Vec<_>,
+                Vec<_>,
                 Vec<_>,
                 Vec<_>,
                 Vec<_>,
@@ -2314,6 +3133,7 @@ impl Database {
                         .replace(&[',', '+', '(', ')', '[', ']'] as &[char], " ")
                         .split_whitespace()
                         .join(".");
+                    let search_key = phonetic_search_key(&segment.morpheme);
 
                     (
                         document_id,
@@ -2322,6 +3142,8 @@ impl Database {
                         index as i64,
                         segment.morpheme,
                         segment.role as WordSegmentRole,
+                        search_key,
+                        segment.class,
                     )
                 })
                 .multiunzip();
@@ -2341,7 +3163,9 @@ impl Database {
                 &*word_id,
                 &*index,
                 &*morpheme,
-                &*role as _
+                &*role as _,
+                &*search_key,
+                &*class as _
             )
             .execute(&mut *tx)
             .await?;
@@ -2352,40 +3176,60 @@ impl Database {
         Ok(word_id)
     }
 
-    pub async fn insert_morpheme_system(&self, short_name: String, title: String) -> Result<Uuid> {
-        Ok(
-            query_file_scalar!("queries/insert_abbreviation_system.sql", short_name, title)
-                .fetch_one(&self.client)
-                .await?,
+    /// Insert or update a system for displaying the tags of the language with
+    /// the given name.
+    pub async fn insert_morpheme_system(
+        &self,
+        language: &str,
+        short_name: String,
+        title: String,
+    ) -> Result<Uuid> {
+        query_file_scalar!(
+            "queries/insert_abbreviation_system.sql",
+            short_name,
+            title,
+            language
         )
+        .fetch_optional(&self.client)
+        .await?
+        .ok_or_else(|| anyhow::format_err!("Unknown language: {}", language))
     }
 
-    pub async fn insert_abstract_tag(&self, tag: AbstractMorphemeTag) -> Result<()> {
+    /// Insert or update an abstract tag of the language with the given name.
+    pub async fn insert_abstract_tag(
+        &self,
+        tag: AbstractMorphemeTag,
+        language: &str,
+    ) -> Result<()> {
         let abstract_id = query_file_scalar!(
             "queries/upsert_morpheme_tag.sql",
             &tag.id,
-            tag.morpheme_type
+            tag.morpheme_type,
+            language
         )
-        .fetch_one(&self.client)
-        .await?;
-        let doc_id: Option<Uuid> = None;
-        query_file!(
-            "queries/upsert_morpheme_gloss.sql",
-            doc_id,
+        .fetch_optional(&self.client)
+        .await?
+        .ok_or_else(|| anyhow::format_err!("Unknown language: {}", language))?;
+        query_file!("queries/upsert_global_tag_gloss.sql", tag.id, abstract_id)
+            .execute(&self.client)
+            .await?;
+        Ok(())
+    }
+
+    /// Insert a custom abstract tag in the tag language of the given document.
+    pub async fn insert_custom_abstract_tag(
+        &self,
+        tag: AbstractMorphemeTag,
+        document_id: Option<DocumentId>,
+    ) -> Result<Uuid> {
+        let abstract_id = query_file_scalar!(
+            "queries/insert_custom_abstract_tag.sql",
             tag.id,
-            None as Option<String>,
-            Some(abstract_id)
+            "custom",
+            document_id.map(|id| id.0)
         )
         .fetch_one(&self.client)
         .await?;
-        Ok(())
-    }
-
-    pub async fn insert_custom_abstract_tag(&self, tag: AbstractMorphemeTag) -> Result<Uuid> {
-        let abstract_id =
-            query_file_scalar!("queries/insert_custom_abstract_tag.sql", tag.id, "custom")
-                .fetch_one(&self.client)
-                .await?;
         Ok(abstract_id)
     }
 
@@ -2416,7 +3260,8 @@ impl Database {
     pub async fn insert_morpheme_tag(&self, form: MorphemeTag, system_id: Uuid) -> Result<()> {
         let abstract_ids = query_file_scalar!(
             "queries/abstract_tag_ids_from_glosses.sql",
-            &form.internal_tags[..]
+            &form.internal_tags[..],
+            system_id
         )
         .fetch_all(&self.client)
         .await?;
@@ -2447,18 +3292,24 @@ impl Database {
     pub async fn insert_morpheme_relations(&self, links: Vec<LexicalConnection>) -> Result<()> {
         // Ignores glosses without a document ID.
         // TODO Consider whether that's a reasonable constraint.
-        let (left_doc, left_gloss, right_doc, right_gloss): (Vec<_>, Vec<_>, Vec<_>, Vec<_>) =
-            links
-                .into_iter()
-                .filter_map(|link| {
-                    Some((
-                        link.left.document_name?,
-                        link.left.gloss,
-                        link.right.document_name?,
-                        link.right.gloss,
-                    ))
-                })
-                .multiunzip();
+        let (left_doc, left_gloss, right_doc, right_gloss, kinds): (
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+            Vec<_>,
+        ) = links
+            .into_iter()
+            .filter_map(|link| {
+                Some((
+                    link.left.document_name?,
+                    link.left.gloss,
+                    link.right.document_name?,
+                    link.right.gloss,
+                    link.kind,
+                ))
+            })
+            .multiunzip();
         // Don't crash if a morpheme relation fails to insert.
         let _ = query_file!(
             "queries/insert_morpheme_relations.sql",
@@ -2466,12 +3317,153 @@ impl Database {
             &*left_gloss,
             &*right_doc,
             &*right_gloss,
+            &kinds as _,
         )
         .execute(&self.client)
         .await;
         Ok(())
     }
 
+    /// Every link from a gloss defined in the document with the given short
+    /// name to a gloss defined in any document.
+    pub async fn document_morpheme_relations(
+        &self,
+        document_name: &str,
+    ) -> Result<Vec<LexicalConnection>> {
+        Ok(
+            query_file!("queries/document_morpheme_relations.sql", document_name)
+                .fetch_all(&self.client)
+                .await?
+                .into_iter()
+                .map(|link| {
+                    LexicalConnection::with_kind(
+                        MorphemeId::new(Some(document_name.to_owned()), None, link.left_gloss),
+                        MorphemeId::new(Some(link.right_doc_name), None, link.right_gloss),
+                        link.kind,
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// The given morpheme IDs that don't match a gloss defined in their
+    /// document, which links can't be made to. IDs without a document name
+    /// are never resolved.
+    pub async fn unresolved_morphemes<'a>(
+        &self,
+        ids: &'a [MorphemeId],
+    ) -> Result<Vec<&'a MorphemeId>> {
+        let (doc_names, glosses): (Vec<_>, Vec<_>) = ids
+            .iter()
+            .map(|id| {
+                (
+                    id.document_name.clone().unwrap_or_default(),
+                    id.gloss.clone(),
+                )
+            })
+            .unzip();
+        let positions = query_file_scalar!(
+            "queries/unresolved_morpheme_ids.sql",
+            &*doc_names,
+            &*glosses
+        )
+        .fetch_all(&self.client)
+        .await?;
+        // Positions from `with ordinality` start at one.
+        Ok(positions
+            .into_iter()
+            .filter_map(|position| ids.get(position as usize - 1))
+            .collect())
+    }
+
+    /// Link one gloss to another, replacing the kind of any existing link
+    /// between them. Both glosses must be defined in a document.
+    pub async fn insert_morpheme_relation(&self, link: LexicalConnection) -> Result<()> {
+        let (left_doc, right_doc) = Self::relation_documents(&link)?;
+        let inserted = query_file!(
+            "queries/insert_morpheme_relation.sql",
+            left_doc,
+            link.left.gloss,
+            right_doc,
+            link.right.gloss,
+            link.kind as _
+        )
+        .execute(&self.client)
+        .await?
+        .rows_affected();
+        if inserted == 0 {
+            return Err(anyhow::format_err!(
+                "Could not find both {} and {}",
+                link.left,
+                link.right
+            ));
+        }
+        Ok(())
+    }
+
+    /// Remove the link from one gloss to another, returning whether there was
+    /// one to remove.
+    pub async fn delete_morpheme_relation(&self, link: LexicalConnection) -> Result<bool> {
+        let (left_doc, right_doc) = Self::relation_documents(&link)?;
+        let deleted = query_file!(
+            "queries/delete_morpheme_relation.sql",
+            left_doc,
+            link.left.gloss,
+            right_doc,
+            link.right.gloss
+        )
+        .execute(&self.client)
+        .await?
+        .rows_affected();
+        Ok(deleted > 0)
+    }
+
+    fn relation_documents(link: &LexicalConnection) -> Result<(&str, &str)> {
+        match (&link.left.document_name, &link.right.document_name) {
+            (Some(left), Some(right)) => Ok((left, right)),
+            _ => Err(anyhow::format_err!(
+                "Links must be between glosses defined in a document, like DF1975:catch"
+            )),
+        }
+    }
+
+    /// Every gloss within `depth` links of the given morpheme and the links
+    /// between them, with the oldest documents first. Without a document
+    /// name, starts from that gloss in every document.
+    pub async fn etymology_graph(&self, start: &MorphemeId, depth: i32) -> Result<EtymologyGraph> {
+        let nodes: Vec<_> = query_file!(
+            "queries/etymology_nodes.sql",
+            start.gloss,
+            start.document_name,
+            depth
+        )
+        .fetch_all(&self.client)
+        .await?
+        .into_iter()
+        .map(|node| EtymologyNode {
+            id: node.id,
+            morpheme_id: MorphemeId::new(node.document_name.clone(), None, node.gloss.clone())
+                .to_string(),
+            gloss: node.gloss,
+            shape: node.example_shape,
+            document_name: node.document_name,
+            date: node.date,
+        })
+        .collect();
+        let ids: Vec<_> = nodes.iter().map(|node| node.id).collect();
+        let edges = query_file!("queries/morpheme_relations_among.sql", &ids)
+            .fetch_all(&self.client)
+            .await?
+            .into_iter()
+            .map(|edge| EtymologyEdge {
+                from: edge.left_gloss_id,
+                to: edge.right_gloss_id,
+                kind: edge.kind,
+            })
+            .collect();
+        Ok(EtymologyGraph { nodes, edges })
+    }
+
     pub async fn collection(&self, slug: String) -> Result<DocumentCollection> {
         let collection = query_file!("queries/document_group_details.sql", slug)
             .fetch_one(&self.client)
@@ -2822,12 +3814,20 @@ impl Database {
         Ok((document_id, chapter_id))
     }
 
-    pub async fn abbreviation_id_from_short_name(&self, short_name: &str) -> Result<Uuid> {
-        Ok(
-            query_file_scalar!("queries/abbreviation_id_from_short_name.sql", short_name)
-                .fetch_one(&self.client)
-                .await?,
+    /// Find the tag system with the given short name in the tag language of
+    /// the given document.
+    pub async fn abbreviation_id_from_short_name(
+        &self,
+        short_name: &str,
+        document_id: Option<DocumentId>,
+    ) -> Result<Uuid> {
+        Ok(query_file_scalar!(
+            "queries/abbreviation_id_from_short_name.sql",
+            short_name,
+            document_id.map(|id| id.0)
         )
+        .fetch_one(&self.client)
+        .await?)
     }
 
     pub async fn upsert_page(&self, input: NewPageInput) -> Result<String> {
@@ -3181,6 +4181,7 @@ impl Loader<PartsOfWord> for Database {
                         gloss: part.gloss,
                         gloss_id: part.gloss_id,
                         role: part.role,
+                        class: part.class,
                         matching_tag: None,
                     },
                 )
@@ -3198,7 +4199,6 @@ impl Loader<TagId> for Database {
         let glosses: Vec<_> = keys.iter().map(|k| k.0.clone()).collect();
         let systems: Vec<_> = keys
             .iter()
-            .unique()
             .map(|k| {
                 if let Value::Enum(s) = k.1.to_value() {
                     s.as_str().to_owned()
@@ -3207,9 +4207,15 @@ impl Loader<TagId> for Database {
                 }
             })
             .collect();
-        let items = query_file!("queries/morpheme_tags_by_gloss.sql", &glosses, &systems)
-            .fetch_all(&self.client)
-            .await?;
+        let documents: Vec<_> = keys.iter().map(|k| k.2.map(|id| id.0)).collect();
+        let items = query_file!(
+            "queries/morpheme_tags_by_gloss.sql",
+            &glosses,
+            &systems,
+            &documents as _
+        )
+        .fetch_all(&self.client)
+        .await?;
         Ok(items
             .into_iter()
             .map(|tag| {
@@ -3217,6 +4223,7 @@ impl Loader<TagId> for Database {
                     TagId(
                         tag.abstract_gloss.clone(),
                         InputType::parse(Some(Value::Enum(Name::new(tag.system_name)))).unwrap(),
+                        tag.document_id.map(DocumentId),
                     ),
                     MorphemeTag {
                         internal_tags: tag.internal_tags.unwrap_or_default(),
@@ -3511,6 +4518,44 @@ struct BasicWord {
     audio_edited_by_name: Option<String>,
 }
 
+struct BasicLexicalEntry {
+    id: Uuid,
+    gloss_id: Uuid,
+    gloss: String,
+    shape: Option<String>,
+    headword: String,
+    part_of_speech: Option<PartOfSpeech>,
+    senses: Vec<String>,
+    page_number: Option<String>,
+    document_id: Uuid,
+    document_name: String,
+    document_title: String,
+    document_date: Option<Date>,
+    document_order_index: i64,
+}
+
+impl From<BasicLexicalEntry> for LexicalEntry {
+    fn from(entry: BasicLexicalEntry) -> Self {
+        Self {
+            id: entry.id,
+            gloss_id: entry.gloss_id,
+            headword: entry.headword,
+            gloss: entry.gloss,
+            shape: entry.shape,
+            part_of_speech: entry.part_of_speech,
+            senses: entry.senses,
+            source: DocumentReference {
+                id: entry.document_id,
+                short_name: entry.document_name,
+                title: entry.document_title,
+                date: entry.document_date,
+                order_index: entry.document_order_index,
+            },
+            page_number: entry.page_number,
+        }
+    }
+}
+
 impl BasicWord {
     fn audio_slice(&self) -> Option<BasicAudioSlice> {
         Some(BasicAudioSlice {
@@ -3793,8 +4838,11 @@ impl From<BasicComment> for Comment {
     }
 }
 
+/// Abstract gloss and tag system of a functional morpheme, resolved within
+/// the tag language of the given document. Without a document, the tag is
+/// resolved in Cherokee.
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
-pub struct TagId(pub String, pub CherokeeOrthography);
+pub struct TagId(pub String, pub CherokeeOrthography, pub Option<DocumentId>);
 
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct PartsOfWord(pub Uuid);
@@ -3857,3 +4905,84 @@ pub struct WordsInDocument {
     /// List of annotated and potentially segmented forms
     pub forms: Vec<AnnotatedForm>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    #[ignore = "needs a database at DATABASE_URL"]
+    async fn same_gloss_in_two_languages(client: sqlx::PgPool) -> Result<()> {
+        let db = Database { client };
+        sqlx::query("insert into language (name, status) values ('Massachusett', 'approved')")
+            .execute(&db.client)
+            .await?;
+        let systems = [
+            ("Cherokee", "TAOC", "third person"),
+            ("Massachusett", "WLRP", "3rd person"),
+        ];
+        for (language, system, title) in systems {
+            db.insert_abstract_tag(
+                AbstractMorphemeTag {
+                    id: "3SG".to_owned(),
+                    morpheme_type: format!("{} prefix", language),
+                },
+                language,
+            )
+            .await?;
+            let system_id = db
+                .insert_morpheme_system(language, system.to_owned(), system.to_owned())
+                .await?;
+            db.insert_morpheme_tag(
+                MorphemeTag {
+                    internal_tags: vec!["3SG".to_owned()],
+                    tag: "3".to_owned(),
+                    title: title.to_owned(),
+                    shape: None,
+                    details_url: None,
+                    definition: String::new(),
+                    morpheme_type: String::new(),
+                    role_override: None,
+                },
+                system_id,
+            )
+            .await?;
+        }
+
+        // A document in the second language sees only that language's tags.
+        let document_id: Uuid = sqlx::query_scalar(
+            "with g as (insert into document_group (slug, title) values ('test', 'Test') returning id),
+             d as (insert into document (short_name, title, group_id, is_reference)
+                   select 'WAMP', 'Test', g.id, false from g returning id)
+             insert into document_language (document_id, language_id)
+             select d.id, language.id from d, language where language.name = 'Massachusett'
+             returning document_id",
+        )
+        .fetch_one(&db.client)
+        .await?;
+        let cherokee = db.all_tags(CherokeeOrthography::Taoc, None).await?;
+        let massachusett = db
+            .tag_glosses("WLRP", Some(DocumentId(document_id)))
+            .await?;
+        assert_eq!(cherokee.len(), 1);
+        assert_eq!(cherokee[0].title, "third person");
+        assert_eq!(cherokee[0].morpheme_type, "Cherokee prefix");
+        assert_eq!(massachusett, vec!["3".to_owned()]);
+        assert!(db
+            .tag_glosses("TAOC", Some(DocumentId(document_id)))
+            .await?
+            .is_empty());
+
+        // The gloss shared by both languages still belongs to the first one.
+        let language: String = sqlx::query_scalar(
+            "select language.name from morpheme_gloss
+               inner join abstract_morpheme_tag on abstract_morpheme_tag.id = morpheme_gloss.tag_id
+               inner join language on language.id = abstract_morpheme_tag.language_id
+             where morpheme_gloss.document_id is null and morpheme_gloss.gloss = '3SG'",
+        )
+        .fetch_one(&db.client)
+        .await?;
+        assert_eq!(language, "Cherokee");
+        Ok(())
+    }
+}
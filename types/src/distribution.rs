@@ -0,0 +1,97 @@
+//! Statistics about where morphemes appear in the corpus, which grammarians
+//! use as evidence for their analysis of each tag.
+
+use crate::DocumentId;
+use sqlx::types::Uuid;
+
+/// Which side of a morpheme to look at for others appearing with it.
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum MorphemeSide {
+    /// Only morphemes that come earlier in the word, like the prefixes of a root
+    Before,
+    /// Only morphemes that come later in the word, like the suffixes of a root
+    After,
+    /// Morphemes anywhere else in the word
+    #[default]
+    Either,
+}
+
+impl MorphemeSide {
+    /// Whether to include morphemes before and after the given one, in that
+    /// order.
+    pub fn includes(self) -> (bool, bool) {
+        match self {
+            Self::Before => (true, false),
+            Self::After => (false, true),
+            Self::Either => (true, true),
+        }
+    }
+}
+
+/// How to group the words containing a morpheme.
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DistributionGrouping {
+    /// One group for each document
+    #[default]
+    Document,
+    /// One group for each decade that documents were written in
+    Decade,
+}
+
+/// How often another morpheme appears in the same words as a given one.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct MorphemeCooccurrence {
+    /// Gloss of the other morpheme
+    pub gloss: String,
+    /// Number of words containing both morphemes
+    pub word_count: i64,
+    /// A few of the words containing both morphemes
+    pub example_word_ids: Vec<Uuid>,
+}
+
+/// How often a morpheme appears in one document or decade.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct MorphemeDistributionBucket {
+    /// Document of this group, when grouping by document
+    pub document_id: Option<DocumentId>,
+    /// Short name of the document, when grouping by document
+    pub document_name: Option<String>,
+    /// First year of the decade, when grouping by decade. Missing for
+    /// documents without a date.
+    pub decade: Option<i32>,
+    /// Number of words in this group containing the morpheme
+    pub word_count: i64,
+    /// Number of words in this group overall
+    pub total_words: i64,
+    /// Share of the words in this group that contain the morpheme
+    pub relative_frequency: f64,
+    /// A few of the words in this group containing the morpheme
+    pub example_word_ids: Vec<Uuid>,
+}
+
+/// A functional morpheme that never appears in the same word as a given one.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct MorphemeExclusion {
+    /// Gloss of the other morpheme
+    pub gloss: String,
+    /// Number of words containing the other morpheme
+    pub word_count: i64,
+    /// How many words would contain both if the two morphemes appeared
+    /// independently of each other. The higher this is, the less likely it is
+    /// that they never appear together by chance.
+    pub expected_cooccurrences: f64,
+    /// A few of the words containing the other morpheme
+    pub example_word_ids: Vec<Uuid>,
+}
+
+impl MorphemeDistributionBucket {
+    /// Share of `total` words that `count` words make up, or zero for an empty
+    /// group.
+    pub fn frequency(count: i64, total: i64) -> f64 {
+        if total > 0 {
+            count as f64 / total as f64
+        } else {
+            0.0
+        }
+    }
+}
@@ -207,6 +207,15 @@ impl Language {
     async fn approved(&self) -> bool {
         matches!(self.status, ApprovalStatus::Approved)
     }
+
+    /// Writing systems registered for this language
+    async fn orthographies(&self) -> Vec<crate::RegisteredOrthography> {
+        crate::OrthographyRegistry::global()
+            .for_language(&self.name)
+            .iter()
+            .map(|o| crate::RegisteredOrthography::new(&self.name, *o))
+            .collect()
+    }
 }
 
 /// Converts Language struct to corresponding Uuid
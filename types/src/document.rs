@@ -8,7 +8,8 @@ use crate::person::{
 };
 use crate::{
     auth::UserInfo, comment::Comment, date::DateInput, slugify, AnnotatedForm, AudioSlice,
-    Database, Date, Translation, TranslationBlock,
+    CherokeeOrthography, Database, Date, IgtFormat, InterlinearExample, OrthographyId, Translation,
+    TranslationBlock,
 };
 
 use itertools::Itertools;
@@ -177,6 +178,19 @@ impl AnnotatedDoc {
             .await?)
     }
 
+    /// The whole document as interlinear glossed text, one example per
+    /// paragraph, with morphemes and tags written in the given system.
+    async fn interlinear_text(
+        &self,
+        context: &async_graphql::Context<'_>,
+        #[graphql(default_with = "CherokeeOrthography::Taoc.into()")] system: OrthographyId,
+        #[graphql(default_with = "IgtFormat::PlainText")] format: IgtFormat,
+    ) -> FieldResult<String> {
+        let db = context.data::<DataLoader<Database>>()?;
+        let examples = InterlinearExample::from_document(db, self, &system).await?;
+        Ok(InterlinearExample::render_all(&examples, format))
+    }
+
     /// All the words contained in this document, dropping structural formatting
     /// like line and page breaks.
     async fn forms(
@@ -459,6 +473,19 @@ impl DocumentParagraph {
             .unwrap_or_default())
     }
 
+    /// This paragraph as interlinear glossed text, with morphemes and tags
+    /// written in the given system.
+    async fn interlinear_text(
+        &self,
+        context: &async_graphql::Context<'_>,
+        #[graphql(default_with = "CherokeeOrthography::Taoc.into()")] system: OrthographyId,
+        #[graphql(default_with = "IgtFormat::PlainText")] format: IgtFormat,
+    ) -> FieldResult<String> {
+        let db = context.data::<DataLoader<Database>>()?;
+        let example = InterlinearExample::from_paragraph(db, self, &system).await?;
+        Ok(InterlinearExample::render_all(&[example], format))
+    }
+
     /// Get comments on this paragraph
     async fn comments(&self, context: &async_graphql::Context<'_>) -> FieldResult<Vec<Comment>> {
         let db = context.data::<DataLoader<Database>>()?.loader();
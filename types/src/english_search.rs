@@ -0,0 +1,74 @@
+//! Find Cherokee forms from English, using the translations of dictionary
+//! entries, morphemes, words, and paragraphs.
+
+use crate::{AnnotatedDoc, Database, DocumentId};
+use async_graphql::{dataloader::DataLoader, Context, FieldResult};
+use sqlx::types::Uuid;
+
+/// Where the English that matched a Cherokee form comes from. Matches are
+/// ranked in the order listed here.
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnglishMatchSource {
+    /// The senses or gloss of a dictionary entry
+    DictionaryEntry,
+    /// The gloss of a morpheme used in words, like "catch" in a letter
+    MorphemeGloss,
+    /// The translation of a word
+    Word,
+    /// The translation of a whole paragraph
+    Paragraph,
+}
+
+impl EnglishMatchSource {
+    /// The source with the given rank, as numbered by queries.
+    pub fn from_rank(rank: i32) -> Self {
+        match rank {
+            0 => Self::DictionaryEntry,
+            1 => Self::MorphemeGloss,
+            2 => Self::Word,
+            _ => Self::Paragraph,
+        }
+    }
+}
+
+/// A Cherokee form whose English translation matches a search.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+#[graphql(complex)]
+pub struct EnglishMatch {
+    /// Where the matching English comes from
+    pub source: EnglishMatchSource,
+    /// The Cherokee form, like a dictionary headword, the most common shape of
+    /// a morpheme, a word as written in its document, or a whole paragraph
+    pub cherokee: String,
+    /// Romanized spelling of the form, if known
+    pub romanized: Option<String>,
+    /// The English translation that matched
+    pub english: String,
+    /// How closely the English matches the search, higher being closer
+    pub score: f64,
+    /// Number of words in the corpus that attest this form. Dictionary
+    /// entries count words in other documents that use their morpheme or one
+    /// linked to it.
+    pub attestation_count: i64,
+    /// Identifier of the morpheme for entries and glosses, like "DF1975:catch"
+    pub morpheme_id: Option<String>,
+    /// Documents where this form appears with this translation
+    #[graphql(skip)]
+    pub document_ids: Vec<Uuid>,
+}
+
+#[async_graphql::ComplexObject]
+impl EnglishMatch {
+    /// Documents where this form appears with this translation
+    async fn documents(&self, context: &Context<'_>) -> FieldResult<Vec<AnnotatedDoc>> {
+        let mut docs = context
+            .data::<DataLoader<Database>>()?
+            .load_many(self.document_ids.iter().map(|&id| DocumentId(id)))
+            .await?;
+        Ok(self
+            .document_ids
+            .iter()
+            .filter_map(|&id| docs.remove(&DocumentId(id)))
+            .collect())
+    }
+}
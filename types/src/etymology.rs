@@ -0,0 +1,119 @@
+//! Typed links between morpheme glosses across documents, which together
+//! trace how words changed over time.
+
+use crate::Date;
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    postgres::{PgHasArrayType, PgTypeInfo},
+    types::Uuid,
+};
+
+/// How one morpheme gloss came from or led to another. Each link points from
+/// one gloss to another, as described by each variant.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Serialize,
+    Deserialize,
+    sqlx::Type,
+    async_graphql::Enum,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[sqlx(type_name = "morpheme_relation_kind")]
+pub enum MorphemeRelationKind {
+    /// Generic link between two glosses, without assuming which came first
+    #[default]
+    Related,
+    /// From a phrase to one of the words that make it up
+    PhraseToWord,
+    /// From a word to the lexical entry of its root
+    WordToEntry,
+    /// From a lexical entry to an older entry it descends from
+    OlderEntry,
+    /// From a phrase to a newer single word that merged it together
+    Merger,
+}
+
+impl PgHasArrayType for MorphemeRelationKind {
+    fn array_type_info() -> PgTypeInfo {
+        <&str as PgHasArrayType>::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        <&str as PgHasArrayType>::array_compatible(ty)
+    }
+}
+
+/// One morpheme gloss in an etymology graph.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct EtymologyNode {
+    /// Database ID of the morpheme gloss, which edges refer to
+    pub id: Uuid,
+    /// Identifier of the gloss, like "DF1975:catch"
+    pub morpheme_id: String,
+    /// The gloss itself, like "catch"
+    pub gloss: String,
+    /// Example shape of the morpheme in DAILP internal orthography
+    pub shape: Option<String>,
+    /// Short name of the document defining this gloss, missing for glosses
+    /// shared by all documents
+    pub document_name: Option<String>,
+    /// Date of the document defining this gloss, if known
+    pub date: Option<Date>,
+}
+
+/// A directed link between two morpheme glosses.
+#[derive(async_graphql::SimpleObject, Clone, Debug, PartialEq, Eq)]
+pub struct EtymologyEdge {
+    /// Gloss this link starts from
+    pub from: Uuid,
+    /// Gloss this link points to
+    pub to: Uuid,
+    /// How the two glosses are related
+    pub kind: MorphemeRelationKind,
+}
+
+/// The glosses linked to a morpheme and the links between them.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct EtymologyGraph {
+    /// Every gloss in the graph, oldest document first
+    pub nodes: Vec<EtymologyNode>,
+    /// Every link between two glosses in the graph
+    pub edges: Vec<EtymologyEdge>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_graphql::resolver_utils::EnumType;
+
+    #[test]
+    fn relation_kinds_match_database() {
+        // Each kind is stored under its variant name, so the database enum
+        // needs exactly the same labels in the same order.
+        let migration =
+            include_str!("../migrations/20261018160000_add-morpheme-relation-kinds.sql");
+        let labels: Vec<&str> = migration
+            .split("as enum (")
+            .nth(1)
+            .and_then(|rest| rest.split(')').next())
+            .unwrap()
+            .split(',')
+            .map(|label| label.trim().trim_matches('\''))
+            .collect();
+        let kinds: Vec<String> = MorphemeRelationKind::items()
+            .iter()
+            .map(|item| format!("{:?}", item.value))
+            .collect();
+        assert_eq!(labels, kinds);
+        // Links without a kind, like those from older spreadsheets, are generic.
+        assert_eq!(
+            MorphemeRelationKind::default(),
+            MorphemeRelationKind::Related
+        );
+    }
+}
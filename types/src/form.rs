@@ -1,6 +1,8 @@
 use crate::{
-    comment::Comment, AnnotatedDoc, AudioSlice, CherokeeOrthography, Database, Date, DocumentId,
-    MorphemeSegmentUpdate, PartsOfWord, PositionInDocument, TagId, WordSegment, WordSegmentRole,
+    comment::Comment, phonetic_search_key, AnnotatedDoc, AudioSlice, CherokeeOrthography, Database,
+    Date, DocumentId, LanguagesForDocument, MorphemeSegmentUpdate, Orthography, OrthographyId,
+    PartsOfWord, PhonemicString, PositionInDocument, SpellingOptions, SyllableAlignment, TagId,
+    WordSegment, WordSegmentRole,
 };
 use async_graphql::{dataloader::DataLoader, FieldResult, MaybeUndefined};
 use itertools::Itertools;
@@ -61,102 +63,103 @@ impl AnnotatedForm {
     /// For example, a verb form glossed as "he catches" might have a root morpheme
     /// corresponding to "catch."
     async fn root(&self, context: &async_graphql::Context<'_>) -> FieldResult<Option<WordSegment>> {
-        let segments = self.segments(context, CherokeeOrthography::Taoc).await?;
-        for seg in segments {
-            if is_root_morpheme(&seg.gloss) {
-                return Ok(Some(seg));
-            }
-        }
-        Ok(None)
+        let db = context.data::<DataLoader<Database>>()?;
+        let segments = self
+            .concrete_segments(db, CherokeeOrthography::Taoc)
+            .await?;
+        Ok(segments.into_iter().find(WordSegment::is_root))
     }
 
-    async fn romanized_source(&self, system: CherokeeOrthography) -> Option<Cow<'_, str>> {
-        self.simple_phonetics.as_ref().map(|phonetic| {
-            if system == CherokeeOrthography::Learner {
-                crate::lexical::simple_phonetics_to_worcester(phonetic).into()
-            } else {
-                phonetic.into()
+    /// The phonetic layer of this word written with the given system, which
+    /// may be any registered orthography for the language of this word's
+    /// document. Passing `spelling` writes the phonemic layer with the
+    /// spelling choices of a romanized Cherokee system, changed by those
+    /// options.
+    async fn romanized_source(
+        &self,
+        context: &async_graphql::Context<'_>,
+        #[graphql(default_with = "CherokeeOrthography::Taoc.into()")] system: OrthographyId,
+        spelling: Option<SpellingOptions>,
+    ) -> FieldResult<Option<Cow<'_, str>>> {
+        let orthography = self.orthography_for(context, &system).await?;
+        Ok(match (system.cherokee(), spelling) {
+            (Some(system), Some(spelling)) => {
+                let profile = system.profile_with(&spelling)?;
+                self.phonemic.as_ref().map(|phonemic| {
+                    PhonemicString::parse_dailp(phonemic)
+                        .render(&profile)
+                        .into()
+                })
+            }
+            (Some(system), None) => self.romanized_in(system),
+            (None, Some(_)) => {
+                return Err(anyhow::format_err!(
+                    "Spelling options only apply to Cherokee systems, not {}",
+                    system.0
+                )
+                .into())
             }
+            (None, None) => self
+                .phonemic
+                .as_ref()
+                .map(|phonemic| orthography.render(phonemic).into()),
         })
     }
 
+    /// Each syllable of the phonemic layer lined up with the source syllabary
+    /// character that should spell it. Mismatches may point to transcription
+    /// slips in either layer.
+    async fn syllables(&self) -> Vec<SyllableAlignment> {
+        self.phonemic
+            .as_ref()
+            .map(|phonemic| {
+                PhonemicString::parse_dailp(phonemic).align_with_syllabary(&self.source)
+            })
+            .unwrap_or_default()
+    }
+
+    /// The morphemic segmentation of this word written with the given system,
+    /// which may be any registered orthography for the language of this
+    /// word's document. Cherokee systems also choose which tags to show.
+    /// Passing `spelling` writes each morpheme with the spelling choices of a
+    /// romanized Cherokee system, changed by those options.
     async fn segments(
         &self,
         context: &async_graphql::Context<'_>,
-        system: CherokeeOrthography,
+        #[graphql(default_with = "CherokeeOrthography::Taoc.into()")] system: OrthographyId,
+        spelling: Option<SpellingOptions>,
     ) -> FieldResult<Vec<WordSegment>> {
-        let db = context.data::<DataLoader<Database>>()?;
-        // 1. To convert to a concrete analysis, start with a list of abstract tags.
-        let abstract_segments = db
-            .load_one(PartsOfWord(*self.id.as_ref().unwrap()))
-            .await?
-            .unwrap_or_default();
+        let orthography = self.orthography_for(context, &system).await?;
+        let cherokee = system.cherokee();
+        let profile = match (cherokee, spelling) {
+            (Some(system), Some(spelling)) => Some(system.profile_with(&spelling)?),
+            (None, Some(_)) => {
+                return Err(anyhow::format_err!(
+                    "Spelling options only apply to Cherokee systems, not {}",
+                    system.0
+                )
+                .into())
+            }
+            _ => None,
+        };
 
-        // 2. Request all concrete tags that start with each abstract tag.
-        let concrete_tag_matches = db
-            .load_many(
-                abstract_segments
-                    .iter()
-                    .map(|seg| TagId(seg.gloss.clone(), system)),
-            )
+        let db = context.data::<DataLoader<Database>>()?;
+        let mut concrete_segments = self
+            .concrete_segments(db, cherokee.unwrap_or(CherokeeOrthography::Taoc))
             .await?;
 
-        // 3. Pick the longest match for each abstract segment.
-        let mut concrete_segments = Vec::new();
-        let mut curr_index = 0;
-        for (idx, abstract_segment) in abstract_segments.iter().enumerate() {
-            // If this segment has already been filled by a previous match, skip it.
-            if idx < curr_index {
-                continue;
+        // Render each morpheme up front, leaving no system for the segment to
+        // convert with later.
+        if let Some(profile) = profile {
+            for seg in &mut concrete_segments {
+                seg.morpheme = PhonemicString::parse_dailp(&seg.morpheme).render(&profile);
+                seg.system = None;
             }
-
-            let concrete_tags =
-                concrete_tag_matches.get(&TagId(abstract_segment.gloss.clone(), system));
-            if let Some(concrete_tags) = concrete_tags {
-                for concrete_tag in concrete_tags {
-                    // Check whether the whole sequence of abstract tags is the current
-                    // start of the abstract segment list.
-                    let abstract_matches = concrete_tag
-                        .internal_tags
-                        .iter()
-                        .zip(abstract_segments.iter().skip(curr_index));
-                    let is_match = abstract_matches.clone().all(|(a, b)| *a == b.gloss);
-                    if is_match {
-                        let corresponding_segments = abstract_segments
-                            .iter()
-                            .skip(curr_index)
-                            .take(concrete_tag.internal_tags.len());
-                        concrete_segments.push(WordSegment {
-                            system: Some(system),
-                            // Use the segment type of the first abstract one
-                            // unless the concrete segment overrides the segment type.
-                            role: concrete_tag
-                                .role_override
-                                .or_else(|| {
-                                    corresponding_segments.clone().next().map(|seg| seg.role)
-                                })
-                                .unwrap_or(WordSegmentRole::Morpheme),
-                            morpheme: corresponding_segments.map(|seg| &seg.morpheme).join(""),
-                            gloss: concrete_tag.tag.clone(),
-                            gloss_id: None,
-                            matching_tag: Some(concrete_tag.clone()),
-                        });
-                        curr_index += concrete_tag.internal_tags.len();
-                        break;
-                    }
-                }
-            } else {
-                // If this abstract segment was unmatched (probably a root),
-                // then just use it directly.
-                concrete_segments.push(WordSegment {
-                    system: Some(system),
-                    ..abstract_segment.clone()
-                });
-                curr_index += 1;
+        } else if cherokee.is_none() {
+            for seg in &mut concrete_segments {
+                seg.morpheme = orthography.render(&seg.morpheme);
+                seg.system = None;
             }
-            // if !success {
-            //     anyhow::bail!("Failed to generate all morpheme tags");
-            // }
         }
         Ok(concrete_segments)
     }
@@ -254,11 +257,174 @@ impl AnnotatedForm {
 }
 
 impl AnnotatedForm {
-    /// Look for a root morpheme in the word using crude case checks.
-    pub fn find_root(&self) -> Option<&WordSegment> {
-        self.segments
-            .as_ref()
-            .and_then(|segments| segments.iter().find(|seg| is_root_morpheme(&seg.gloss)))
+    /// Look up a registered orthography, making sure that it's for the
+    /// language of this word's document. Documents without a language are
+    /// taken to be in Cherokee.
+    async fn orthography_for(
+        &self,
+        context: &async_graphql::Context<'_>,
+        system: &OrthographyId,
+    ) -> FieldResult<&'static dyn Orthography> {
+        let (language, orthography) = system.resolve()?;
+        let languages = context
+            .data::<DataLoader<Database>>()?
+            .load_one(LanguagesForDocument(self.position.document_id.0))
+            .await?
+            .unwrap_or_default();
+        let matches = if languages.is_empty() {
+            language.eq_ignore_ascii_case("Cherokee")
+        } else {
+            languages
+                .iter()
+                .any(|l| l.name.eq_ignore_ascii_case(language))
+        };
+        if matches {
+            Ok(orthography)
+        } else {
+            Err(anyhow::format_err!(
+                "The {} orthography is for {}, which is not the language of this word",
+                orthography.id(),
+                language
+            )
+            .into())
+        }
+    }
+
+    /// The phonetic layer of this word written with the given Cherokee system.
+    pub fn romanized_in(&self, system: CherokeeOrthography) -> Option<Cow<'_, str>> {
+        match system {
+            // Writing the phonemic layer back in syllabary lets editors check it
+            // against the source text, while IPA can only come from the
+            // phonemic layer.
+            CherokeeOrthography::Syllabary | CherokeeOrthography::Ipa => self
+                .phonemic
+                .as_ref()
+                .map(|phonemic| system.convert(phonemic).into()),
+            CherokeeOrthography::Learner => self
+                .simple_phonetics
+                .as_ref()
+                .map(|phonetic| crate::lexical::simple_phonetics_to_worcester(phonetic).into()),
+            _ => self.simple_phonetics.as_deref().map(Cow::from),
+        }
+    }
+
+    /// Simple phonetics to store for a romanized source that an editor
+    /// submitted in the given system, or nothing to keep the stored value.
+    /// Reading back from some systems loses tone, vowel length, or consonant
+    /// distinctions, so a value that reads the same as the stored one is
+    /// left alone.
+    pub fn simple_phonetics_from(
+        &self,
+        system: CherokeeOrthography,
+        submitted: &str,
+    ) -> Option<String> {
+        if self.romanized_in(system).as_deref() == Some(submitted) {
+            return None;
+        }
+        match system {
+            CherokeeOrthography::Taoc | CherokeeOrthography::Crg => Some(submitted.to_owned()),
+            // Glottal stops are stored as apostrophes, like the spreadsheets
+            // they were migrated from.
+            CherokeeOrthography::Learner => {
+                Some(crate::lexical::worcester_to_simple_phonetics(submitted).replace('ʔ', "'"))
+            }
+            // The syllabary and IPA show the phonemic layer instead, which
+            // can't be edited here.
+            CherokeeOrthography::Syllabary | CherokeeOrthography::Ipa => None,
+        }
+    }
+
+    /// The morphemic segmentation of this word, with tags from the given
+    /// Cherokee system. Each segment converts its morpheme to that system when
+    /// read with [`WordSegment::get_morpheme`].
+    pub async fn concrete_segments(
+        &self,
+        db: &DataLoader<Database>,
+        system: CherokeeOrthography,
+    ) -> anyhow::Result<Vec<WordSegment>> {
+        // 1. To convert to a concrete analysis, start with a list of abstract tags.
+        let abstract_segments = db
+            .load_one(PartsOfWord(*self.id.as_ref().unwrap()))
+            .await?
+            .unwrap_or_default();
+
+        // 2. Request all concrete tags that start with each abstract tag, in
+        // the tag language of this word's document.
+        let document_id = Some(self.position.document_id);
+        let concrete_tag_matches = db
+            .load_many(
+                abstract_segments
+                    .iter()
+                    .map(|seg| TagId(seg.gloss.clone(), system.tag_system(), document_id)),
+            )
+            .await?;
+
+        // 3. Pick the longest match for each abstract segment.
+        let mut concrete_segments = Vec::new();
+        let mut curr_index = 0;
+        for (idx, abstract_segment) in abstract_segments.iter().enumerate() {
+            // If this segment has already been filled by a previous match, skip it.
+            if idx < curr_index {
+                continue;
+            }
+
+            let concrete_tags = concrete_tag_matches.get(&TagId(
+                abstract_segment.gloss.clone(),
+                system.tag_system(),
+                document_id,
+            ));
+            if let Some(concrete_tags) = concrete_tags {
+                for concrete_tag in concrete_tags {
+                    // Check whether the whole sequence of abstract tags is the current
+                    // start of the abstract segment list.
+                    let abstract_matches = concrete_tag
+                        .internal_tags
+                        .iter()
+                        .zip(abstract_segments.iter().skip(curr_index));
+                    let is_match = abstract_matches.clone().all(|(a, b)| *a == b.gloss);
+                    if is_match {
+                        let corresponding_segments = abstract_segments
+                            .iter()
+                            .skip(curr_index)
+                            .take(concrete_tag.internal_tags.len());
+                        concrete_segments.push(WordSegment {
+                            system: Some(system),
+                            // Use the segment type of the first abstract one
+                            // unless the concrete segment overrides the segment type.
+                            role: concrete_tag
+                                .role_override
+                                .or_else(|| {
+                                    corresponding_segments.clone().next().map(|seg| seg.role)
+                                })
+                                .unwrap_or(WordSegmentRole::Morpheme),
+                            class: corresponding_segments
+                                .clone()
+                                .next()
+                                .and_then(|seg| seg.class),
+                            morpheme: corresponding_segments.map(|seg| &seg.morpheme).join(""),
+                            gloss: concrete_tag.tag.clone(),
+                            gloss_id: None,
+                            matching_tag: Some(concrete_tag.clone()),
+                        });
+                        curr_index += concrete_tag.internal_tags.len();
+                        break;
+                    }
+                }
+            } else {
+                // If this abstract segment was unmatched (probably a root),
+                // then just use it directly.
+                concrete_segments.push(WordSegment {
+                    system: Some(system),
+                    ..abstract_segment.clone()
+                });
+                curr_index += 1;
+            }
+            // if !success {
+            //     anyhow::bail!("Failed to generate all morpheme tags");
+            // }
+        }
+
+        Ok(concrete_segments)
     }
 
     /// Find a morpheme within this word with the given exact gloss.
@@ -279,14 +445,15 @@ impl AnnotatedForm {
             self.source.contains('?')
         }
     }
-}
 
-/// Is the given gloss for a root morpheme? This is a crude calculation that just
-/// checks if there are any lowercase characters. Convention says that typically
-/// functional morpheme tags are all uppercase (plus numbers and punctuation),
-/// so having lowercase characters indicates a lexical morpheme gloss.
-pub fn is_root_morpheme(s: &str) -> bool {
-    s.contains(|c: char| c.is_lowercase())
+    /// Loose phonetic key for this word, taken from the phonemic layer if
+    /// there is one and the simple phonetics otherwise.
+    pub fn search_key(&self) -> Option<String> {
+        self.phonemic
+            .as_deref()
+            .or(self.simple_phonetics.as_deref())
+            .map(phonetic_search_key)
+    }
 }
 
 /// A single word in an annotated document that can be edited.
@@ -299,6 +466,9 @@ pub struct AnnotatedFormUpdate {
     pub source: MaybeUndefined<String>,
     /// Possible update to normalized source content
     pub romanized_source: MaybeUndefined<String>,
+    /// Which Cherokee representation system the romanized source is written
+    /// with, TAOC if not given
+    pub system: Option<CherokeeOrthography>,
     /// Possible update to commentary
     pub commentary: MaybeUndefined<String>,
     /// Updated segments
@@ -322,3 +492,104 @@ impl<T> MaybeUndefinedExt<T> for MaybeUndefined<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word() -> AnnotatedForm {
+        AnnotatedForm {
+            id: None,
+            source: "ᎠᏆᏓᏂ".to_owned(),
+            normalized_source: None,
+            simple_phonetics: Some("agwa:da'ni".to_owned()),
+            phonemic: Some("akwǎːtaʔni".to_owned()),
+            segments: Some(vec![
+                WordSegment::new("a".to_owned(), "3SG.B".to_owned(), None),
+                WordSegment::new("kwǎːtaʔn".to_owned(), "know".to_owned(), None),
+                WordSegment::new("i".to_owned(), "PRS".to_owned(), None),
+            ]),
+            english_gloss: Vec::new(),
+            commentary: None,
+            line_break: None,
+            page_break: None,
+            position: PositionInDocument::new(DocumentId::default(), "1".to_owned(), 1),
+            date_recorded: None,
+            ingested_audio_track: None,
+        }
+    }
+
+    /// Submit every segment and the romanized source of a word exactly as an
+    /// editor viewing it in the given system sees them.
+    fn resubmit(
+        word: &AnnotatedForm,
+        system: CherokeeOrthography,
+    ) -> (Option<String>, Vec<String>) {
+        let simple_phonetics = word
+            .romanized_in(system)
+            .and_then(|romanized| word.simple_phonetics_from(system, &romanized));
+        let stored = word.segments.as_ref().unwrap();
+        let morphemes = stored
+            .iter()
+            .flat_map(|seg| {
+                let update = MorphemeSegmentUpdate {
+                    system: Some(system),
+                    morpheme: system.convert(&seg.morpheme),
+                    gloss: seg.gloss.clone(),
+                    role: seg.role,
+                    class: seg.class,
+                };
+                update
+                    .dailp_morphemes(system, std::slice::from_ref(&seg.gloss), stored)
+                    .unwrap()
+            })
+            .collect();
+        (simple_phonetics, morphemes)
+    }
+
+    #[test]
+    fn unchanged_save_keeps_stored_forms() {
+        let word = word();
+        let stored: Vec<_> = word
+            .segments
+            .iter()
+            .flatten()
+            .map(|seg| seg.morpheme.clone())
+            .collect();
+        for system in [CherokeeOrthography::Learner, CherokeeOrthography::Syllabary] {
+            // Reading the displayed forms back would lose detail.
+            assert_ne!(
+                system.to_dailp(&system.convert(&stored[1])).unwrap(),
+                stored[1]
+            );
+            let (simple_phonetics, morphemes) = resubmit(&word, system);
+            assert_eq!(simple_phonetics, None, "{:?}", system);
+            assert_eq!(morphemes, stored, "{:?}", system);
+        }
+    }
+
+    #[test]
+    fn changed_save_reads_back_submitted_forms() {
+        let word = word();
+        assert_eq!(
+            word.simple_phonetics_from(CherokeeOrthography::Learner, "aquada'nii")
+                .as_deref(),
+            Some("agwada'nii")
+        );
+        let update = MorphemeSegmentUpdate {
+            system: Some(CherokeeOrthography::Crg),
+            morpheme: "ii".to_owned(),
+            gloss: "PRS".to_owned(),
+            role: WordSegmentRole::Morpheme,
+            class: None,
+        };
+        let morphemes = update
+            .dailp_morphemes(
+                CherokeeOrthography::Crg,
+                &["PRS".to_owned()],
+                word.segments.as_ref().unwrap(),
+            )
+            .unwrap();
+        assert_eq!(morphemes, ["ii"]);
+    }
+}
@@ -0,0 +1,224 @@
+//! Ranked full-text search over the words and paragraphs of every document,
+//! with highlights showing which part of each result matched.
+
+use crate::{AnnotatedDoc, AnnotatedForm, Database, DocumentId, DocumentParagraph};
+use async_graphql::{dataloader::DataLoader, Context, FieldResult};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Uuid;
+
+/// Marks the start of a highlight in text built by the database.
+const HIGHLIGHT_START: char = '\u{2}';
+/// Marks the end of a highlight in text built by the database.
+const HIGHLIGHT_STOP: char = '\u{3}';
+
+/// Part of a search result that can match a query.
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchField {
+    /// Syllabary source of a word
+    Source,
+    /// Simple phonetics of a word
+    SimplePhonetics,
+    /// English gloss of a word
+    EnglishGloss,
+    /// Commentary on a word
+    Commentary,
+    /// English translation of a paragraph
+    Translation,
+}
+
+/// A run of text that either matched a search or didn't.
+#[derive(async_graphql::SimpleObject, Clone, Debug, PartialEq, Eq)]
+pub struct HighlightFragment {
+    /// Text of this run, exactly as written in the field
+    pub text: String,
+    /// Whether this run matched the search
+    pub matched: bool,
+}
+
+/// The text of one field of a search result, split into runs that did and
+/// didn't match the search.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct SearchHighlight {
+    /// Field that matched
+    pub field: SearchField,
+    /// Runs of text in reading order. Long fields may be cut down to the
+    /// parts around each match.
+    pub fragments: Vec<HighlightFragment>,
+}
+
+impl SearchHighlight {
+    /// Split text that the database wrapped in highlight markers. Returns
+    /// nothing if no part of the text was highlighted.
+    pub fn from_marked(field: SearchField, marked: &str) -> Option<Self> {
+        let mut fragments = Vec::new();
+        let mut matched = false;
+        for (idx, piece) in marked.split([HIGHLIGHT_START, HIGHLIGHT_STOP]).enumerate() {
+            // Each marker flips between matched and unmatched text.
+            if idx > 0 {
+                matched = !matched;
+            }
+            if !piece.is_empty() {
+                fragments.push(HighlightFragment {
+                    text: piece.to_owned(),
+                    matched,
+                });
+            }
+        }
+        Self::new(field, fragments)
+    }
+
+    /// Highlight the same characters of `original` that are highlighted in
+    /// `marked`, a copy of the original with some characters replaced by
+    /// others. Returns nothing if the two have different lengths or no part
+    /// of the text was highlighted.
+    pub fn from_marked_copy(field: SearchField, marked: &str, original: &str) -> Option<Self> {
+        let copy = Self::from_marked(field, marked)?;
+        let mut chars = original.chars();
+        let fragments: Vec<_> = copy
+            .fragments
+            .into_iter()
+            .map(|fragment| HighlightFragment {
+                text: chars.by_ref().take(fragment.text.chars().count()).collect(),
+                matched: fragment.matched,
+            })
+            .collect();
+        let text_len: usize = fragments.iter().map(|f| f.text.len()).sum();
+        if chars.next().is_some() || text_len != original.len() {
+            return None;
+        }
+        Self::new(field, fragments)
+    }
+
+    /// Highlight every place that any of the given terms appears in `text`,
+    /// ignoring case. Returns nothing if none of them appear.
+    pub fn from_terms(field: SearchField, text: &str, terms: &[String]) -> Option<Self> {
+        let chars: Vec<char> = text.chars().collect();
+        let terms: Vec<Vec<char>> = terms.iter().map(|t| t.chars().collect()).collect();
+        let mut mask = vec![false; chars.len()];
+        for start in 0..chars.len() {
+            for term in &terms {
+                let end = start + term.len();
+                if !term.is_empty()
+                    && end <= chars.len()
+                    && chars[start..end]
+                        .iter()
+                        .zip(term)
+                        .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+                {
+                    mask[start..end].fill(true);
+                }
+            }
+        }
+        let mut fragments: Vec<HighlightFragment> = Vec::new();
+        for (c, matched) in chars.into_iter().zip(mask) {
+            match fragments.last_mut() {
+                Some(last) if last.matched == matched => last.text.push(c),
+                _ => fragments.push(HighlightFragment {
+                    text: c.to_string(),
+                    matched,
+                }),
+            }
+        }
+        Self::new(field, fragments)
+    }
+
+    fn new(field: SearchField, fragments: Vec<HighlightFragment>) -> Option<Self> {
+        if fragments.iter().any(|f| f.matched) {
+            Some(Self { field, fragments })
+        } else {
+            None
+        }
+    }
+}
+
+/// Pieces of a search to look for within Cherokee text: the whole search,
+/// and each word of it that isn't excluded with a leading "-".
+pub(crate) fn search_terms(query: &str) -> Vec<String> {
+    let query = query.trim();
+    let words = query
+        .split_whitespace()
+        .filter(|w| !w.starts_with('-') && !w.eq_ignore_ascii_case("or"))
+        .map(|w| w.trim_matches('"'))
+        .filter(|w| !w.is_empty() && *w != query);
+    std::iter::once(query)
+        .chain(words)
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Position of a result within a list of search results, used to continue
+/// the list from there.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct SearchCursor {
+    /// Relevance of the result
+    pub score: f32,
+    /// Unique identifier of the word or paragraph
+    pub id: Uuid,
+}
+
+/// A word or paragraph that matched a full-text search.
+#[derive(async_graphql::SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct SearchHit {
+    /// Matching word, unless this result is a paragraph
+    pub word: Option<AnnotatedForm>,
+    /// Paragraph whose translation matched, unless this result is a word
+    pub paragraph: Option<DocumentParagraph>,
+    /// How well this result matches the search, where higher is better.
+    /// Matches in Cherokee text count more than matches in translations.
+    pub score: f64,
+    /// Fields of this result that matched the search
+    pub highlights: Vec<SearchHighlight>,
+    /// Document that this result is in
+    #[graphql(skip)]
+    pub document_id: DocumentId,
+    /// Position of this result in the list of results
+    #[graphql(skip)]
+    pub cursor: SearchCursor,
+}
+
+#[async_graphql::ComplexObject]
+impl SearchHit {
+    /// Document that this result is in
+    async fn document(&self, context: &Context<'_>) -> FieldResult<Option<AnnotatedDoc>> {
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .load_one(self.document_id)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marked_texts(highlight: &SearchHighlight) -> Vec<&str> {
+        highlight
+            .fragments
+            .iter()
+            .filter(|f| f.matched)
+            .map(|f| &*f.text)
+            .collect()
+    }
+
+    #[test]
+    fn highlights() {
+        let gloss = SearchHighlight::from_marked_copy(
+            SearchField::EnglishGloss,
+            "\u{2}catch\u{3} animate",
+            "catch.animate",
+        )
+        .unwrap();
+        assert_eq!(marked_texts(&gloss), ["catch"]);
+        assert_eq!(gloss.fragments[1].text, ".animate");
+        assert!(SearchHighlight::from_marked(SearchField::Commentary, "no match").is_none());
+
+        let terms = search_terms("ᏥᏃᎦ -ᎦᏙ ajinoga");
+        assert_eq!(terms, ["ᏥᏃᎦ -ᎦᏙ ajinoga", "ᏥᏃᎦ", "ajinoga"]);
+        let source = SearchHighlight::from_terms(SearchField::Source, "ᎠᏥᏃᎦ ᎦᏙ", &terms).unwrap();
+        assert_eq!(marked_texts(&source), ["ᏥᏃᎦ"]);
+        let phonetics =
+            SearchHighlight::from_terms(SearchField::SimplePhonetics, "Ajinoga", &terms).unwrap();
+        assert_eq!(marked_texts(&phonetics), ["Ajinoga"]);
+    }
+}
@@ -3,11 +3,12 @@
 use crate::{WordSegment, WordSegmentRole};
 use nom::{
     bytes::complete::*,
+    character::complete::one_of,
     combinator::{map, opt},
-    multi::many1,
+    multi::many0,
     sequence::pair,
-    IResult,
 };
+use std::fmt;
 
 struct GlossSegment<'a> {
     tag: &'a [u8],
@@ -16,51 +17,469 @@ struct GlossSegment<'a> {
 
 const SEPARATORS: &str = "-=~\\";
 
+/// Separators that we can store as a [`WordSegmentRole`].
+const SUPPORTED_SEPARATORS: &[char] = &['-', '='];
+
+/// One of the two layers of an interlinear segmentation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GlossLayer {
+    /// Source language morphemes, like "ga-ka-nog-a"
+    Morphemes,
+    /// Target language glosses, like "3SG.A-catch-PRS"
+    Glosses,
+}
+
+impl fmt::Display for GlossLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Morphemes => write!(f, "morphemic segmentation"),
+            Self::Glosses => write!(f, "morpheme gloss"),
+        }
+    }
+}
+
+/// Reason that a morphemic segmentation couldn't be read. Offsets count
+/// characters from the start of the layer, starting at zero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GlossParseError {
+    /// The layer is empty, or a separator isn't followed by a morpheme.
+    MissingMorpheme {
+        /// Layer with the missing morpheme
+        layer: GlossLayer,
+        /// Where the morpheme should start
+        offset: usize,
+    },
+    /// A separator was found where a different one, or none at all, was
+    /// expected.
+    UnexpectedSeparator {
+        /// Layer with the unexpected separator
+        layer: GlossLayer,
+        /// Position of the unexpected separator
+        offset: usize,
+        /// The separator that was found
+        found: char,
+        /// Separators that would be allowed here, if any
+        expected: &'static [char],
+    },
+    /// The two layers split the word into different numbers of segments.
+    CountMismatch {
+        /// Number of segments in the morphemic segmentation
+        morphemes: usize,
+        /// Number of segments in the morpheme gloss
+        glosses: usize,
+    },
+}
+
+impl fmt::Display for GlossParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMorpheme { layer, offset } => write!(
+                f,
+                "Expected a morpheme at character {} of the {}",
+                offset + 1,
+                layer
+            ),
+            Self::UnexpectedSeparator {
+                layer,
+                offset,
+                found,
+                expected,
+            } => {
+                write!(
+                    f,
+                    "Unexpected separator '{}' at character {} of the {}, ",
+                    found,
+                    offset + 1,
+                    layer
+                )?;
+                if expected.is_empty() {
+                    write!(f, "expected no separator within a single segment")
+                } else {
+                    let expected: Vec<_> = expected.iter().map(|c| format!("'{}'", c)).collect();
+                    write!(f, "expected one of {}", expected.join(", "))
+                }
+            }
+            Self::CountMismatch { morphemes, glosses } => {
+                write!(f, "Found {} morphemes but {} glosses", morphemes, glosses)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GlossParseError {}
+
+impl GlossParseError {
+    /// Move the position of this error further into its layer.
+    fn shifted(self, by: usize) -> Self {
+        match self {
+            Self::MissingMorpheme { layer, offset } => Self::MissingMorpheme {
+                layer,
+                offset: offset + by,
+            },
+            Self::UnexpectedSeparator {
+                layer,
+                offset,
+                found,
+                expected,
+            } => Self::UnexpectedSeparator {
+                layer,
+                offset: offset + by,
+                found,
+                expected,
+            },
+            other => other,
+        }
+    }
+}
+
+/// Kind of problem found in a morpheme gloss that doesn't stop it from being
+/// saved as is.
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GlossWarningKind {
+    /// A functional gloss that isn't in the tag inventory, which would be
+    /// saved as a gloss local to the document.
+    UnknownTag,
+    /// A functional gloss with empty parts or whitespace in it, like "3SG..A"
+    MalformedTag,
+    /// The morphemic segmentation and gloss have different numbers of segments.
+    CountMismatch,
+    /// One of the layers couldn't be split into segments at all.
+    MalformedSegmentation,
+}
+
+/// A problem with a morphemic segmentation or its glosses.
+#[derive(async_graphql::SimpleObject, Clone, Debug, PartialEq, Eq)]
+pub struct GlossWarning {
+    /// What kind of problem this is
+    pub kind: GlossWarningKind,
+    /// Index of the segment with the problem, if it's limited to one
+    pub segment_index: Option<i32>,
+    /// The gloss with the problem, if it's limited to one
+    pub gloss: Option<String>,
+    /// Known tags that are spelled like the given gloss, closest first
+    pub suggestions: Vec<String>,
+    /// Description of the problem that can be shown to an editor
+    pub message: String,
+}
+
+impl fmt::Display for GlossWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl From<GlossParseError> for GlossWarning {
+    fn from(error: GlossParseError) -> Self {
+        Self {
+            kind: match error {
+                GlossParseError::CountMismatch { .. } => GlossWarningKind::CountMismatch,
+                _ => GlossWarningKind::MalformedSegmentation,
+            },
+            segment_index: None,
+            gloss: None,
+            suggestions: Vec::new(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Whether the given gloss looks like a functional tag, like "3SG.A" or
+/// "PRS", rather than a lexical gloss like "catch" or "be.sick". Functional
+/// tags are written in capitals or start with a person number.
+pub fn is_functional_gloss(gloss: &str) -> bool {
+    gloss.starts_with(|c: char| c.is_ascii_digit())
+        || (gloss.chars().any(char::is_uppercase) && !gloss.chars().any(char::is_lowercase))
+}
+
+/// Check each functional gloss against the known tags in a single system,
+/// suggesting close matches for those we don't recognize. Lexical glosses
+/// are always allowed.
+pub fn check_gloss_tags<'a>(
+    glosses: impl IntoIterator<Item = &'a str>,
+    known_tags: &[String],
+) -> Vec<GlossWarning> {
+    let mut warnings = Vec::new();
+    for (index, gloss) in glosses.into_iter().enumerate() {
+        let gloss = gloss.trim();
+        if !is_functional_gloss(gloss) || known_tags.iter().any(|tag| tag == gloss) {
+            continue;
+        }
+        if gloss.split('.').any(str::is_empty) || gloss.contains(char::is_whitespace) {
+            warnings.push(GlossWarning {
+                kind: GlossWarningKind::MalformedTag,
+                segment_index: Some(index as i32),
+                gloss: Some(gloss.to_owned()),
+                suggestions: Vec::new(),
+                message: format!(
+                    "Malformed tag \"{}\" in segment {}, tags can't have empty parts or spaces",
+                    gloss,
+                    index + 1
+                ),
+            });
+            continue;
+        }
+        let suggestions = closest_tags(gloss, known_tags);
+        let message = if suggestions.is_empty() {
+            format!("Unknown tag \"{}\" in segment {}", gloss, index + 1)
+        } else {
+            format!(
+                "Unknown tag \"{}\" in segment {}, did you mean {}?",
+                gloss,
+                index + 1,
+                suggestions.join(" or ")
+            )
+        };
+        warnings.push(GlossWarning {
+            kind: GlossWarningKind::UnknownTag,
+            segment_index: Some(index as i32),
+            gloss: Some(gloss.to_owned()),
+            suggestions,
+            message,
+        });
+    }
+    warnings
+}
+
+/// Check a whole segmentation, given as its two layers, for malformed
+/// segments, count mismatches, and unknown tags. Tags are still checked when
+/// the layers don't line up.
+pub fn check_gloss_layers(
+    morpheme_layer: &str,
+    gloss_layer: &str,
+    known_tags: &[String],
+) -> Vec<GlossWarning> {
+    let mut warnings = Vec::new();
+    if let Err(error) = parse_gloss_layers(morpheme_layer, gloss_layer) {
+        warnings.push(error.into());
+    }
+    let glosses = gloss_layer
+        .split(|c| SEPARATORS.contains(c))
+        .filter(|gloss| !gloss.trim().is_empty());
+    warnings.append(&mut check_gloss_tags(glosses, known_tags));
+    warnings
+}
+
+/// Up to three known tags closest to the given gloss, ignoring case.
+fn closest_tags(gloss: &str, known_tags: &[String]) -> Vec<String> {
+    const MAX_SUGGESTIONS: usize = 3;
+    let gloss: Vec<char> = gloss.to_uppercase().chars().collect();
+    // Allow about one typo for every three characters.
+    let max_distance = (gloss.len() / 3).max(1);
+    let mut matches: Vec<_> = known_tags
+        .iter()
+        .filter_map(|tag| {
+            let upper: Vec<char> = tag.to_uppercase().chars().collect();
+            let distance = edit_distance(&gloss, &upper);
+            (distance <= max_distance).then_some((distance, tag))
+        })
+        .collect();
+    matches.sort();
+    matches.dedup_by(|a, b| a.1 == b.1);
+    matches
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, tag)| tag.clone())
+        .collect()
+}
+
+/// Number of single character insertions, deletions, or substitutions it
+/// takes to turn one string into the other.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, x) in a.iter().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, y) in b.iter().enumerate() {
+            curr[j + 1] = (prev[j] + usize::from(x != y))
+                .min(prev[j + 1] + 1)
+                .min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
 /// Parse a canonical morphemic segmentation from the two layers: morphemes and glosses.
-pub fn parse_gloss_layers<'a>(
-    layer_one: &'a str,
-    layer_two: &'a str,
-) -> IResult<&'a [u8], Vec<WordSegment>> {
-    let (_, one) = gloss_line(layer_one.as_bytes())?;
-    let (_, two) = gloss_line(layer_two.as_bytes())?;
-    Ok((
-        &[],
-        one.into_iter()
-            .zip(two)
-            .map(|(morpheme, gloss)| {
-                WordSegment::new(
-                    String::from_utf8_lossy(morpheme.tag).trim().to_owned(),
-                    String::from_utf8_lossy(gloss.tag).trim().to_owned(),
-                    // The gloss line is most likely to have the correct separator.
-                    gloss.role,
-                )
-            })
-            .collect(),
-    ))
+pub fn parse_gloss_layers(
+    layer_one: &str,
+    layer_two: &str,
+) -> Result<Vec<WordSegment>, GlossParseError> {
+    let one = gloss_line(GlossLayer::Morphemes, layer_one)?;
+    let two = gloss_line(GlossLayer::Glosses, layer_two)?;
+    if one.len() != two.len() {
+        return Err(GlossParseError::CountMismatch {
+            morphemes: one.len(),
+            glosses: two.len(),
+        });
+    }
+    Ok(one
+        .into_iter()
+        .zip(two)
+        .map(|(morpheme, gloss)| {
+            WordSegment::new(
+                String::from_utf8_lossy(morpheme.tag).trim().to_owned(),
+                String::from_utf8_lossy(gloss.tag).trim().to_owned(),
+                // The gloss line is most likely to have the correct separator.
+                gloss.role,
+            )
+        })
+        .collect())
+}
+
+/// Check segments that were already split apart, like those sent by an
+/// editor, by making sure each morpheme and gloss is a single segment.
+/// Offsets count from the start of each layer as if the segments were joined
+/// back together with one separator between each.
+pub fn check_segment_layers<'a>(
+    segments: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Result<(), GlossParseError> {
+    let mut morpheme_offset = 0;
+    let mut gloss_offset = 0;
+    for (index, (morpheme, gloss)) in segments.into_iter().enumerate() {
+        if index > 0 {
+            morpheme_offset += 1;
+            gloss_offset += 1;
+        }
+        check_single_segment(GlossLayer::Morphemes, morpheme)
+            .map_err(|e| e.shifted(morpheme_offset))?;
+        check_single_segment(GlossLayer::Glosses, gloss).map_err(|e| e.shifted(gloss_offset))?;
+        morpheme_offset += morpheme.chars().count();
+        gloss_offset += gloss.chars().count();
+    }
+    Ok(())
+}
+
+fn check_single_segment(layer: GlossLayer, segment: &str) -> Result<(), GlossParseError> {
+    if segment.trim().is_empty() {
+        return Err(GlossParseError::MissingMorpheme { layer, offset: 0 });
+    }
+    match segment
+        .chars()
+        .enumerate()
+        .find(|(_, c)| SEPARATORS.contains(*c))
+    {
+        Some((offset, found)) => Err(GlossParseError::UnexpectedSeparator {
+            layer,
+            offset,
+            found,
+            expected: &[],
+        }),
+        None => Ok(()),
+    }
 }
 
 /// Parses a string following the Leipzig glossing guidelines, where morphemes
 /// or morpheme glosses are separated by several different delimiters, each with
 /// different semantics.
-fn gloss_line(input: &[u8]) -> IResult<&[u8], Vec<GlossSegment>> {
-    many1(tailed_morpheme)(input)
+fn gloss_line(layer: GlossLayer, line: &str) -> Result<Vec<GlossSegment<'_>>, GlossParseError> {
+    let input = line.as_bytes();
+    // Each segment consumes at least one byte, so this can't fail.
+    let (rest, segments) =
+        many0(tailed_morpheme)(input).map_err(|_: nom::Err<nom::error::Error<&[u8]>>| {
+            GlossParseError::MissingMorpheme { layer, offset: 0 }
+        })?;
+    if rest.is_empty() && !segments.is_empty() {
+        return Ok(segments);
+    }
+
+    // Separators are all ASCII, so this is a character boundary.
+    let byte_offset = input.len() - rest.len();
+    let offset = line[..byte_offset].chars().count();
+    Err(match line[byte_offset..].chars().next() {
+        // A supported separator with nothing usable after it.
+        Some(c) if SUPPORTED_SEPARATORS.contains(&c) => GlossParseError::MissingMorpheme {
+            layer,
+            offset: offset + 1,
+        },
+        Some(found) => GlossParseError::UnexpectedSeparator {
+            layer,
+            offset,
+            found,
+            expected: SUPPORTED_SEPARATORS,
+        },
+        None => GlossParseError::MissingMorpheme { layer, offset },
+    })
 }
 
-fn tailed_morpheme(input: &[u8]) -> IResult<&[u8], GlossSegment> {
+fn tailed_morpheme(input: &[u8]) -> nom::IResult<&[u8], GlossSegment> {
     map(pair(opt(morpheme_sep), morpheme), |(sep, m)| GlossSegment {
         tag: m,
         role: sep,
     })(input)
 }
 
-fn morpheme(input: &[u8]) -> IResult<&[u8], &[u8]> {
+fn morpheme(input: &[u8]) -> nom::IResult<&[u8], &[u8]> {
     take_while1(|c| !SEPARATORS.contains(c as char))(input)
 }
 
-fn morpheme_sep(input: &[u8]) -> IResult<&[u8], WordSegmentRole> {
-    map(is_a(SEPARATORS), |c: &[u8]| match c[0] as char {
-        '-' => WordSegmentRole::Morpheme,
+fn morpheme_sep(input: &[u8]) -> nom::IResult<&[u8], WordSegmentRole> {
+    map(one_of("-="), |c| match c {
         '=' => WordSegmentRole::Clitic,
-        _ => todo!("Unrecognized morpheme separator"),
+        _ => WordSegmentRole::Morpheme,
     })(input)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gloss_tag_warnings() {
+        let known: Vec<String> = ["3PL.B", "3SG.A", "PRS", "PL"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        assert!(check_gloss_tags(["3SG.A", "catch", "PRS", "be.sick"], &known).is_empty());
+
+        let warnings = check_gloss_tags(["3PL.b", "see", "PRSS", "3SG..A"], &known);
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(warnings[0].kind, GlossWarningKind::UnknownTag);
+        assert_eq!(warnings[0].suggestions, ["3PL.B"]);
+        assert_eq!(warnings[1].segment_index, Some(2));
+        assert_eq!(warnings[1].suggestions, ["PRS"]);
+        assert_eq!(warnings[2].kind, GlossWarningKind::MalformedTag);
+
+        let warnings = check_gloss_layers("ga-kanog-a", "3SG.A-catch", &known);
+        assert_eq!(warnings[0].kind, GlossWarningKind::CountMismatch);
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn gloss_errors() {
+        let segments = parse_gloss_layers("ga-kanog-a=hv", "3SG-catch-PRS=Q").unwrap();
+        assert_eq!(segments.len(), 4);
+        assert_eq!(segments[3].role, WordSegmentRole::Clitic);
+
+        assert_eq!(
+            parse_gloss_layers("ga-kanog-a", "3SG-catch").unwrap_err(),
+            GlossParseError::CountMismatch {
+                morphemes: 3,
+                glosses: 2
+            }
+        );
+        assert_eq!(
+            parse_gloss_layers("ga--kanog", "3SG-catch").unwrap_err(),
+            GlossParseError::MissingMorpheme {
+                layer: GlossLayer::Morphemes,
+                offset: 3
+            }
+        );
+        assert_eq!(
+            parse_gloss_layers("ga-kanog", "3SG~catch").unwrap_err(),
+            GlossParseError::UnexpectedSeparator {
+                layer: GlossLayer::Glosses,
+                offset: 3,
+                found: '~',
+                expected: SUPPORTED_SEPARATORS,
+            }
+        );
+        assert_eq!(
+            check_segment_layers([("ga", "3SG"), ("kanog-a", "catch")]).unwrap_err(),
+            GlossParseError::UnexpectedSeparator {
+                layer: GlossLayer::Morphemes,
+                offset: 8,
+                found: '-',
+                expected: &[],
+            }
+        );
+    }
+}
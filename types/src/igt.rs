@@ -0,0 +1,455 @@
+//! Export paragraphs and documents as interlinear glossed text (IGT) following
+//! the Leipzig Glossing Rules, ready to paste into a paper or web page.
+
+use crate::{
+    AnnotatedDoc, AnnotatedForm, AnnotatedSeg, CherokeeOrthography, Database, DocumentParagraph,
+    OrthographyId, PagesInDocument, ParagraphsInPage, WordSegment, WordsInParagraph,
+};
+use async_graphql::dataloader::DataLoader;
+use futures::future::try_join_all;
+use itertools::Itertools;
+
+/// Markup to write interlinear glossed text in.
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IgtFormat {
+    /// Plain text with each word's lines padded to the same width
+    PlainText,
+    /// LaTeX for the gb4e package
+    Gb4e,
+    /// LaTeX for the expex package
+    Expex,
+    /// HTML with one inline block per word
+    Html,
+}
+
+/// All the lines of one word in an interlinear example.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InterlinearWord {
+    /// Source text as written in the document
+    pub source: String,
+    /// The whole word in the selected orthography
+    pub romanized: Option<String>,
+    /// Morphemic segmentation in the selected orthography
+    pub morphemes: Option<String>,
+    /// Morpheme glosses with tags from the selected system
+    pub glosses: Option<String>,
+}
+
+/// One interlinear example, usually a single paragraph of a document.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InterlinearExample {
+    /// Words of the example in order
+    pub words: Vec<InterlinearWord>,
+    /// Free translation of the whole example
+    pub translation: Option<String>,
+}
+
+/// The lines of an example that are lined up word by word with the source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Tier {
+    Source,
+    Romanized,
+    Morphemes,
+    Glosses,
+}
+
+impl Tier {
+    const ALL: [Self; 4] = [
+        Self::Source,
+        Self::Romanized,
+        Self::Morphemes,
+        Self::Glosses,
+    ];
+
+    fn class_name(self) -> &'static str {
+        match self {
+            Self::Source => "igt-source",
+            Self::Romanized => "igt-romanized",
+            Self::Morphemes => "igt-morphemes",
+            Self::Glosses => "igt-glosses",
+        }
+    }
+}
+
+impl InterlinearWord {
+    /// Gather the lines of a single word in the given registered system.
+    /// Words from the database get their tags from that system if it's a
+    /// Cherokee one, while words that were never saved keep the glosses they
+    /// have.
+    pub async fn from_form(
+        db: &DataLoader<Database>,
+        form: &AnnotatedForm,
+        system: &OrthographyId,
+    ) -> anyhow::Result<Self> {
+        let (_, orthography) = system.resolve()?;
+        let cherokee = system.cherokee();
+        let mut segments = if form.id.is_some() {
+            form.concrete_segments(db, cherokee.unwrap_or(CherokeeOrthography::Taoc))
+                .await?
+        } else {
+            form.segments
+                .iter()
+                .flatten()
+                .map(|segment| WordSegment {
+                    system: cherokee,
+                    ..segment.clone()
+                })
+                .collect()
+        };
+        // Other languages' systems write each morpheme up front.
+        if cherokee.is_none() {
+            for segment in &mut segments {
+                segment.morpheme = orthography.render(&segment.morpheme);
+                segment.system = None;
+            }
+        }
+        Ok(Self {
+            source: form.source.clone(),
+            romanized: match cherokee {
+                // The syllabary line would only repeat the source.
+                Some(CherokeeOrthography::Syllabary) => None,
+                Some(system) => form.romanized_in(system).map(String::from),
+                None => form
+                    .phonemic
+                    .as_ref()
+                    .map(|phonemic| orthography.render(phonemic)),
+            },
+            morphemes: (!segments.is_empty()).then(|| WordSegment::morpheme_layer(&segments)),
+            glosses: (!segments.is_empty()).then(|| WordSegment::gloss_layer(&segments)),
+        })
+    }
+
+    fn get(&self, tier: Tier) -> Option<&str> {
+        match tier {
+            Tier::Source => Some(&self.source),
+            Tier::Romanized => self.romanized.as_deref(),
+            Tier::Morphemes => self.morphemes.as_deref(),
+            Tier::Glosses => self.glosses.as_deref(),
+        }
+    }
+}
+
+impl InterlinearExample {
+    /// Gather the lines of the given words in the given system.
+    pub async fn from_segments(
+        db: &DataLoader<Database>,
+        source: &[AnnotatedSeg],
+        translation: Option<String>,
+        system: &OrthographyId,
+    ) -> anyhow::Result<Self> {
+        // Load every word at once so that the data loader can batch them.
+        let words = try_join_all(
+            source
+                .iter()
+                .filter_map(AnnotatedSeg::form)
+                .map(|form| InterlinearWord::from_form(db, form, system)),
+        )
+        .await?;
+        Ok(Self {
+            words,
+            translation: translation.filter(|t| !t.trim().is_empty()),
+        })
+    }
+
+    /// Gather the lines of a whole paragraph in the given system.
+    pub async fn from_paragraph(
+        db: &DataLoader<Database>,
+        paragraph: &DocumentParagraph,
+        system: &OrthographyId,
+    ) -> anyhow::Result<Self> {
+        let source = db
+            .load_one(WordsInParagraph(paragraph.id))
+            .await?
+            .unwrap_or_default();
+        Self::from_segments(db, &source, Some(paragraph.translation.clone()), system).await
+    }
+
+    /// One example for each paragraph in the given document.
+    pub async fn from_document(
+        db: &DataLoader<Database>,
+        doc: &AnnotatedDoc,
+        system: &OrthographyId,
+    ) -> anyhow::Result<Vec<Self>> {
+        // Documents that haven't been saved yet carry their own contents.
+        if let Some(pages) = &doc.segments {
+            return try_join_all(
+                pages
+                    .iter()
+                    .flat_map(|page| &page.paragraphs)
+                    .map(|section| {
+                        Self::from_segments(
+                            db,
+                            &section.source,
+                            section.translation.clone(),
+                            system,
+                        )
+                    }),
+            )
+            .await;
+        }
+
+        let pages = db
+            .load_one(PagesInDocument(doc.meta.id.0))
+            .await?
+            .unwrap_or_default();
+        let mut paragraphs = db
+            .load_many(pages.iter().map(|page| ParagraphsInPage(page.id)))
+            .await?;
+        let paragraphs: Vec<DocumentParagraph> = pages
+            .iter()
+            .flat_map(|page| {
+                paragraphs
+                    .remove(&ParagraphsInPage(page.id))
+                    .unwrap_or_default()
+            })
+            .collect();
+        try_join_all(
+            paragraphs
+                .iter()
+                .map(|paragraph| Self::from_paragraph(db, paragraph, system)),
+        )
+        .await
+    }
+
+    /// Write several examples in the given format, one after another.
+    pub fn render_all(examples: &[Self], format: IgtFormat) -> String {
+        let body = examples
+            .iter()
+            .filter(|example| !example.words.is_empty())
+            .map(|example| example.render(format))
+            .join("\n");
+        match format {
+            // gb4e numbers every example within one environment.
+            IgtFormat::Gb4e => format!("\\begin{{exe}}\n{}\\end{{exe}}\n", body),
+            _ => body,
+        }
+    }
+
+    /// Write this example in the given format.
+    pub fn render(&self, format: IgtFormat) -> String {
+        match format {
+            IgtFormat::PlainText => self.plain_text(),
+            IgtFormat::Gb4e => self.gb4e(),
+            IgtFormat::Expex => self.expex(),
+            IgtFormat::Html => self.html(),
+        }
+    }
+
+    /// Lines that at least one word has, in order.
+    fn tiers(&self) -> Vec<Tier> {
+        Tier::ALL
+            .into_iter()
+            .filter(|tier| self.words.iter().any(|word| word.get(*tier).is_some()))
+            .collect()
+    }
+
+    fn plain_text(&self) -> String {
+        let tiers = self.tiers();
+        let widths: Vec<usize> = self
+            .words
+            .iter()
+            .map(|word| {
+                tiers
+                    .iter()
+                    .map(|tier| display_width(word.get(*tier).unwrap_or_default()))
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+        let mut output = String::new();
+        for tier in tiers {
+            let line = self
+                .words
+                .iter()
+                .zip(&widths)
+                .map(|(word, width)| {
+                    let cell = word.get(tier).unwrap_or_default();
+                    let padding = width - display_width(cell);
+                    format!("{}{}", cell, " ".repeat(padding))
+                })
+                .join(" ");
+            output.push_str(line.trim_end());
+            output.push('\n');
+        }
+        if let Some(translation) = &self.translation {
+            output.push_str(&format!("‘{}’\n", translation.trim()));
+        }
+        output
+    }
+
+    fn gb4e(&self) -> String {
+        let aligned: Vec<_> = self
+            .tiers()
+            .into_iter()
+            .filter(|tier| *tier != Tier::Source)
+            .collect();
+        let mut output = String::from("\\ex\n");
+        output.push_str(&self.latex_line(Tier::Source));
+        output.push_str(" \\\\\n");
+        if !aligned.is_empty() {
+            let command = if aligned.len() > 2 { "\\glll" } else { "\\gll" };
+            output.push_str(command);
+            output.push(' ');
+            // A single aligned line still needs a second one for \gll.
+            let lines: Vec<_> = if aligned.len() == 1 {
+                vec![self.latex_line(aligned[0]), String::new()]
+            } else {
+                aligned.iter().map(|tier| self.latex_line(*tier)).collect()
+            };
+            output.push_str(&lines.join(" \\\\\n"));
+            output.push_str(" \\\\\n");
+        }
+        if let Some(translation) = &self.translation {
+            output.push_str(&format!("\\glt `{}'\n", latex_escape(translation.trim())));
+        }
+        output
+    }
+
+    fn expex(&self) -> String {
+        let mut output = String::from("\\ex\n\\begingl\n");
+        output.push_str(&format!(
+            "\\glpreamble {} //\n",
+            self.latex_line(Tier::Source)
+        ));
+        let aligned = self
+            .tiers()
+            .into_iter()
+            .filter(|tier| *tier != Tier::Source);
+        for (command, tier) in ["\\gla", "\\glb", "\\glc"].into_iter().zip(aligned) {
+            output.push_str(&format!("{} {} //\n", command, self.latex_line(tier)));
+        }
+        if let Some(translation) = &self.translation {
+            output.push_str(&format!(
+                "\\glft `{}' //\n",
+                latex_escape(translation.trim())
+            ));
+        }
+        output.push_str("\\endgl\n\\xe\n");
+        output
+    }
+
+    /// One line of LaTeX, with each word grouped so that it lines up as one.
+    fn latex_line(&self, tier: Tier) -> String {
+        self.words
+            .iter()
+            .map(|word| {
+                let cell = latex_escape(word.get(tier).unwrap_or_default());
+                if cell.is_empty() || cell.contains(char::is_whitespace) {
+                    format!("{{{}}}", cell)
+                } else {
+                    cell
+                }
+            })
+            .join(" ")
+    }
+
+    fn html(&self) -> String {
+        let tiers = self.tiers();
+        let mut output = String::from("<div class=\"igt\">\n");
+        for word in &self.words {
+            output.push_str(
+                "  <div class=\"igt-word\" style=\"display: inline-block; margin-right: 1em\">\n",
+            );
+            for tier in &tiers {
+                output.push_str(&format!(
+                    "    <div class=\"{}\">{}</div>\n",
+                    tier.class_name(),
+                    html_escape(word.get(*tier).unwrap_or_default())
+                ));
+            }
+            output.push_str("  </div>\n");
+        }
+        if let Some(translation) = &self.translation {
+            output.push_str(&format!(
+                "  <div class=\"igt-translation\">‘{}’</div>\n",
+                html_escape(translation.trim())
+            ));
+        }
+        output.push_str("</div>\n");
+        output
+    }
+}
+
+/// Number of columns a string takes up in monospace text. Combining accents
+/// sit on top of the previous character, so they don't count.
+fn display_width(s: &str) -> usize {
+    s.chars()
+        .filter(|c| !('\u{300}'..='\u{36f}').contains(c))
+        .count()
+}
+
+fn latex_escape(s: &str) -> String {
+    let mut output = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => output.push_str("\\textbackslash{}"),
+            '~' => output.push_str("\\textasciitilde{}"),
+            '^' => output.push_str("\\textasciicircum{}"),
+            '#' | '$' | '%' | '&' | '_' | '{' | '}' => {
+                output.push('\\');
+                output.push(c);
+            }
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> InterlinearExample {
+        InterlinearExample {
+            words: vec![
+                InterlinearWord {
+                    source: "ᎦᏅᎦ".to_owned(),
+                    romanized: Some("gahnoga".to_owned()),
+                    morphemes: Some("ga-hnog-a".to_owned()),
+                    glosses: Some("3SG.A-catch-PRS".to_owned()),
+                },
+                InterlinearWord {
+                    source: "ᎠᏣᏗ".to_owned(),
+                    romanized: Some("atsadi".to_owned()),
+                    morphemes: None,
+                    glosses: None,
+                },
+            ],
+            translation: Some("He catches fish & more".to_owned()),
+        }
+    }
+
+    #[test]
+    fn plain_text_alignment() {
+        assert_eq!(
+            example().render(IgtFormat::PlainText),
+            "ᎦᏅᎦ             ᎠᏣᏗ\n\
+             gahnoga         atsadi\n\
+             ga-hnog-a\n\
+             3SG.A-catch-PRS\n\
+             ‘He catches fish & more’\n"
+        );
+    }
+
+    #[test]
+    fn latex_and_html() {
+        let gb4e = InterlinearExample::render_all(&[example()], IgtFormat::Gb4e);
+        assert!(gb4e.starts_with("\\begin{exe}\n\\ex\nᎦᏅᎦ ᎠᏣᏗ \\\\\n\\glll gahnoga atsadi"));
+        assert!(gb4e.contains("3SG.A-catch-PRS {} \\\\\n"));
+        assert!(gb4e.contains("\\glt `He catches fish \\& more'"));
+
+        let expex = example().render(IgtFormat::Expex);
+        assert!(expex.contains("\\glc 3SG.A-catch-PRS {} //\n"));
+
+        let html = example().render(IgtFormat::Html);
+        assert!(html.contains("<div class=\"igt-glosses\">3SG.A-catch-PRS</div>"));
+        assert!(html.contains("fish &amp; more"));
+    }
+}
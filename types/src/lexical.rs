@@ -1,4 +1,8 @@
-use crate::{AnnotatedForm, Database, Date, DocumentId, Geometry, WordSegment};
+use crate::{
+    AffricateSpelling, AnnotatedForm, Database, Date, DocumentId, Geometry, GlottalStopSpelling,
+    LabiovelarSpelling, MorphemeRelationKind, OrthographyProfile, StopSpelling, ToneMarking,
+    VowelLength, WordSegment,
+};
 use serde::{Deserialize, Serialize};
 
 /// The reference position within a document of one specific form
@@ -128,6 +132,9 @@ pub struct LexicalConnection {
     pub left: MorphemeId,
     /// Second morpheme to associate
     pub right: MorphemeId,
+    /// How the first morpheme relates to the second
+    #[serde(default)]
+    pub kind: MorphemeRelationKind,
 }
 impl LexicalConnection {
     /// Make a new association between these two identifiers.
@@ -136,6 +143,15 @@ impl LexicalConnection {
             id: format!("{}-{}", from, to),
             left: from,
             right: to,
+            kind: MorphemeRelationKind::Related,
+        }
+    }
+
+    /// Make a new link of the given kind from one identifier to another.
+    pub fn with_kind(from: MorphemeId, to: MorphemeId, kind: MorphemeRelationKind) -> Self {
+        Self {
+            kind,
+            ..Self::new(from, to)
         }
     }
 
@@ -193,6 +209,32 @@ impl MorphemeId {
             index: index.and_then(|i| i.parse().ok()),
         })
     }
+
+    /// Parse an ID that must point into a particular document, like
+    /// "DF1975:catch" or "DF1975.23:catch", explaining what's wrong with it
+    /// otherwise.
+    pub fn parse_reference(input: &str) -> anyhow::Result<Self> {
+        let input = input.trim();
+        let id = Self::parse(input)
+            .ok_or_else(|| anyhow::format_err!("\"{}\" spans several lines", input))?;
+        let document_part = match id.document_name {
+            Some(_) => input.split(':').next().unwrap_or_default(),
+            None => "",
+        };
+        if document_part.is_empty() || document_part.contains(char::is_whitespace) {
+            anyhow::bail!(
+                "\"{}\" should start with a document name, like \"DF1975:catch\"",
+                input
+            );
+        }
+        if document_part.contains('.') && id.index.is_none() {
+            anyhow::bail!("\"{}\" has an index that isn't a number", input);
+        }
+        if id.gloss.trim().is_empty() {
+            anyhow::bail!("\"{}\" is missing a gloss after the colon", input);
+        }
+        Ok(id)
+    }
 }
 
 impl std::fmt::Display for MorphemeId {
@@ -273,7 +315,7 @@ pub fn seg_verb_surface_form(
         None
     };
 
-    let segments = WordSegment::parse_many(&morpheme_layer, &gloss_layer)?;
+    let segments = WordSegment::parse_many(&morpheme_layer, &gloss_layer).ok()?;
 
     Some(AnnotatedForm {
         id: None,
@@ -382,7 +424,7 @@ pub fn root_verb_surface_form(
         cols.next();
     }
 
-    let segments = WordSegment::parse_many(&morpheme_layer, &gloss_layer)?;
+    let segments = WordSegment::parse_many(&morpheme_layer, &gloss_layer).ok()?;
 
     Some(AnnotatedForm {
         id: None,
@@ -462,7 +504,7 @@ pub fn root_noun_surface_form(
         }
     }
     let commentary = if has_comment { cols.next() } else { None };
-    let segments = WordSegment::parse_many(&morpheme_layer, &gloss_layer)?;
+    let segments = WordSegment::parse_many(&morpheme_layer, &gloss_layer).ok()?;
 
     Some(AnnotatedForm {
         id: None,
@@ -539,10 +581,39 @@ pub fn convert_udb(input: &str) -> PhonemicString {
     }
 }
 
+/// One syllable of a phonetic string, which the syllabary writes with a single
+/// character.
+#[derive(Clone, Debug, PartialEq, Eq, async_graphql::SimpleObject)]
+pub struct Syllable {
+    /// Phonemic spelling of this syllable in DAILP internal orthography,
+    /// including any consonants or morpheme separators that the syllabary
+    /// doesn't write.
+    pub phonemic: String,
+    /// Index of the first character of this syllable in the whole phonemic
+    /// string.
+    pub start: i32,
+    /// The syllabary character that spells this syllable, or nothing for
+    /// consonants the syllabary can't write.
+    pub syllabary: String,
+}
+
+/// Pairing of a phonemic syllable with a character of the source syllabary.
+#[derive(Clone, Debug, PartialEq, Eq, async_graphql::SimpleObject)]
+pub struct SyllableAlignment {
+    /// Syllable from the phonemic layer, missing if the source has an extra
+    /// character here.
+    pub syllable: Option<Syllable>,
+    /// Character of the source syllabary, missing if the source lacks a
+    /// character for this syllable.
+    pub source: Option<String>,
+    /// Whether the source character is the one the phonemic layer calls for.
+    pub is_match: bool,
+}
+
 /// Storage format for Cherokee phonetics.
 /// Consonants: t/th in storage, converted to d/t on output.
 /// Vowels: struct-defined
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PhonemicString {
     /// A whole word consisting of several consonant and vowel clusters
     Form(Vec<PhonemicString>),
@@ -661,6 +732,13 @@ impl PhonemicString {
         }
     }
 
+    /// Parse a phonetic romanization in the simplified learner orthography.
+    /// Tone and vowel length aren't marked, so every vowel is read as short and
+    /// low, and "qu" is read as the plain consonant.
+    pub fn parse_learner(input: &str) -> Self {
+        Self::parse_crg(&worcester_to_simple_phonetics(&input.to_lowercase()))
+    }
+
     /// Parse a phonetic romanization in CRG form.
     pub fn parse_crg(input: &str) -> Self {
         use {
@@ -694,43 +772,44 @@ impl PhonemicString {
                 "v̀" => ("v", VowelType::ShortLowfall),
                 "v̋" => ("v", VowelType::ShortSuperhigh),
             };
+            // Long vowels as written by `OrthographyProfile::CRG`.
             static ref LONG_VOWELS: HashMap<&'static str, (&'static str, VowelType)> = hashmap! {
                 "aa" => ("a", VowelType::LongLow),
-                "áá" => ("a", VowelType::LongHigh),
+                "áa" => ("a", VowelType::LongHigh),
                 "aá" => ("a", VowelType::Rising),
-                "áa" => ("a", VowelType::Falling),
-                "àà" => ("a", VowelType::Lowfall),
-                "aa̋" => ("a", VowelType::Superhigh),
+                "áà" => ("a", VowelType::Falling),
+                "aà" => ("a", VowelType::Lowfall),
+                "áá" => ("a", VowelType::Superhigh),
                 "ee" => ("e", VowelType::LongLow),
-                "éé" => ("e", VowelType::LongHigh),
+                "ée" => ("e", VowelType::LongHigh),
                 "eé" => ("e", VowelType::Rising),
-                "ée" => ("e", VowelType::Falling),
-                "èè" => ("e", VowelType::Lowfall),
-                "ee̋" => ("e", VowelType::Superhigh),
+                "éè" => ("e", VowelType::Falling),
+                "eè" => ("e", VowelType::Lowfall),
+                "éé" => ("e", VowelType::Superhigh),
                 "ii" => ("i", VowelType::LongLow),
-                "íí" => ("i", VowelType::LongHigh),
+                "íi" => ("i", VowelType::LongHigh),
                 "ií" => ("i", VowelType::Rising),
-                "íi" => ("i", VowelType::Falling),
-                "ìì" => ("i", VowelType::Lowfall),
-                "ii̋" => ("i", VowelType::Superhigh),
+                "íì" => ("i", VowelType::Falling),
+                "iì" => ("i", VowelType::Lowfall),
+                "íí" => ("i", VowelType::Superhigh),
                 "oo" => ("o", VowelType::LongLow),
-                "óó" => ("o", VowelType::LongHigh),
+                "óo" => ("o", VowelType::LongHigh),
                 "oó" => ("o", VowelType::Rising),
-                "óo" => ("o", VowelType::Falling),
-                "òò" => ("o", VowelType::Lowfall),
-                "oő" => ("o", VowelType::Superhigh),
+                "óò" => ("o", VowelType::Falling),
+                "oò" => ("o", VowelType::Lowfall),
+                "óó" => ("o", VowelType::Superhigh),
                 "uu" => ("u", VowelType::LongLow),
-                "úú" => ("u", VowelType::LongHigh),
+                "úu" => ("u", VowelType::LongHigh),
                 "uú" => ("u", VowelType::Rising),
-                "úu" => ("u", VowelType::Falling),
-                "ùù" => ("u", VowelType::Lowfall),
-                "uű" => ("u", VowelType::Superhigh),
+                "úù" => ("u", VowelType::Falling),
+                "uù" => ("u", VowelType::Lowfall),
+                "úú" => ("u", VowelType::Superhigh),
                 "vv" => ("v", VowelType::LongLow),
-                "v́v́" => ("v", VowelType::LongHigh),
+                "v́v" => ("v", VowelType::LongHigh),
                 "vv́" => ("v", VowelType::Rising),
-                "v́v" => ("v", VowelType::Falling),
-                "v̀v̀" => ("v", VowelType::Lowfall),
-                "vv̋" => ("v", VowelType::Superhigh),
+                "v́v̀" => ("v", VowelType::Falling),
+                "vv̀" => ("v", VowelType::Lowfall),
+                "v́v́" => ("v", VowelType::Superhigh),
             };
             static ref PAT: regex::Regex = {
                 // NOTE This contains the list of acceptable consonants and symbols.
@@ -743,7 +822,8 @@ impl PhonemicString {
         }
 
         let mut syllables = Vec::new();
-        let mut input = input.nfc().to_string();
+        // CRG writes the colon of TAOC as "xx".
+        let mut input = input.nfc().to_string().replace("xx", ":");
         input.make_ascii_lowercase();
         for caps in PAT.captures_iter(&input) {
             if let Some(consonant) = caps.get(1) {
@@ -774,6 +854,58 @@ impl PhonemicString {
         }
     }
 
+    /// Parse a string written in the Cherokee syllabary.
+    /// The syllabary doesn't mark tone, vowel length, or glottal stops, so every
+    /// vowel is read as short and low. Where one character stands for both a
+    /// plain and an aspirated consonant, like Ꭸ for /ke/ and /khe/, we read the
+    /// plain consonant. Any other characters are kept as they are.
+    pub fn parse_syllabary(input: &str) -> Self {
+        use unicode_normalization::UnicodeNormalization;
+        fn push_consonant(syllables: &mut Vec<PhonemicString>, consonant: &str) {
+            if consonant.is_empty() {
+                return;
+            }
+            if let Some(PhonemicString::Consonant(prev)) = syllables.last_mut() {
+                prev.push_str(consonant);
+            } else {
+                syllables.push(PhonemicString::Consonant(consonant.to_owned()));
+            }
+        }
+
+        let mut syllables = Vec::new();
+        for c in input.nfc() {
+            // Lowercase syllabary is only a stylistic variant.
+            let upper = c.to_uppercase().next().unwrap_or(c);
+            if let Some(sound) = SYLLABARY_SOUNDS.get(&upper) {
+                // Split a sound like "kha" into the consonant "kh" and vowel "a".
+                let vowel_start = sound.find(is_syllabary_vowel).unwrap_or(sound.len());
+                let (consonant, rest) = sound.split_at(vowel_start);
+                push_consonant(&mut syllables, consonant);
+                if !rest.is_empty() {
+                    let (vowel, coda) = rest.split_at(1);
+                    syllables.push(PhonemicString::Vowel(vowel.to_owned(), VowelType::ShortLow));
+                    push_consonant(&mut syllables, coda);
+                }
+            } else {
+                push_consonant(&mut syllables, &c.to_string());
+            }
+        }
+
+        if syllables.is_empty() {
+            PhonemicString::Consonant(input.to_owned())
+        } else {
+            PhonemicString::Form(syllables)
+        }
+    }
+
+    /// Every consonant cluster and vowel in this string, in order.
+    pub fn segments(&self) -> Vec<&PhonemicString> {
+        match self {
+            PhonemicString::Form(all) => all.iter().flat_map(|x| x.segments()).collect(),
+            other => vec![other],
+        }
+    }
+
     /// Convert the consonants and vowels in this Cherokee phonetic string into
     /// the internal DAILP representation.
     pub fn into_dailp(self) -> String {
@@ -804,40 +936,268 @@ impl PhonemicString {
     /// Convert the consonants and vowels in this Cherokee phonetic string into
     /// a form compatible with Cherokee Reference Grammar (CRG).
     pub fn into_crg(self) -> String {
-        use {itertools::Itertools, unicode_normalization::UnicodeNormalization, VowelType::*};
+        self.render(&OrthographyProfile::CRG)
+    }
+
+    /// Simplify all vowels by stripping out tone and length.
+    /// Convert t/th consonants to the d/t representation with apostrophe for
+    /// the glottal stop.
+    pub fn into_learner(self) -> String {
+        self.render(&OrthographyProfile::LEARNER)
+    }
+
+    /// Spell out this Cherokee phonetic string with the given set of spelling
+    /// choices.
+    pub fn render(self, profile: &OrthographyProfile) -> String {
+        use {itertools::Itertools, unicode_normalization::UnicodeNormalization};
         match self {
             PhonemicString::Form(all) => all
                 .into_iter()
                 // Join all decomposed unicode.
-                .map(|x| x.into_crg())
+                .map(|x| x.render(profile))
                 .join("")
                 .nfc()
                 .to_string(),
-            PhonemicString::Consonant(s) => tth_to_dt(&s, true, Some("xx"), false, false),
-            PhonemicString::Vowel(v, ty) => match ty {
-                // Short vowels in CRG match TAOC.
-                ShortLow => v,
-                ShortHigh | ShortSuperhigh => format!("{}\u{0301}", v),
-                ShortLowfall => format!("{}\u{0300}", v),
-                // The long vowels are slightly different.
-                LongLow => format!("{}{}", v, v),
-                LongHigh => format!("{}\u{0301}{}", v, v),
-                Rising => format!("{}{}\u{0301}", v, v),
-                Falling => format!("{}\u{0301}{}\u{0300}", v, v),
-                Lowfall => format!("{}{}\u{0300}", v, v),
-                Superhigh => format!("{}\u{0301}{}\u{0301}", v, v),
-            },
+            PhonemicString::Consonant(s) => spell_consonants(&s, profile),
+            PhonemicString::Vowel(v, ty) => spell_vowel(&v, ty, profile),
         }
     }
 
-    /// Simplify all vowels by stripping out tone and length.
-    /// Convert t/th consonants to the d/t representation with apostrophe for
-    /// the glottal stop.
-    pub fn into_learner(self) -> String {
-        use itertools::Itertools;
+    /// Convert this Cherokee phonetic string into the International Phonetic
+    /// Alphabet. Tones are written after each vowel with Chao tone letters, on
+    /// a scale where low is 2, high is 3, and superhigh rises to 4.
+    pub fn into_ipa(self) -> String {
+        use {itertools::Itertools, unicode_normalization::UnicodeNormalization, VowelType::*};
         match self {
-            PhonemicString::Form(all) => all.into_iter().map(|x| x.into_learner()).join(""),
-            PhonemicString::Consonant(s) => tth_to_dt(&s, false, Some(""), true, true),
+            PhonemicString::Form(all) => all
+                .into_iter()
+                .map(|x| x.into_ipa())
+                .join("")
+                .nfc()
+                .to_string(),
+            PhonemicString::Consonant(s) => ipa_consonants(&s),
+            PhonemicString::Vowel(v, ty) => {
+                // The nasal vowel v is a mid central vowel.
+                let v = if v == "v" { "ə̃" } else { &v };
+                let length = if ty.is_long() { "ː" } else { "" };
+                let tone = match ty {
+                    ShortLow | LongLow => "˨",
+                    ShortHigh | LongHigh => "˧",
+                    ShortLowfall | Lowfall => "˨˩",
+                    ShortSuperhigh | Superhigh => "˧˦",
+                    Rising => "˨˧",
+                    Falling => "˧˨",
+                };
+                format!("{}{}{}", v, length, tone)
+            }
+        }
+    }
+
+    /// Transliterate this phonetic string into the Cherokee syllabary.
+    /// Tone, vowel length, and glottal stops are dropped. Consonants without a
+    /// following vowel can't be written either, except for /s/ which has its
+    /// own character.
+    pub fn into_syllabary(self) -> String {
+        use {lazy_static::lazy_static, regex::Regex};
+        lazy_static! {
+            static ref SYLLABLE: Regex = Regex::new("([^aeiouv]*)([aeiouv])").unwrap();
+        }
+        let plain = self.into_plain();
+        let mut result = String::new();
+        let mut rest = 0;
+        for caps in SYLLABLE.captures_iter(&plain) {
+            let (leftover, syllable) = syllabary_syllable(&caps[1], &caps[2]);
+            result.push_str(&syllabary_consonants(leftover));
+            result.push(syllable);
+            rest = caps.get(0).unwrap().end();
+        }
+        result.push_str(&syllabary_consonants(&plain[rest..]));
+        result
+    }
+
+    /// Split this phonetic string into syllables that each correspond to one
+    /// character of the syllabary. Consonants the syllabary doesn't write join
+    /// the following syllable, or the last one at the end of a word.
+    pub fn syllabify(self) -> Vec<Syllable> {
+        use unicode_normalization::UnicodeNormalization;
+        /// Split off a standalone syllable for each /s/ in the given cluster,
+        /// returning the rest of the cluster after the last one.
+        fn split_s<'a>(syllables: &mut Vec<Syllable>, mut cluster: &'a str) -> &'a str {
+            while let Some(idx) = cluster.find('s') {
+                syllables.push(Syllable {
+                    phonemic: cluster[..=idx].to_owned(),
+                    start: 0,
+                    syllabary: "Ꮝ".to_owned(),
+                });
+                cluster = &cluster[idx + 1..];
+            }
+            cluster
+        }
+
+        let items = match self {
+            PhonemicString::Form(all) => all,
+            other => vec![other],
+        };
+        let mut syllables = Vec::new();
+        let mut cluster = String::new();
+        for item in items {
+            match item {
+                PhonemicString::Vowel(v, ty) => {
+                    let (leftover, c) = syllabary_syllable(&cluster, &v);
+                    let onset = format!(
+                        "{}{}",
+                        split_s(&mut syllables, leftover),
+                        &cluster[leftover.len()..]
+                    );
+                    syllables.push(Syllable {
+                        phonemic: onset + &PhonemicString::Vowel(v, ty).into_dailp(),
+                        start: 0,
+                        syllabary: c.to_string(),
+                    });
+                    cluster.clear();
+                }
+                PhonemicString::Consonant(s) => cluster.push_str(&s),
+                PhonemicString::Form(all) => {
+                    cluster.push_str(&PhonemicString::Form(all).into_dailp())
+                }
+            }
+        }
+        let rest = split_s(&mut syllables, &cluster).to_owned();
+        if let Some(last) = syllables.last_mut() {
+            last.phonemic.push_str(&rest);
+        } else if !rest.is_empty() {
+            syllables.push(Syllable {
+                phonemic: rest,
+                start: 0,
+                syllabary: String::new(),
+            });
+        }
+
+        // Find where each syllable starts within the whole string.
+        let mut start = 0;
+        for syllable in &mut syllables {
+            syllable.phonemic = syllable.phonemic.nfc().collect();
+            syllable.start = start;
+            start += syllable.phonemic.chars().count() as i32;
+        }
+        syllables
+    }
+
+    /// Line up each syllable of this phonetic string with the characters of
+    /// the given syllabary source, marking where they disagree.
+    /// Characters other than syllabary in the source are ignored.
+    pub fn align_with_syllabary(self, source: &str) -> Vec<SyllableAlignment> {
+        let syllables = self.syllabify();
+        let source: Vec<String> = source
+            .chars()
+            .map(|c| c.to_uppercase().next().unwrap_or(c))
+            .filter(|c| SYLLABARY_SOUNDS.contains_key(c))
+            .map(|c| c.to_string())
+            .collect();
+
+        // Syllables the syllabary can't write are free to skip, but can't
+        // line up with a source character.
+        let skip_cost = |s: &Syllable| if s.syllabary.is_empty() { 0 } else { 1 };
+        let sub_cost = |s: &Syllable, c: &String| {
+            if s.syllabary.is_empty() {
+                2
+            } else if s.syllabary == *c {
+                0
+            } else {
+                1
+            }
+        };
+
+        // Standard edit distance table between syllables and source characters.
+        let (n, m) = (syllables.len(), source.len());
+        let mut dist = vec![vec![0; m + 1]; n + 1];
+        for i in 1..=n {
+            dist[i][0] = dist[i - 1][0] + skip_cost(&syllables[i - 1]);
+        }
+        for (j, d) in dist[0].iter_mut().enumerate() {
+            *d = j;
+        }
+        for i in 1..=n {
+            for j in 1..=m {
+                dist[i][j] = (dist[i - 1][j - 1] + sub_cost(&syllables[i - 1], &source[j - 1]))
+                    .min(dist[i - 1][j] + skip_cost(&syllables[i - 1]))
+                    .min(dist[i][j - 1] + 1);
+            }
+        }
+
+        // Walk back through the table to recover the alignment.
+        let mut alignment = Vec::new();
+        let (mut i, mut j) = (n, m);
+        while i > 0 || j > 0 {
+            if i > 0
+                && j > 0
+                && dist[i][j] == dist[i - 1][j - 1] + sub_cost(&syllables[i - 1], &source[j - 1])
+            {
+                alignment.push(SyllableAlignment {
+                    is_match: syllables[i - 1].syllabary == source[j - 1],
+                    syllable: Some(syllables[i - 1].clone()),
+                    source: Some(source[j - 1].clone()),
+                });
+                i -= 1;
+                j -= 1;
+            } else if i > 0 && dist[i][j] == dist[i - 1][j] + skip_cost(&syllables[i - 1]) {
+                alignment.push(SyllableAlignment {
+                    is_match: syllables[i - 1].syllabary.is_empty(),
+                    syllable: Some(syllables[i - 1].clone()),
+                    source: None,
+                });
+                i -= 1;
+            } else {
+                alignment.push(SyllableAlignment {
+                    is_match: false,
+                    syllable: None,
+                    source: Some(source[j - 1].clone()),
+                });
+                j -= 1;
+            }
+        }
+        alignment.reverse();
+        alignment
+    }
+
+    /// Reduce this phonetic string to a key for loose searching, which ignores
+    /// tone, vowel length, glottal stops, and aspiration so that t/th and d/t
+    /// spellings of the same word produce the same key.
+    pub fn into_search_key(self) -> String {
+        use {
+            lazy_static::lazy_static,
+            regex::{Captures, Regex},
+        };
+        lazy_static! {
+            static ref CONSONANTS: Regex =
+                Regex::new(r"(tlh|kwh|dl|qu|gw|kh|th|ts|ch|d|g|j)").unwrap();
+        }
+        let plain: String = self
+            .into_plain()
+            .chars()
+            .filter(|c| c.is_alphanumeric() && *c != 'ʔ')
+            .collect();
+        CONSONANTS
+            .replace_all(&plain, |cap: &Captures| match &cap[0] {
+                "tlh" | "dl" => "tl",
+                "kwh" | "qu" | "gw" => "kw",
+                "kh" | "g" => "k",
+                "th" | "d" => "t",
+                "ts" | "ch" | "j" => "c",
+                _ => unreachable!(),
+            })
+            .into_owned()
+    }
+
+    /// Strip all tone and length markings, leaving plain consonants and vowels.
+    fn into_plain(self) -> String {
+        use {itertools::Itertools, unicode_normalization::UnicodeNormalization};
+        match self {
+            PhonemicString::Form(all) => all.into_iter().map(|x| x.into_plain()).join(""),
+            PhonemicString::Consonant(s) => s
+                .nfd()
+                .filter(|c| !unicode_normalization::char::is_combining_mark(*c))
+                .collect(),
             PhonemicString::Vowel(v, _ty) => reduce_long_vowels(&v).to_owned(),
         }
     }
@@ -852,6 +1212,176 @@ fn reduce_long_vowels(input: &str) -> &str {
     }
 }
 
+/// Convert the t/th consonants of DAILP internal orthography into IPA.
+fn ipa_consonants(input: &str) -> String {
+    use {
+        lazy_static::lazy_static,
+        regex::{Captures, Regex},
+    };
+    lazy_static! {
+        static ref TTH_PATTERN: Regex = Regex::new(r"(tlh|kwh|tl|kw|kh|th|ch|ts|c|y|'|:)").unwrap();
+    }
+    let result = TTH_PATTERN.replace_all(input, |cap: &Captures| match &cap[0] {
+        "tlh" => "t͡ɬʰ",
+        "kwh" => "kʷʰ",
+        "tl" => "t͡ɬ",
+        "kw" => "kʷ",
+        "kh" => "kʰ",
+        "th" => "tʰ",
+        "ch" => "t͡sʰ",
+        "ts" | "c" => "t͡s",
+        "y" => "j",
+        "'" => "ʔ",
+        // Colons mark a morphophonemic process with no phonetic value.
+        ":" => "",
+        _ => unreachable!(),
+    });
+    result.into_owned()
+}
+
+/// Every character of the Cherokee syllabary paired with the sound it
+/// represents in DAILP internal orthography.
+const SYLLABARY: &[(char, &str)] = &[
+    ('Ꭰ', "a"),
+    ('Ꭱ', "e"),
+    ('Ꭲ', "i"),
+    ('Ꭳ', "o"),
+    ('Ꭴ', "u"),
+    ('Ꭵ', "v"),
+    ('Ꭶ', "ka"),
+    ('Ꭷ', "kha"),
+    ('Ꭸ', "ke"),
+    ('Ꭹ', "ki"),
+    ('Ꭺ', "ko"),
+    ('Ꭻ', "ku"),
+    ('Ꭼ', "kv"),
+    ('Ꭽ', "ha"),
+    ('Ꭾ', "he"),
+    ('Ꭿ', "hi"),
+    ('Ꮀ', "ho"),
+    ('Ꮁ', "hu"),
+    ('Ꮂ', "hv"),
+    ('Ꮃ', "la"),
+    ('Ꮄ', "le"),
+    ('Ꮅ', "li"),
+    ('Ꮆ', "lo"),
+    ('Ꮇ', "lu"),
+    ('Ꮈ', "lv"),
+    ('Ꮉ', "ma"),
+    ('Ꮊ', "me"),
+    ('Ꮋ', "mi"),
+    ('Ꮌ', "mo"),
+    ('Ꮍ', "mu"),
+    ('Ꮎ', "na"),
+    ('Ꮏ', "hna"),
+    ('Ꮐ', "nah"),
+    ('Ꮑ', "ne"),
+    ('Ꮒ', "ni"),
+    ('Ꮓ', "no"),
+    ('Ꮔ', "nu"),
+    ('Ꮕ', "nv"),
+    ('Ꮖ', "kwa"),
+    ('Ꮗ', "kwe"),
+    ('Ꮘ', "kwi"),
+    ('Ꮙ', "kwo"),
+    ('Ꮚ', "kwu"),
+    ('Ꮛ', "kwv"),
+    ('Ꮜ', "sa"),
+    ('Ꮝ', "s"),
+    ('Ꮞ', "se"),
+    ('Ꮟ', "si"),
+    ('Ꮠ', "so"),
+    ('Ꮡ', "su"),
+    ('Ꮢ', "sv"),
+    ('Ꮣ', "ta"),
+    ('Ꮤ', "tha"),
+    ('Ꮥ', "te"),
+    ('Ꮦ', "the"),
+    ('Ꮧ', "ti"),
+    ('Ꮨ', "thi"),
+    ('Ꮩ', "to"),
+    ('Ꮪ', "tu"),
+    ('Ꮫ', "tv"),
+    ('Ꮬ', "tla"),
+    ('Ꮭ', "tlha"),
+    ('Ꮮ', "tle"),
+    ('Ꮯ', "tli"),
+    ('Ꮰ', "tlo"),
+    ('Ꮱ', "tlu"),
+    ('Ꮲ', "tlv"),
+    ('Ꮳ', "ca"),
+    ('Ꮴ', "ce"),
+    ('Ꮵ', "ci"),
+    ('Ꮶ', "co"),
+    ('Ꮷ', "cu"),
+    ('Ꮸ', "cv"),
+    ('Ꮹ', "wa"),
+    ('Ꮺ', "we"),
+    ('Ꮻ', "wi"),
+    ('Ꮼ', "wo"),
+    ('Ꮽ', "wu"),
+    ('Ꮾ', "wv"),
+    ('Ꮿ', "ya"),
+    ('Ᏸ', "ye"),
+    ('Ᏹ', "yi"),
+    ('Ᏺ', "yo"),
+    ('Ᏻ', "yu"),
+    ('Ᏼ', "yv"),
+    ('Ᏽ', "mv"),
+];
+
+lazy_static::lazy_static! {
+    static ref SYLLABARY_SOUNDS: std::collections::HashMap<char, &'static str> =
+        SYLLABARY.iter().copied().collect();
+    static ref SYLLABARY_CHARS: std::collections::HashMap<&'static str, char> =
+        SYLLABARY.iter().map(|(c, sound)| (*sound, *c)).collect();
+}
+
+fn is_syllabary_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'v')
+}
+
+/// Find the syllabary character for the given vowel along with as much of the
+/// preceding consonant cluster as one character can represent. Returns the
+/// leftover consonants that come before that syllable.
+fn syllabary_syllable<'a>(cluster: &'a str, vowel: &str) -> (&'a str, char) {
+    // Try the longest onset first, so that "tlh" takes precedence over "h".
+    for (idx, _) in cluster.char_indices() {
+        let onset = match &cluster[idx..] {
+            "g" => "k",
+            "d" => "t",
+            "j" | "ts" | "ch" => "c",
+            "gw" | "kwh" => "kw",
+            "dl" => "tl",
+            other => other,
+        };
+        // Most syllables don't distinguish aspirated consonants, so fall back
+        // on the plain consonant.
+        let plain = onset.strip_suffix('h').filter(|c| !c.is_empty());
+        for onset in std::iter::once(onset).chain(plain) {
+            if let Some(c) = SYLLABARY_CHARS.get(format!("{}{}", onset, vowel).as_str()) {
+                return (&cluster[..idx], *c);
+            }
+        }
+    }
+    (cluster, SYLLABARY_CHARS[vowel])
+}
+
+/// Write out consonants that aren't followed by a vowel. Only /s/ can stand
+/// alone in the syllabary, so the rest are dropped while symbols like morpheme
+/// separators are kept.
+fn syllabary_consonants(consonants: &str) -> String {
+    consonants
+        .chars()
+        .filter_map(|c| match c {
+            's' => Some('Ꮝ'),
+            't' | 'd' | 'k' | 'g' | 'h' | 'c' | 'j' | 'm' | 'n' | 'w' | 'r' | 'y' | 'l' | 'q'
+            | 'ʔ' | '\'' | ':' => None,
+            other => Some(other),
+        })
+        .collect()
+}
+
 fn dt_to_tth(input: &str, keep_glottal_stops: bool, replace_colons: Option<&str>) -> String {
     use {
         lazy_static::lazy_static,
@@ -888,6 +1418,12 @@ fn dt_to_tth(input: &str, keep_glottal_stops: bool, replace_colons: Option<&str>
     result.into_owned()
 }
 
+/// Make a loose search key from a phonetic string in either the t/th or d/t
+/// system. See [`PhonemicString::into_search_key`].
+pub fn phonetic_search_key(input: &str) -> String {
+    PhonemicString::parse_dailp(input).into_search_key()
+}
+
 /// Convert consonants in the given d/t phonetics string into their Worcester
 /// phonetics equivalents. gw => qu, j => ts
 pub fn simple_phonetics_to_worcester(input: &str) -> String {
@@ -909,19 +1445,83 @@ pub fn simple_phonetics_to_worcester(input: &str) -> String {
     result.into_owned()
 }
 
-fn tth_to_dt(
-    input: &str,
-    keep_glottal_stops: bool,
-    replace_colons: Option<&str>,
-    qu_and_ts: bool,
-    drop_initial_glottal_stop: bool,
-) -> String {
+/// Convert the consonants in the given Worcester phonetics string back into
+/// their d/t phonetics equivalents. qu => gw, ts => j
+///
+/// Worcester phonetics doesn't distinguish kw from gw, so "qu" is always read
+/// as the plain consonant.
+pub fn worcester_to_simple_phonetics(input: &str) -> String {
+    use {
+        lazy_static::lazy_static,
+        regex::{Captures, Regex},
+    };
+    lazy_static! {
+        static ref WORCESTER_PATTERN: Regex = Regex::new(r"(qu|ts|')").unwrap();
+    }
+    let result = WORCESTER_PATTERN.replace_all(input, |cap: &Captures| match &cap[0] {
+        "qu" => "gw",
+        "ts" => "j",
+        "'" => "ʔ",
+        _ => unreachable!(),
+    });
+    result.into_owned()
+}
+
+/// Spell a vowel with the tone and length marking of the given profile.
+fn spell_vowel(v: &str, ty: VowelType, profile: &OrthographyProfile) -> String {
+    use VowelType::*;
+    let ty = match profile.vowel_length {
+        VowelLength::Doubled => ty,
+        VowelLength::Single => ty.shortened(),
+    };
+    match profile.tone {
+        ToneMarking::Taoc => match ty {
+            ShortLow => v.to_owned(),
+            ShortHigh => format!("{}\u{0301}", v),
+            ShortLowfall => format!("{}\u{0300}", v),
+            ShortSuperhigh => format!("{}\u{030B}", v),
+            LongLow => format!("{}{}", v, v),
+            LongHigh => format!("{}\u{0301}{}\u{0301}", v, v),
+            Rising => format!("{}{}\u{0301}", v, v),
+            Falling => format!("{}\u{0301}{}", v, v),
+            Lowfall => format!("{}\u{0300}{}\u{0300}", v, v),
+            Superhigh => format!("{}{}\u{030B}", v, v),
+        },
+        ToneMarking::Crg => match ty {
+            // Short vowels in CRG match TAOC.
+            ShortLow => v.to_owned(),
+            ShortHigh | ShortSuperhigh => format!("{}\u{0301}", v),
+            ShortLowfall => format!("{}\u{0300}", v),
+            // The long vowels are slightly different.
+            LongLow => format!("{}{}", v, v),
+            LongHigh => format!("{}\u{0301}{}", v, v),
+            Rising => format!("{}{}\u{0301}", v, v),
+            Falling => format!("{}\u{0301}{}\u{0300}", v, v),
+            Lowfall => format!("{}{}\u{0300}", v, v),
+            Superhigh => format!("{}\u{0301}{}\u{0301}", v, v),
+        },
+        ToneMarking::Unmarked => {
+            let v = reduce_long_vowels(v);
+            if ty.is_long() {
+                format!("{}{}", v, v)
+            } else {
+                v.to_owned()
+            }
+        }
+    }
+}
+
+/// Convert the t/th consonants of DAILP internal orthography into the
+/// consonants of the given profile.
+fn spell_consonants(input: &str, profile: &OrthographyProfile) -> String {
     use {
         lazy_static::lazy_static,
         regex::{Captures, Regex},
     };
     let glottal_stop_len = 'ʔ'.len_utf8();
-    let input = if drop_initial_glottal_stop && input.len() > glottal_stop_len {
+    let input = if profile.glottal_stop == GlottalStopSpelling::Apostrophe
+        && input.len() > glottal_stop_len
+    {
         if input.starts_with('ʔ') {
             &input[glottal_stop_len..]
         } else if input.ends_with('ʔ') {
@@ -932,64 +1532,48 @@ fn tth_to_dt(
     } else {
         input
     };
-    // Convert the t/th consonants to d/t
     lazy_static! {
         static ref TTH_PATTERN: Regex =
             Regex::new(r"(qu|ts|ks|tlh|kwh|tl|kw|kh|th|ch|k|t|c|ʔ|:)").unwrap();
     }
+    let dt = profile.stops == StopSpelling::Dt;
+    let qu = profile.labiovelar == LabiovelarSpelling::Qu;
+    let ts = profile.affricate == AffricateSpelling::Ts;
     let result = TTH_PATTERN.replace_all(input, |cap: &Captures| match &cap[0] {
-        "tlh" => "tl",
-        "tl" => "dl",
-        "qu" => {
-            if qu_and_ts {
-                "qu"
-            } else {
-                "gw"
-            }
-        }
-        "kwh" => {
-            if qu_and_ts {
-                "qu"
-            } else {
-                "kw"
-            }
-        }
-        "kw" => {
-            if qu_and_ts {
-                "qu"
-            } else {
-                "gw"
-            }
-        }
-        "kh" => "k",
-        "th" => "t",
+        "tlh" if dt => "tl",
+        "tl" if dt => "dl",
+        "qu" | "kw" | "kwh" if qu => "qu",
+        "qu" | "kw" if dt => "gw",
+        "qu" => "kw",
+        "kwh" if dt => "kw",
+        "kh" if dt => "k",
+        "th" if dt => "t",
         "ch" => "ch", // Not sure I've ever seen this segment in data before.
-        "k" => "g",
-        "t" => "d",
-        "c" => {
-            if qu_and_ts {
-                "ts"
-            } else {
-                "j"
-            }
-        }
-        "ʔ" => {
-            if keep_glottal_stops {
-                "ʔ"
-            } else {
-                "'"
-            }
-        }
-        ":" => replace_colons.unwrap_or(":"),
+        "k" if dt => "g",
+        "t" if dt => "d",
+        "c" | "ts" if ts => "ts",
+        "c" | "ts" if dt => "j",
+        "ʔ" => match profile.glottal_stop {
+            GlottalStopSpelling::Letter => "ʔ",
+            GlottalStopSpelling::Apostrophe => "'",
+        },
+        ":" => match profile.tone {
+            ToneMarking::Taoc => ":",
+            ToneMarking::Crg => "xx",
+            ToneMarking::Unmarked => "",
+        },
         // Any other matches we should leave as-is, retaining for example "ts"
-        // and "ks" in the d/t representation.
-        "ts" => {
-            if qu_and_ts {
-                "ts"
-            } else {
-                "j"
-            }
-        }
+        // and "ks" in the t/th representation.
+        "tlh" => "tlh",
+        "tl" => "tl",
+        "kwh" => "kwh",
+        "kw" => "kw",
+        "kh" => "kh",
+        "th" => "th",
+        "k" => "k",
+        "t" => "t",
+        "c" => "c",
+        "ts" => "ts",
         "ks" => "ks",
         _ => unreachable!(),
     });
@@ -997,7 +1581,7 @@ fn tth_to_dt(
 }
 
 /// Cherokee vowel categories based on tone and length
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VowelType {
     /// Short sound, low flat tone
     ShortLow,
@@ -1021,10 +1605,85 @@ pub enum VowelType {
     Superhigh,
 }
 
+impl VowelType {
+    /// Whether this is a long vowel.
+    pub fn is_long(&self) -> bool {
+        use VowelType::*;
+        !matches!(self, ShortLow | ShortHigh | ShortLowfall | ShortSuperhigh)
+    }
+
+    /// The short vowel with the closest tone to this one.
+    pub fn shortened(self) -> Self {
+        use VowelType::*;
+        match self {
+            LongLow => ShortLow,
+            LongHigh => ShortHigh,
+            Rising | Superhigh => ShortSuperhigh,
+            Falling | Lowfall => ShortLowfall,
+            short => short,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn syllabify() {
+        let syllables: Vec<_> = PhonemicString::parse_dailp("ùùnatoótákwààskvv̋ʔi")
+            .syllabify()
+            .into_iter()
+            .map(|s| (s.phonemic, s.start, s.syllabary))
+            .collect();
+        assert_eq!(
+            syllables,
+            [
+                ("ùù", 0, "Ꭴ"),
+                ("na", 2, "Ꮎ"),
+                ("toó", 4, "Ꮩ"),
+                ("tá", 7, "Ꮣ"),
+                ("kwàà", 9, "Ꮖ"),
+                ("s", 13, "Ꮝ"),
+                ("kvv̋", 14, "Ꭼ"),
+                ("ʔi", 18, "Ꭲ"),
+            ]
+            .map(|(p, i, s)| (p.to_owned(), i, s.to_owned()))
+        );
+    }
+
+    #[test]
+    fn syllabary_alignment() {
+        let alignment =
+            PhonemicString::parse_dailp("ùùnatoótákwààskvv̋ʔi").align_with_syllabary("ᎤᎾᏙᏓᏆᏍᎬᎢ");
+        assert_eq!(alignment.len(), 8);
+        assert!(alignment.iter().all(|a| a.is_match));
+
+        // One character written wrong and another missing.
+        let alignment =
+            PhonemicString::parse_dailp("ùùnatoótákwààskvv̋ʔi").align_with_syllabary("ᎤᏫᏙᏓᏍᎬᎢ");
+        let mismatches: Vec<_> = alignment
+            .iter()
+            .filter(|a| !a.is_match)
+            .map(|a| {
+                (
+                    a.syllable.as_ref().map(|s| s.phonemic.as_str()),
+                    a.source.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(mismatches, [(Some("na"), Some("Ꮻ")), (Some("kwàà"), None)]);
+    }
+
+    #[test]
+    fn search_keys() {
+        let key = phonetic_search_key("ùùnatoótákwààskvv̋ʔi");
+        assert_eq!(key, "unatotakwaskvi");
+        assert_eq!(phonetic_search_key("unadodaquasgvi"), key);
+        assert_eq!(phonetic_search_key("uunatootakwaaskvv'i"), key);
+        assert_eq!(phonetic_search_key("ts-tlhi=hn"), "ctlihn");
+    }
+
     #[test]
     fn morpheme_id_page_number() {
         let id = MorphemeId::parse("DF2018:55");
@@ -1052,6 +1711,19 @@ DF2018:54",
         assert_eq!(id, None);
     }
 
+    #[test]
+    fn morpheme_id_references() {
+        let id = MorphemeId::parse_reference(" DF1975.23:catch ").unwrap();
+        assert_eq!(id.document_name.as_deref(), Some("DF1975"));
+        assert_eq!(id.index, Some(23));
+        assert_eq!(id.gloss, "catch");
+        assert!(MorphemeId::parse_reference("catch").is_err());
+        assert!(MorphemeId::parse_reference(":catch").is_err());
+        assert!(MorphemeId::parse_reference("DF1975:").is_err());
+        assert!(MorphemeId::parse_reference("DF1975.x:catch").is_err());
+        assert!(MorphemeId::parse_reference("DF 1975:catch").is_err());
+    }
+
     // #[test]
     // fn morpheme_id_raw() {
     //     let raw = "as for me (1SG.PRO + CS)";
@@ -0,0 +1,191 @@
+//! Dictionary entries, which describe the morphemes that a dictionary like
+//! DF1975 defines along with the forms it lists for each.
+
+use crate::{AnnotatedForm, CherokeeOrthography, Database, DocumentReference, OrthographyId};
+use async_graphql::{dataloader::DataLoader, Context, FieldResult};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    postgres::{PgHasArrayType, PgTypeInfo},
+    types::Uuid,
+};
+
+/// Part of speech that a dictionary headword is listed under.
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, sqlx::Type, async_graphql::Enum, PartialEq, Eq,
+)]
+#[sqlx(type_name = "part_of_speech")]
+pub enum PartOfSpeech {
+    /// Inflected for person and aspect, like "to catch"
+    Verb,
+    /// Names a person, place, or thing
+    Noun,
+    /// Describes a noun
+    Adjective,
+    /// Counts or orders things
+    Numeral,
+    /// Any other uninflected word
+    Particle,
+}
+
+impl PgHasArrayType for PartOfSpeech {
+    fn array_type_info() -> PgTypeInfo {
+        <&str as PgHasArrayType>::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        <&str as PgHasArrayType>::array_compatible(ty)
+    }
+}
+
+/// A headword to insert into a dictionary, along with the stem it defines.
+#[derive(Clone, Debug)]
+pub struct NewLexicalEntry {
+    /// Headword as written in the dictionary, whose first segment is the stem
+    /// it defines and whose English glosses are its senses
+    pub stem: AnnotatedForm,
+    /// Part of speech the headword is listed under, if known
+    pub part_of_speech: Option<PartOfSpeech>,
+}
+
+impl NewLexicalEntry {
+    /// Gloss identifying this entry within its dictionary, made from the gloss
+    /// of its stem without any punctuation. For example, "catch (animate)"
+    /// becomes "catch.animate".
+    pub fn gloss(&self) -> Option<String> {
+        let segment = self.stem.segments.as_ref()?.first()?;
+        Some(Self::normalize_gloss(&segment.gloss))
+    }
+
+    /// Write a gloss without any punctuation, the way entry glosses are
+    /// stored, like "catch.animate" for "catch (animate)".
+    pub fn normalize_gloss(gloss: &str) -> String {
+        gloss
+            .replace(&[',', '+', '(', ')', '[', ']'] as &[char], " ")
+            .split_whitespace()
+            .join(".")
+    }
+}
+
+/// One headword of a dictionary, describing a morpheme defined there.
+#[derive(async_graphql::SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct LexicalEntry {
+    /// Unique identifier of this entry
+    pub id: Uuid,
+    /// Morpheme gloss that this entry describes
+    #[graphql(skip)]
+    pub gloss_id: Uuid,
+    /// Headword as written in the dictionary
+    pub headword: String,
+    /// Gloss identifying this entry within its dictionary, like "catch"
+    pub gloss: String,
+    /// Shape of the morpheme in DAILP internal orthography
+    #[graphql(skip)]
+    pub shape: Option<String>,
+    /// Part of speech the headword is listed under, if known
+    pub part_of_speech: Option<PartOfSpeech>,
+    /// English translations, in the order the dictionary gives them
+    pub senses: Vec<String>,
+    /// Dictionary that this entry comes from
+    pub source: DocumentReference,
+    /// Page of the dictionary this entry is on
+    pub page_number: Option<String>,
+}
+
+#[async_graphql::ComplexObject]
+impl LexicalEntry {
+    /// Identifier of the morpheme this entry describes, like "DF1975:catch",
+    /// which other queries take as a gloss.
+    async fn morpheme_id(&self) -> String {
+        format!("{}:{}", self.source.short_name, self.gloss)
+    }
+
+    /// Shape of the morpheme, written in the given registered orthography
+    async fn shape(
+        &self,
+        #[graphql(default_with = "CherokeeOrthography::Taoc.into()")] system: OrthographyId,
+    ) -> FieldResult<Option<String>> {
+        let (_, orthography) = system.resolve()?;
+        Ok(self.shape.as_ref().map(|shape| orthography.render(shape)))
+    }
+
+    /// How to cite this entry, like "Cherokee–English Dictionary (DF1975), p. 42"
+    async fn citation(&self) -> String {
+        let source = format!("{} ({})", self.source.title, self.source.short_name);
+        match &self.page_number {
+            Some(page) if !page.is_empty() => format!("{}, p. {}", source, page),
+            _ => source,
+        }
+    }
+
+    /// Inflected forms that the dictionary lists under this entry
+    async fn surface_forms(&self, context: &Context<'_>) -> FieldResult<Vec<AnnotatedForm>> {
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .forms_with_root(&self.gloss, Some(&self.source.short_name))
+            .await?)
+    }
+
+    /// Words from other documents that contain this morpheme or one linked to
+    /// it, like the uses of a verb in a letter
+    async fn attestations(&self, context: &Context<'_>) -> FieldResult<Vec<AnnotatedForm>> {
+        let forms = context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .connected_forms(Some(crate::DocumentId(self.source.id)), &self.gloss)
+            .await?;
+        Ok(forms
+            .into_iter()
+            .filter(|form| form.position.document_id.0 != self.source.id)
+            .unique_by(|form| form.id)
+            .collect())
+    }
+
+    /// Entries in other dictionaries that describe a related morpheme
+    async fn cross_references(&self, context: &Context<'_>) -> FieldResult<Vec<LexicalEntry>> {
+        Ok(context
+            .data::<DataLoader<Database>>()?
+            .loader()
+            .lexical_cross_references(self.gloss_id)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DocumentId, PositionInDocument, WordSegment};
+
+    #[test]
+    fn entry_gloss() {
+        let entry = NewLexicalEntry {
+            stem: AnnotatedForm {
+                id: None,
+                source: "hnoga".to_owned(),
+                normalized_source: None,
+                simple_phonetics: None,
+                phonemic: None,
+                segments: Some(vec![WordSegment::new(
+                    "hnoga".to_owned(),
+                    "catch (animate), [live]".to_owned(),
+                    None,
+                )]),
+                english_gloss: Vec::new(),
+                commentary: None,
+                line_break: None,
+                page_break: None,
+                position: PositionInDocument::new(
+                    DocumentId(Default::default()),
+                    "1".to_owned(),
+                    1,
+                ),
+                date_recorded: None,
+                ingested_audio_track: None,
+            },
+            part_of_speech: Some(PartOfSpeech::Verb),
+        };
+        assert_eq!(entry.gloss().as_deref(), Some("catch.animate.live"));
+    }
+}
@@ -18,6 +18,8 @@
 //! knows what document it came from, retaining a sense of source and concrete
 //! reference.
 
+mod advanced_search;
+mod allomorph;
 pub mod annotation;
 mod audio;
 
@@ -31,22 +33,32 @@ pub mod auth;
 mod cherokee;
 pub mod collection;
 pub mod comment;
+mod concordance;
 mod database_sql;
 mod date;
+mod distribution;
 pub mod doc_metadata;
 mod document;
+mod english_search;
+mod etymology;
 mod form;
+mod full_text_search;
 mod geometry;
 mod gloss;
+mod igt;
 pub mod iiif;
 mod lexical;
+mod lexical_entry;
 pub mod menu;
 mod morpheme;
+mod orthography;
 pub mod page;
+mod paradigm;
 mod person;
 pub mod raw;
 pub mod sheet_result;
 mod slugs;
+mod suggestion;
 mod tag;
 mod translation;
 pub mod user;
@@ -56,21 +68,33 @@ pub use async_graphql;
 pub use chrono;
 pub use sqlx::types::Uuid;
 
+pub use advanced_search::*;
+pub use allomorph::*;
 pub use audio::*;
 pub use cherokee::*;
 pub use collection::*;
+pub use concordance::*;
 pub use database_sql::*;
 pub use date::*;
+pub use distribution::*;
 pub use doc_metadata::*;
 pub use document::*;
+pub use english_search::*;
+pub use etymology::*;
 pub use form::*;
+pub use full_text_search::*;
 pub use geometry::*;
 pub use gloss::*;
+pub use igt::*;
 pub use lexical::*;
+pub use lexical_entry::*;
 pub use menu::*;
 pub use morpheme::*;
+pub use orthography::*;
+pub use paradigm::*;
 pub use person::*;
 pub use sheet_result::*;
 pub use slugs::*;
+pub use suggestion::*;
 pub use tag::*;
 pub use translation::*;
@@ -24,6 +24,9 @@ pub struct WordSegment {
     /// This field determines what character should separate this segment from
     /// the next one when reconstituting a full segmentation string.
     pub role: WordSegmentRole,
+    /// Whether this segment is a root, affix, or clitic, if known.
+    #[serde(default)]
+    pub class: Option<WordSegmentClass>,
     /// Optional glossary entry for this segment which gives further information,
     /// like a definition and example usages.
     pub matching_tag: Option<MorphemeTag>,
@@ -32,7 +35,7 @@ pub struct WordSegment {
 /// The kind of segment that a particular sequence of characters in a morphemic
 /// segmentations represent.
 #[derive(
-    Clone, Copy, Debug, Serialize, Deserialize, sqlx::Type, async_graphql::Enum, PartialEq, Eq,
+    Clone, Copy, Debug, Serialize, Deserialize, sqlx::Type, async_graphql::Enum, PartialEq, Eq, Hash,
 )]
 #[sqlx(type_name = "word_segment_role")]
 pub enum WordSegmentRole {
@@ -54,6 +57,31 @@ impl PgHasArrayType for WordSegmentRole {
     }
 }
 
+/// The kind of unit of meaning that a segment is, regardless of the character
+/// that separates it from the others.
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, sqlx::Type, async_graphql::Enum, PartialEq, Eq,
+)]
+#[sqlx(type_name = "word_segment_class")]
+pub enum WordSegmentClass {
+    /// Lexical morpheme that carries the core meaning of a word
+    Root,
+    /// Functional morpheme bound to a root, like a prefix or suffix
+    Affix,
+    /// Functional morpheme that attaches to a whole word or phrase
+    Clitic,
+}
+
+impl PgHasArrayType for WordSegmentClass {
+    fn array_type_info() -> PgTypeInfo {
+        <&str as PgHasArrayType>::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        <&str as PgHasArrayType>::array_compatible(ty)
+    }
+}
+
 impl WordSegment {
     /// Make a new morpheme segment
     pub fn new(morpheme: String, gloss: String, role: Option<WordSegmentRole>) -> Self {
@@ -65,6 +93,7 @@ impl WordSegment {
             // migration code to create this data structure.
             gloss_id: None,
             role: role.unwrap_or(WordSegmentRole::Morpheme),
+            class: None,
             matching_tag: None,
         }
     }
@@ -72,9 +101,16 @@ impl WordSegment {
     /// Parse all segments from a raw interlinear morphemic segmentation.
     /// The first argument is the segmented source, while the second argument is
     /// the target language gloss of each segment.
-    pub fn parse_many(morpheme_layer: &str, gloss_layer: &str) -> Option<Vec<Self>> {
-        let (_, result) = parse_gloss_layers(morpheme_layer, gloss_layer).ok()?;
-        Some(result)
+    pub fn parse_many(
+        morpheme_layer: &str,
+        gloss_layer: &str,
+    ) -> Result<Vec<Self>, GlossParseError> {
+        parse_gloss_layers(morpheme_layer, gloss_layer)
+    }
+
+    /// Is this segment the root of its word?
+    pub fn is_root(&self) -> bool {
+        self.class == Some(WordSegmentClass::Root)
     }
 
     /// The separator that should follow this segment, based on the type of the
@@ -108,6 +144,26 @@ impl WordSegment {
             .join("")
     }
 
+    /// Build a string of the morphemic segmentation line, used in interlinear
+    /// gloss text (IGT). Each morpheme is written in its segment's system.
+    pub fn morpheme_layer<'a>(segments: impl IntoIterator<Item = &'a WordSegment>) -> String {
+        use itertools::Itertools;
+        segments
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, s)| {
+                vec![
+                    Cow::Borrowed(if index > 0 {
+                        s.get_previous_separator()
+                    } else {
+                        ""
+                    }),
+                    s.get_morpheme(),
+                ]
+            })
+            .join("")
+    }
+
     /// Convert the source representation of this segment into the given
     /// phonemic writing system.
     pub fn get_morpheme(&self) -> Cow<'_, str> {
@@ -135,6 +191,11 @@ impl WordSegment {
         self.role
     }
 
+    /// Whether this segment is a root, affix, or clitic, if known
+    async fn class(&self) -> Option<WordSegmentClass> {
+        self.class
+    }
+
     /// This field determines what character should separate this segment from
     /// the previous one when reconstituting the full segmentation string.
     async fn previous_separator(&self) -> &str {
@@ -155,7 +216,9 @@ impl WordSegment {
                 .data::<DataLoader<Database>>()?
                 .load_one(TagForMorpheme(
                     gloss_id,
-                    self.system.unwrap_or(CherokeeOrthography::Taoc),
+                    self.system
+                        .unwrap_or(CherokeeOrthography::Taoc)
+                        .tag_system(),
                 ))
                 .await?)
         } else {
@@ -176,4 +239,50 @@ pub struct MorphemeSegmentUpdate {
     /// This field determines what character should separate this segment from
     /// the next one when reconstituting the full segmentation string.
     pub role: WordSegmentRole,
+    /// Whether this segment is a root, affix, or clitic. If not given, this is
+    /// decided from the tag inventory.
+    pub class: Option<WordSegmentClass>,
+}
+
+impl MorphemeSegmentUpdate {
+    /// Make sure that each of the given segments has exactly one morpheme and
+    /// one gloss in it.
+    pub fn check_many(segments: &[Self]) -> Result<(), GlossParseError> {
+        check_segment_layers(
+            segments
+                .iter()
+                .map(|segment| (&*segment.morpheme, &*segment.gloss)),
+        )
+    }
+
+    /// Morphemes in DAILP internal orthography to store for this segment,
+    /// one for each of the abstract tags it stands for. If a run of the
+    /// stored segments has those tags and reads the same in this segment's
+    /// system, their morphemes are kept as they are, since reading back from
+    /// some systems loses tone, vowel length, or consonant distinctions.
+    /// Otherwise, the whole morpheme goes on the first tag.
+    pub fn dailp_morphemes(
+        &self,
+        default_system: CherokeeOrthography,
+        abstract_glosses: &[String],
+        stored: &[WordSegment],
+    ) -> Result<Vec<String>, anyhow::Error> {
+        if abstract_glosses.is_empty() {
+            return Ok(Vec::new());
+        }
+        let system = self.system.unwrap_or(default_system);
+        let unchanged = stored.windows(abstract_glosses.len()).find(|run| {
+            run.iter().map(|seg| &seg.gloss).eq(abstract_glosses)
+                && system.convert(&run.iter().map(|seg| &*seg.morpheme).collect::<String>())
+                    == self.morpheme
+        });
+        if let Some(run) = unchanged {
+            return Ok(run.iter().map(|seg| seg.morpheme.clone()).collect());
+        }
+        let morpheme = system.to_dailp(&self.morpheme)?;
+        Ok(std::iter::once(morpheme)
+            .chain(std::iter::repeat(String::new()))
+            .take(abstract_glosses.len())
+            .collect())
+    }
 }
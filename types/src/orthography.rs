@@ -0,0 +1,330 @@
+use crate::{CherokeeOrthography, PhonemicString};
+use anyhow::Result;
+use lazy_static::lazy_static;
+use unicode_normalization::UnicodeNormalization;
+
+/// A writing system for the phonology of one language.
+/// Each language stores its phonemic layer in one internal orthography, and
+/// every other system converts to and from that representation.
+pub trait Orthography: Send + Sync {
+    /// Unique identifier for this system, accepted by GraphQL `system`
+    /// arguments.
+    fn id(&self) -> &str;
+
+    /// Human-readable name of this system.
+    fn title(&self) -> &str;
+
+    /// Read a string written in this system into the internal orthography of
+    /// its language.
+    fn parse(&self, input: &str) -> Result<String>;
+
+    /// Write a string in the internal orthography of its language with this
+    /// system.
+    fn render(&self, internal: &str) -> String;
+
+    /// Put a string written in this system into a canonical form, so that
+    /// equivalent spellings compare equal.
+    fn normalize(&self, input: &str) -> String;
+}
+
+impl Orthography for CherokeeOrthography {
+    fn id(&self) -> &str {
+        use CherokeeOrthography::*;
+        match self {
+            Taoc => "TAOC",
+            Crg => "CRG",
+            Learner => "LEARNER",
+            Syllabary => "SYLLABARY",
+            Ipa => "IPA",
+        }
+    }
+
+    fn title(&self) -> &str {
+        use CherokeeOrthography::*;
+        match self {
+            Taoc => "Tone and Accent in Oklahoma Cherokee",
+            Crg => "Cherokee Reference Grammar",
+            Learner => "Learner",
+            Syllabary => "Cherokee Syllabary",
+            Ipa => "International Phonetic Alphabet",
+        }
+    }
+
+    fn parse(&self, input: &str) -> Result<String> {
+        use CherokeeOrthography::*;
+        let input = self.normalize(input);
+        let ast = match self {
+            Taoc => PhonemicString::parse_dailp(&input),
+            Crg => PhonemicString::parse_crg(&input),
+            Learner => PhonemicString::parse_learner(&input),
+            Syllabary => PhonemicString::parse_syllabary(&input),
+            Ipa => anyhow::bail!("Reading Cherokee from IPA is not supported"),
+        };
+        Ok(ast.into_dailp())
+    }
+
+    fn render(&self, internal: &str) -> String {
+        self.convert(internal)
+    }
+
+    fn normalize(&self, input: &str) -> String {
+        match self {
+            // Lowercase syllabary is only a stylistic variant.
+            CherokeeOrthography::Syllabary => input.nfc().collect::<String>().to_uppercase(),
+            _ => input.nfc().collect::<String>().to_lowercase(),
+        }
+    }
+}
+
+/// Orthography for a language written with the Latin alphabet, described by
+/// pairs of internal and written spellings. This is enough for systems that
+/// only differ in the letters they use for certain sounds.
+pub struct LatinOrthography {
+    id: &'static str,
+    title: &'static str,
+    /// Pairs of internal spelling and spelling in this system, applied in order.
+    spellings: &'static [(&'static str, &'static str)],
+}
+
+impl LatinOrthography {
+    /// Make a new system from pairs of internal and written spellings.
+    pub const fn new(
+        id: &'static str,
+        title: &'static str,
+        spellings: &'static [(&'static str, &'static str)],
+    ) -> Self {
+        Self {
+            id,
+            title,
+            spellings,
+        }
+    }
+}
+
+impl Orthography for LatinOrthography {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn title(&self) -> &str {
+        self.title
+    }
+
+    fn parse(&self, input: &str) -> Result<String> {
+        Ok(self
+            .spellings
+            .iter()
+            .fold(self.normalize(input), |s, (internal, written)| {
+                s.replace(written, internal)
+            }))
+    }
+
+    fn render(&self, internal: &str) -> String {
+        self.spellings.iter().fold(
+            internal.nfc().collect(),
+            |s: String, (internal, written)| s.replace(internal, written),
+        )
+    }
+
+    fn normalize(&self, input: &str) -> String {
+        input.nfc().collect::<String>().to_lowercase()
+    }
+}
+
+/// The Wôpanâak Language Reclamation Project orthography for Massachusett,
+/// which is also our internal orthography for that language.
+pub const WOPANAAK: LatinOrthography =
+    LatinOrthography::new("WLRP", "Wôpanâak Language Reclamation Project", &[]);
+
+/// The WLRP orthography with the 8 written as the ꝏ ligature that it stands
+/// for, as in colonial-era Massachusett texts.
+pub const WOPANAAK_LIGATURE: LatinOrthography = LatinOrthography::new(
+    "WLRP_LIGATURE",
+    "Wôpanâak Language Reclamation Project with ꝏ",
+    &[("8", "ꝏ")],
+);
+
+/// All the writing systems we support, grouped by the language they're for.
+pub struct OrthographyRegistry {
+    languages: Vec<(String, Vec<&'static dyn Orthography>)>,
+}
+
+lazy_static! {
+    static ref ORTHOGRAPHIES: OrthographyRegistry = OrthographyRegistry::default();
+}
+
+impl OrthographyRegistry {
+    /// Make a registry with no systems in it.
+    pub fn empty() -> Self {
+        Self {
+            languages: Vec::new(),
+        }
+    }
+
+    /// The registry of every built-in writing system.
+    pub fn global() -> &'static Self {
+        &ORTHOGRAPHIES
+    }
+
+    /// Add a writing system for the given language.
+    pub fn register(&mut self, language: &str, orthography: &'static dyn Orthography) {
+        if let Some((_, systems)) = self
+            .languages
+            .iter_mut()
+            .find(|(name, _)| name.eq_ignore_ascii_case(language))
+        {
+            systems.push(orthography);
+        } else {
+            self.languages
+                .push((language.to_owned(), vec![orthography]));
+        }
+    }
+
+    /// Find the registered system with the given identifier.
+    pub fn get(&self, id: &str) -> Option<&'static dyn Orthography> {
+        self.all()
+            .find(|(_, o)| o.id().eq_ignore_ascii_case(id))
+            .map(|(_, o)| o)
+    }
+
+    /// All systems registered for the given language. The first one is the
+    /// internal orthography of that language.
+    pub fn for_language(&self, language: &str) -> &[&'static dyn Orthography] {
+        self.languages
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(language))
+            .map(|(_, systems)| &systems[..])
+            .unwrap_or_default()
+    }
+
+    /// The language that the system with the given identifier belongs to.
+    pub fn language_of(&self, id: &str) -> Option<&str> {
+        self.all()
+            .find(|(_, o)| o.id().eq_ignore_ascii_case(id))
+            .map(|(l, _)| l)
+    }
+
+    /// Every registered system along with the name of its language.
+    pub fn all(&self) -> impl Iterator<Item = (&str, &'static dyn Orthography)> + '_ {
+        self.languages
+            .iter()
+            .flat_map(|(language, systems)| systems.iter().map(move |o| (&**language, *o)))
+    }
+}
+
+impl Default for OrthographyRegistry {
+    fn default() -> Self {
+        use CherokeeOrthography::*;
+        let mut registry = Self::empty();
+        for system in [&Taoc, &Crg, &Learner, &Syllabary, &Ipa] {
+            registry.register("Cherokee", system);
+        }
+        registry.register("Massachusett", &WOPANAAK);
+        registry.register("Massachusett", &WOPANAAK_LIGATURE);
+        registry
+    }
+}
+
+/// Identifier of a system in the [`OrthographyRegistry`], like "CRG" or
+/// "WLRP". Cherokee systems may also be given as [`CherokeeOrthography`] values
+/// like `CRG`, which is how clients have always passed them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrthographyId(pub String);
+
+#[async_graphql::Scalar]
+impl async_graphql::ScalarType for OrthographyId {
+    fn parse(value: async_graphql::Value) -> async_graphql::InputValueResult<Self> {
+        match value {
+            async_graphql::Value::String(id) => Ok(Self(id)),
+            async_graphql::Value::Enum(id) => Ok(Self(id.to_string())),
+            other => Err(async_graphql::InputValueError::expected_type(other)),
+        }
+    }
+
+    fn is_valid(value: &async_graphql::Value) -> bool {
+        matches!(
+            value,
+            async_graphql::Value::String(_) | async_graphql::Value::Enum(_)
+        )
+    }
+
+    fn to_value(&self) -> async_graphql::Value {
+        async_graphql::Value::String(self.0.clone())
+    }
+}
+
+impl From<CherokeeOrthography> for OrthographyId {
+    fn from(system: CherokeeOrthography) -> Self {
+        Self(system.id().to_owned())
+    }
+}
+
+impl OrthographyId {
+    /// Find this system in the global registry, along with the name of its
+    /// language.
+    pub fn resolve(&self) -> Result<(&'static str, &'static dyn Orthography)> {
+        OrthographyRegistry::global()
+            .all()
+            .find(|(_, o)| o.id().eq_ignore_ascii_case(&self.0))
+            .ok_or_else(|| anyhow::format_err!("Unknown orthography: {}", self.0))
+    }
+
+    /// The Cherokee system with this identifier, if it is one.
+    pub fn cherokee(&self) -> Option<CherokeeOrthography> {
+        use CherokeeOrthography::*;
+        [Taoc, Crg, Learner, Syllabary, Ipa]
+            .into_iter()
+            .find(|system| system.id().eq_ignore_ascii_case(&self.0))
+    }
+}
+
+/// A writing system listed in the [`OrthographyRegistry`].
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct RegisteredOrthography {
+    /// Identifier to pass as the `system` argument of other fields
+    pub id: String,
+    /// Human-readable name of the system
+    pub title: String,
+    /// Name of the language this system is for
+    pub language: String,
+}
+
+impl RegisteredOrthography {
+    /// Describe a system registered for the given language.
+    pub fn new(language: &str, orthography: &dyn Orthography) -> Self {
+        Self {
+            id: orthography.id().to_owned(),
+            title: orthography.title().to_owned(),
+            language: language.to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry() {
+        let registry = OrthographyRegistry::global();
+        let crg = registry.get("crg").unwrap();
+        assert_eq!(crg.render("ùùnatoótákwààskvv̋ʔi"), "uùnadoódágwaàsgv́v́ʔi");
+        assert_eq!(registry.language_of("WLRP"), Some("Massachusett"));
+        assert_eq!(registry.for_language("Massachusett").len(), 2);
+        assert!(registry.get("nonsense").is_none());
+
+        let wlrp = OrthographyId("wlrp".to_owned());
+        assert_eq!(wlrp.resolve().unwrap().0, "Massachusett");
+        assert_eq!(wlrp.cherokee(), None);
+        let learner = OrthographyId::from(CherokeeOrthography::Learner);
+        assert_eq!(learner.cherokee(), Some(CherokeeOrthography::Learner));
+        assert!(OrthographyId("nonsense".to_owned()).resolve().is_err());
+    }
+
+    #[test]
+    fn latin_orthography() {
+        assert_eq!(WOPANAAK_LIGATURE.render("m8s"), "mꝏs");
+        assert_eq!(WOPANAAK_LIGATURE.parse("MꝎS").unwrap(), "m8s");
+        assert_eq!(WOPANAAK.normalize("Wo\u{0302}panâak"), "wôpanâak");
+    }
+}
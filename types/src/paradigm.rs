@@ -0,0 +1,346 @@
+//! Arrange the attested forms of a verb into a conjugation table.
+
+use crate::AnnotatedForm;
+
+/// Grammatical person marked by a pronominal prefix.
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Person {
+    /// The speaker, like "I" or "we"
+    First,
+    /// The addressee, like "you"
+    Second,
+    /// Anyone else, like "he", "she", or "they"
+    Third,
+}
+
+/// Grammatical number marked by a pronominal prefix.
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GrammaticalNumber {
+    /// One person
+    Singular,
+    /// Two people
+    Dual,
+    /// Three or more people
+    Plural,
+}
+
+/// Whether a first person dual or plural includes the addressee.
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Clusivity {
+    /// Includes the addressee, like "you and I"
+    Inclusive,
+    /// Excludes the addressee, like "someone else and I"
+    Exclusive,
+}
+
+/// Which person and number a verb form is inflected for. Set A and set B
+/// prefixes of the same person and number share a row, since the choice
+/// between them depends on the aspect.
+#[derive(async_graphql::SimpleObject, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PersonNumber {
+    /// Grammatical person
+    pub person: Person,
+    /// Grammatical number
+    pub number: GrammaticalNumber,
+    /// Whether the addressee is included, only for first person dual and plural
+    pub clusivity: Option<Clusivity>,
+}
+
+impl PersonNumber {
+    /// Every person and number a Cherokee verb can be inflected for, in the
+    /// order of a standard conjugation table.
+    pub const ALL: [Self; 10] = [
+        Self::new(Person::First, GrammaticalNumber::Singular, None),
+        Self::new(Person::Second, GrammaticalNumber::Singular, None),
+        Self::new(Person::Third, GrammaticalNumber::Singular, None),
+        Self::new(
+            Person::First,
+            GrammaticalNumber::Dual,
+            Some(Clusivity::Inclusive),
+        ),
+        Self::new(
+            Person::First,
+            GrammaticalNumber::Dual,
+            Some(Clusivity::Exclusive),
+        ),
+        Self::new(Person::Second, GrammaticalNumber::Dual, None),
+        Self::new(
+            Person::First,
+            GrammaticalNumber::Plural,
+            Some(Clusivity::Inclusive),
+        ),
+        Self::new(
+            Person::First,
+            GrammaticalNumber::Plural,
+            Some(Clusivity::Exclusive),
+        ),
+        Self::new(Person::Second, GrammaticalNumber::Plural, None),
+        Self::new(Person::Third, GrammaticalNumber::Plural, None),
+    ];
+
+    const fn new(person: Person, number: GrammaticalNumber, clusivity: Option<Clusivity>) -> Self {
+        Self {
+            person,
+            number,
+            clusivity,
+        }
+    }
+
+    /// Read the person and number from a simple pronominal prefix gloss, like
+    /// "1SG.A", "3PL.B", or "1DU.IN.A". Prefixes with both an agent and an
+    /// object, like "1SG>2", aren't read.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let mut parts = tag.split('.');
+        let person_number = parts.next()?;
+        let person = match person_number.get(..1)? {
+            "1" => Person::First,
+            "2" => Person::Second,
+            "3" => Person::Third,
+            _ => return None,
+        };
+        let number = match person_number.get(1..)? {
+            "SG" => GrammaticalNumber::Singular,
+            "DU" => GrammaticalNumber::Dual,
+            "PL" => GrammaticalNumber::Plural,
+            _ => return None,
+        };
+        let mut rest: Vec<_> = parts.collect();
+        // The last part is the prefix set.
+        if !matches!(rest.pop(), Some("A") | Some("B")) {
+            return None;
+        }
+        let clusivity = match rest[..] {
+            [] => None,
+            ["IN"] => Some(Clusivity::Inclusive),
+            ["EX"] => Some(Clusivity::Exclusive),
+            _ => return None,
+        };
+        let result = Self::new(person, number, clusivity);
+        Self::ALL.contains(&result).then_some(result)
+    }
+}
+
+/// One of the standard verb forms, determined by the aspect and final
+/// suffixes of the verb.
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VerbAspect {
+    /// Present tense, like "he is catching it"
+    Present,
+    /// Past events the speaker witnessed, like "he caught it"
+    ExperiencedPast,
+    /// Past events the speaker only heard about
+    ReportedPast,
+    /// Habitual actions, like "he catches it regularly"
+    Habitual,
+    /// Future actions, like "he will catch it"
+    Future,
+    /// Commands, like "catch it!"
+    Immediate,
+    /// Infinitive, like "for him to catch it"
+    Infinitive,
+}
+
+impl VerbAspect {
+    /// Every standard verb form, in the order of a conjugation table.
+    pub const ALL: [Self; 7] = [
+        Self::Present,
+        Self::ExperiencedPast,
+        Self::ReportedPast,
+        Self::Habitual,
+        Self::Future,
+        Self::Immediate,
+        Self::Infinitive,
+    ];
+
+    /// Find the verb form that the given sequence of glosses makes up. Final
+    /// suffixes decide it first since they follow the aspect suffix.
+    pub fn from_glosses<'a>(glosses: impl IntoIterator<Item = &'a str> + Clone) -> Option<Self> {
+        let has = |tag: &str| glosses.clone().into_iter().any(|g| g == tag);
+        if has("EXP") {
+            Some(Self::ExperiencedPast)
+        } else if has("NXP") || has("REP") {
+            Some(Self::ReportedPast)
+        } else if has("HAB") {
+            Some(Self::Habitual)
+        } else if has("FUT") {
+            Some(Self::Future)
+        } else if has("IMM") {
+            Some(Self::Immediate)
+        } else if has("DVN") {
+            Some(Self::Infinitive)
+        } else if has("PRS") {
+            Some(Self::Present)
+        } else {
+            None
+        }
+    }
+}
+
+/// All attested forms of a verb in one person, number, and aspect.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct ParadigmCell {
+    /// Verb form of this column
+    pub aspect: VerbAspect,
+    /// Attested forms, which may come from several documents
+    pub forms: Vec<AnnotatedForm>,
+    /// True when no form is attested for this cell
+    pub is_gap: bool,
+}
+
+/// One row of a conjugation table.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct ParadigmRow {
+    /// Person and number of every form in this row
+    pub person_number: PersonNumber,
+    /// One cell for each aspect, in the order of [`VerbParadigm::aspects`]
+    pub cells: Vec<ParadigmCell>,
+}
+
+/// Conjugation table of a verb, built from the forms attested in our
+/// documents and dictionaries.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct VerbParadigm {
+    /// Gloss of the verb root
+    pub root_gloss: String,
+    /// Column headers of the table, in order
+    pub aspects: Vec<VerbAspect>,
+    /// One row for each person and number, in standard order
+    pub rows: Vec<ParadigmRow>,
+    /// Forms containing the root which don't fit in any cell, like those with
+    /// both a subject and object prefix
+    pub unclassified_forms: Vec<AnnotatedForm>,
+    /// Number of cells with no attested form
+    pub gap_count: i32,
+}
+
+impl VerbParadigm {
+    /// Arrange the given forms of a verb with the given root gloss into a
+    /// table. Segment glosses should be internal DAILP glosses.
+    pub fn build(root_gloss: String, forms: Vec<AnnotatedForm>) -> Self {
+        let mut cells: Vec<Vec<Vec<AnnotatedForm>>> =
+            vec![vec![Vec::new(); VerbAspect::ALL.len()]; PersonNumber::ALL.len()];
+        let mut unclassified_forms = Vec::new();
+        for form in forms {
+            match Self::classify(&form) {
+                Some((person_number, aspect)) => {
+                    let row = PersonNumber::ALL.iter().position(|p| *p == person_number);
+                    let col = VerbAspect::ALL.iter().position(|a| *a == aspect);
+                    if let (Some(row), Some(col)) = (row, col) {
+                        cells[row][col].push(form);
+                    }
+                }
+                None => unclassified_forms.push(form),
+            }
+        }
+
+        let rows: Vec<_> = PersonNumber::ALL
+            .into_iter()
+            .zip(cells)
+            .map(|(person_number, row)| ParadigmRow {
+                person_number,
+                cells: VerbAspect::ALL
+                    .into_iter()
+                    .zip(row)
+                    .map(|(aspect, forms)| ParadigmCell {
+                        aspect,
+                        is_gap: forms.is_empty(),
+                        forms,
+                    })
+                    .collect(),
+            })
+            .collect();
+        let gap_count = rows
+            .iter()
+            .flat_map(|row| &row.cells)
+            .filter(|cell| cell.is_gap)
+            .count() as i32;
+
+        Self {
+            root_gloss,
+            aspects: VerbAspect::ALL.to_vec(),
+            rows,
+            unclassified_forms,
+            gap_count,
+        }
+    }
+
+    /// Which cell the given form belongs in, if it has exactly one simple
+    /// pronominal prefix and a recognizable aspect.
+    fn classify(form: &AnnotatedForm) -> Option<(PersonNumber, VerbAspect)> {
+        let glosses = form.segments.as_ref()?.iter().map(|s| &*s.gloss);
+        let mut prefixes = glosses.clone().filter_map(PersonNumber::from_tag);
+        let person_number = prefixes.next()?;
+        if prefixes.next().is_some() {
+            return None;
+        }
+        Some((person_number, VerbAspect::from_glosses(glosses)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DocumentId, PositionInDocument, WordSegment};
+
+    fn form(morphemes: &str, glosses: &str) -> AnnotatedForm {
+        AnnotatedForm {
+            id: None,
+            source: morphemes.to_owned(),
+            normalized_source: None,
+            simple_phonetics: None,
+            phonemic: None,
+            segments: Some(WordSegment::parse_many(morphemes, glosses).unwrap()),
+            english_gloss: Vec::new(),
+            commentary: None,
+            line_break: None,
+            page_break: None,
+            position: PositionInDocument::new(DocumentId(Default::default()), "1".to_owned(), 1),
+            date_recorded: None,
+            ingested_audio_track: None,
+        }
+    }
+
+    #[test]
+    fn person_number_tags() {
+        assert_eq!(
+            PersonNumber::from_tag("1DU.IN.A"),
+            Some(PersonNumber::new(
+                Person::First,
+                GrammaticalNumber::Dual,
+                Some(Clusivity::Inclusive)
+            ))
+        );
+        assert_eq!(
+            PersonNumber::from_tag("3PL.B"),
+            Some(PersonNumber::new(
+                Person::Third,
+                GrammaticalNumber::Plural,
+                None
+            ))
+        );
+        // Only first person has clusivity.
+        assert_eq!(PersonNumber::from_tag("2DU.IN.A"), None);
+        assert_eq!(PersonNumber::from_tag("PRS"), None);
+    }
+
+    #[test]
+    fn build_table() {
+        let paradigm = VerbParadigm::build(
+            "catch".to_owned(),
+            vec![
+                form("ga-hnog-a", "3SG.A-catch-PRS"),
+                form("tsi-hnog-a", "1SG.A-catch-PRS"),
+                form("u-hnog-v-ʔi", "3SG.B-catch-CMP-EXP"),
+                form("hnog", "catch"),
+            ],
+        );
+        let present = &paradigm.rows[2].cells[0];
+        assert_eq!(present.aspect, VerbAspect::Present);
+        assert_eq!(present.forms[0].source, "ga-hnog-a");
+        assert!(!paradigm.rows[0].cells[0].is_gap);
+        assert!(!paradigm.rows[2].cells[1].is_gap);
+        assert!(paradigm.rows[1].cells[0].is_gap);
+        assert_eq!(paradigm.unclassified_forms.len(), 1);
+        assert_eq!(paradigm.gap_count, 70 - 3);
+    }
+}
@@ -0,0 +1,294 @@
+//! Suggest morphemic segmentations for new words based on the words and
+//! morphemes that have already been analyzed.
+
+use crate::{phonetic_search_key, WordSegmentRole};
+use itertools::Itertools;
+use sqlx::types::Uuid;
+
+/// Cost of each character in a stretch of a word that doesn't match any known
+/// morpheme, as a negative log probability. This is high enough that any
+/// attested morpheme is preferred, but still lets us suggest a partial
+/// analysis when part of a word is new to us.
+const UNKNOWN_CHAR_COST: f64 = 12.0;
+
+/// An existing analysis of a word: its ID along with the morpheme, gloss,
+/// and role of each segment.
+pub type AnalyzedWord = (Uuid, Vec<(String, String, WordSegmentRole)>);
+
+/// One shape that a morpheme with a particular gloss takes in analyzed words.
+#[derive(Clone, Debug)]
+pub struct MorphemeShape {
+    /// Loose phonetic key of the shape, see [`phonetic_search_key`]
+    pub key: String,
+    /// Most common spelling of the shape in DAILP internal orthography
+    pub morpheme: String,
+    /// Gloss of the morpheme
+    pub gloss: String,
+    /// Most common role of the morpheme
+    pub role: WordSegmentRole,
+    /// Number of segments with this shape and gloss
+    pub attestations: i64,
+    /// A few words containing this shape and gloss
+    pub example_word_ids: Vec<Uuid>,
+}
+
+/// Where a suggested segmentation came from.
+#[derive(async_graphql::Enum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SuggestionKind {
+    /// Copied from words that sound or look the same as the given one
+    SameWord,
+    /// Built up from morpheme shapes found in other words
+    KnownMorphemes,
+}
+
+/// One segment of a suggested segmentation, along with the analyzed data
+/// that supports it.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct SuggestedSegment {
+    /// Source language representation of this segment
+    pub morpheme: String,
+    /// Suggested gloss, or "?" if no known morpheme fits
+    pub gloss: String,
+    /// Which separator should precede this segment
+    pub role: WordSegmentRole,
+    /// Number of analyzed segments with this shape and gloss
+    pub attestations: i64,
+    /// A few analyzed words containing this shape and gloss
+    pub example_word_ids: Vec<Uuid>,
+}
+
+/// A possible morphemic segmentation for a word that hasn't been analyzed.
+#[derive(async_graphql::SimpleObject, Clone, Debug)]
+pub struct SegmentationSuggestion {
+    /// Where this suggestion came from
+    pub kind: SuggestionKind,
+    /// Proposed segments in order
+    pub segments: Vec<SuggestedSegment>,
+    /// How likely this suggestion is compared to others of the same kind,
+    /// from 0 to 1. For the same word, this is the share of matching words
+    /// analyzed this way. Otherwise, it depends on how common each morpheme
+    /// is.
+    pub score: f64,
+    /// Analyzed words that have exactly this segmentation, for suggestions
+    /// copied from the same word
+    pub matching_word_ids: Vec<Uuid>,
+}
+
+impl SegmentationSuggestion {
+    /// Suggest segmentations for a word with the given search key. Existing
+    /// analyses of matching words, given as (word, segments) pairs, come first.
+    /// Then come up to `limit` combinations of the given morpheme shapes.
+    pub fn rank(
+        key: &str,
+        matching_words: Vec<AnalyzedWord>,
+        shapes: &[MorphemeShape],
+        limit: usize,
+    ) -> Vec<Self> {
+        let evidence = |morpheme: &str, gloss: &str| {
+            let key = phonetic_search_key(morpheme);
+            shapes
+                .iter()
+                .find(|shape| shape.key == key && shape.gloss == gloss)
+        };
+
+        let total_matches = matching_words.len();
+        let same_word = matching_words
+            .into_iter()
+            .into_group_map_by(|(_, segments)| segments.clone())
+            .into_iter()
+            .map(|(segments, words)| Self {
+                kind: SuggestionKind::SameWord,
+                score: words.len() as f64 / total_matches as f64,
+                matching_word_ids: words.into_iter().map(|(id, _)| id).collect(),
+                segments: segments
+                    .into_iter()
+                    .map(|(morpheme, gloss, role)| {
+                        let shape = evidence(&morpheme, &gloss);
+                        SuggestedSegment {
+                            attestations: shape.map(|s| s.attestations).unwrap_or_default(),
+                            example_word_ids: shape
+                                .map(|s| s.example_word_ids.clone())
+                                .unwrap_or_default(),
+                            morpheme,
+                            gloss,
+                            role,
+                        }
+                    })
+                    .collect(),
+            })
+            .sorted_by(|a, b| b.score.total_cmp(&a.score));
+
+        let composed = compose(key, shapes, limit);
+        // Turn costs into likelihoods relative to the best combination.
+        let best_cost = composed.first().map(|(cost, _)| *cost).unwrap_or_default();
+        let total: f64 = composed
+            .iter()
+            .map(|(cost, _)| (best_cost - cost).exp())
+            .sum();
+        let known_morphemes = composed.into_iter().map(|(cost, pieces)| Self {
+            kind: SuggestionKind::KnownMorphemes,
+            score: (best_cost - cost).exp() / total,
+            matching_word_ids: Vec::new(),
+            segments: pieces
+                .into_iter()
+                .map(|piece| match piece {
+                    Piece::Known(index) => {
+                        let shape = &shapes[index];
+                        SuggestedSegment {
+                            morpheme: shape.morpheme.clone(),
+                            gloss: shape.gloss.clone(),
+                            role: shape.role,
+                            attestations: shape.attestations,
+                            example_word_ids: shape.example_word_ids.clone(),
+                        }
+                    }
+                    Piece::Unknown(text) => SuggestedSegment {
+                        morpheme: text,
+                        gloss: "?".to_owned(),
+                        role: WordSegmentRole::Morpheme,
+                        attestations: 0,
+                        example_word_ids: Vec::new(),
+                    },
+                })
+                .collect(),
+        });
+
+        // Skip combinations that were already suggested from the same word.
+        let mut seen = Vec::new();
+        same_word
+            .chain(known_morphemes)
+            .filter(|suggestion| {
+                let shape: Vec<_> = suggestion
+                    .segments
+                    .iter()
+                    .map(|s| (phonetic_search_key(&s.morpheme), s.gloss.clone()))
+                    .collect();
+                if seen.contains(&shape) {
+                    false
+                } else {
+                    seen.push(shape);
+                    true
+                }
+            })
+            .collect()
+    }
+}
+
+/// Part of a word in a combination of morpheme shapes.
+#[derive(Clone, Debug, PartialEq)]
+enum Piece {
+    /// Index of a known morpheme shape
+    Known(usize),
+    /// Stretch of the word that doesn't match any known shape
+    Unknown(String),
+}
+
+/// Find the `limit` most likely ways to split the given key into known
+/// morpheme shapes, treating the word as a sequence of independent morphemes.
+/// Each result comes with its cost, the negative log of its probability.
+fn compose(key: &str, shapes: &[MorphemeShape], limit: usize) -> Vec<(f64, Vec<Piece>)> {
+    let chars: Vec<char> = key.chars().collect();
+    let shape_chars: Vec<Vec<char>> = shapes.iter().map(|s| s.key.chars().collect()).collect();
+    let total: i64 = shapes.iter().map(|s| s.attestations).sum();
+
+    // Best partial combinations covering the first `i` characters of the key.
+    let mut best: Vec<Vec<(f64, Vec<Piece>)>> = vec![Vec::new(); chars.len() + 1];
+    best[0].push((0.0, Vec::new()));
+    for start in 0..chars.len() {
+        let mut partials = std::mem::take(&mut best[start]);
+        partials.sort_by(|a, b| a.0.total_cmp(&b.0));
+        partials.truncate(limit);
+        for (cost, pieces) in &partials {
+            for (index, shape) in shape_chars.iter().enumerate() {
+                if !shape.is_empty() && chars[start..].starts_with(shape) {
+                    let p = shapes[index].attestations as f64 / total as f64;
+                    let mut pieces = pieces.clone();
+                    pieces.push(Piece::Known(index));
+                    best[start + shape.len()].push((cost - p.ln(), pieces));
+                }
+            }
+            // Two unknown stretches in a row are really one.
+            if !matches!(pieces.last(), Some(Piece::Unknown(_))) {
+                for end in start + 1..=chars.len() {
+                    let mut pieces = pieces.clone();
+                    pieces.push(Piece::Unknown(chars[start..end].iter().collect()));
+                    let cost = cost + UNKNOWN_CHAR_COST * (end - start) as f64;
+                    best[end].push((cost, pieces));
+                }
+            }
+        }
+        best[start] = partials;
+    }
+
+    let mut complete = std::mem::take(&mut best[chars.len()]);
+    complete.sort_by(|a, b| a.0.total_cmp(&b.0));
+    complete.truncate(limit);
+    complete
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape(key: &str, gloss: &str, attestations: i64) -> MorphemeShape {
+        MorphemeShape {
+            key: key.to_owned(),
+            morpheme: key.to_owned(),
+            gloss: gloss.to_owned(),
+            role: WordSegmentRole::Morpheme,
+            attestations,
+            example_word_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn compose_known_morphemes() {
+        let shapes = [
+            shape("ka", "3SG.A", 10),
+            shape("kanok", "sing", 1),
+            shape("nok", "sing", 5),
+            shape("a", "PRS", 8),
+        ];
+        let suggestions = SegmentationSuggestion::rank("kanoka", Vec::new(), &shapes, 3);
+        let glosses: Vec<Vec<_>> = suggestions
+            .iter()
+            .map(|s| s.segments.iter().map(|seg| &*seg.gloss).collect())
+            .collect();
+        assert_eq!(glosses[0], ["3SG.A", "sing", "PRS"]);
+        assert_eq!(glosses[1], ["sing", "PRS"]);
+        assert!(suggestions[0].score > suggestions[1].score);
+
+        // Parts of a word we know nothing about are left unglossed.
+        let suggestions = SegmentationSuggestion::rank("kaxyz", Vec::new(), &shapes, 1);
+        assert_eq!(suggestions[0].segments[1].morpheme, "xyz");
+        assert_eq!(suggestions[0].segments[1].gloss, "?");
+    }
+
+    #[test]
+    fn same_word_first() {
+        let shapes = [shape("ka", "3SG.A", 10), shape("nok", "sing", 5)];
+        let analysis = vec![
+            (
+                "ka".to_owned(),
+                "3SG.A".to_owned(),
+                WordSegmentRole::Morpheme,
+            ),
+            (
+                "nok".to_owned(),
+                "sing".to_owned(),
+                WordSegmentRole::Morpheme,
+            ),
+        ];
+        let word = Uuid::nil();
+        let suggestions = SegmentationSuggestion::rank("kanok", vec![(word, analysis)], &shapes, 3);
+        assert_eq!(suggestions[0].kind, SuggestionKind::SameWord);
+        assert_eq!(suggestions[0].matching_word_ids, [word]);
+        assert_eq!(suggestions[0].segments[1].attestations, 5);
+        // The same combination isn't suggested twice.
+        assert!(suggestions[1..].iter().all(|s| s
+            .segments
+            .iter()
+            .map(|seg| &*seg.gloss)
+            .ne(["3SG.A", "sing"])));
+    }
+}
@@ -31,9 +31,6 @@ export const FormProvider = (props: { children: ReactNode }) => {
   const { confirmRomanizedSourceDelete, setConfirmRomanizedSourceDelete } =
     useEditWordCheckContext()
 
-  const settingsAlert =
-    "Currently, only the linguistic analysis using terms from Tone and Accent in Oklahoma Cherokee (TAOC) is supported for editing. Please update your Cherokee description style in the display settings."
-
   /** Calls the backend GraphQL mutation to update a word. */
   const runUpdate = async (variables: {
     word: Dailp.AnnotatedFormUpdate
@@ -80,43 +77,40 @@ export const FormProvider = (props: { children: ReactNode }) => {
         }
       }
 
-      if (cherokeeRepresentation === Dailp.CherokeeOrthography.Taoc) {
-        setIsEditing(false)
-
-        // Create a completely new word state
-        const wordUpdate: Dailp.AnnotatedFormUpdate = {
-          id: values.word["id"],
-          source: values.word["source"],
-          commentary: values.word["commentary"],
-          segments: values.word["segments"].map((segment) => ({
-            system: cherokeeRepresentation,
-            morpheme: segment.morpheme,
-            gloss: segment.gloss,
-            role: segment.role,
-          })),
-          romanizedSource: values.word["romanizedSource"],
-          englishGloss: Array.isArray(values.word["englishGloss"])
-            ? values.word["englishGloss"].join(" ")
-            : String(values.word["englishGloss"]),
-        }
-
-        console.log("Sending complete word update:", wordUpdate)
-
-        runUpdate({
-          word: wordUpdate,
-          morphemeSystem: cherokeeRepresentation,
-        }).then(({ data, error }) => {
-          if (error) {
-            console.log("Update error:", error)
-          } else if (data) {
-            console.log("Server response:", data.updateWord)
-            form.update("word", data.updateWord)
-          }
-          setConfirmRomanizedSourceDelete(false)
-        })
-      } else {
-        alert(settingsAlert)
+      setIsEditing(false)
+
+      // Create a completely new word state
+      const wordUpdate: Dailp.AnnotatedFormUpdate = {
+        id: values.word["id"],
+        source: values.word["source"],
+        commentary: values.word["commentary"],
+        segments: values.word["segments"].map((segment) => ({
+          system: cherokeeRepresentation,
+          morpheme: segment.morpheme,
+          gloss: segment.gloss,
+          role: segment.role,
+        })),
+        romanizedSource: values.word["romanizedSource"],
+        system: cherokeeRepresentation,
+        englishGloss: Array.isArray(values.word["englishGloss"])
+          ? values.word["englishGloss"].join(" ")
+          : String(values.word["englishGloss"]),
       }
+
+      console.log("Sending complete word update:", wordUpdate)
+
+      runUpdate({
+        word: wordUpdate,
+        morphemeSystem: cherokeeRepresentation,
+      }).then(({ data, error }) => {
+        if (error) {
+          console.log("Update error:", error)
+        } else if (data) {
+          console.log("Server response:", data.updateWord)
+          form.update("word", data.updateWord)
+        }
+        setConfirmRomanizedSourceDelete(false)
+      })
     },
   })
 
@@ -22,6 +22,7 @@ export type Scalars = {
   Float: number
   /** A scalar that can represent any JSON value. */
   JSON: any
+  OrthographyId: any
   /**
    * A UUID is a unique 128-bit number, stored as 16 octets. UUIDs are parsed as
    * Strings within GraphQL. UUIDs are used to assign unique identifiers to
@@ -62,6 +63,47 @@ export type AddDocumentPayload = {
   readonly title: Scalars["String"]
 }
 
+/** How to spell the affricate /c/. */
+export enum AffricateSpelling {
+  J = "J",
+  Ts = "TS",
+}
+
+/**
+ * Shapes of a morpheme that sound alike, which are likely allomorphs of each
+ * other.
+ */
+export type AllomorphCluster = {
+  readonly __typename?: "AllomorphCluster"
+  /**
+   * Most common morphemes right after this one, with `gloss` missing for
+   * the end of a word
+   */
+  readonly following: ReadonlyArray<MorphemeContext>
+  /**
+   * Most common morphemes right before this one, with `gloss` missing for
+   * the start of a word
+   */
+  readonly preceding: ReadonlyArray<MorphemeContext>
+  /** Most common shape in this cluster */
+  readonly representative: Scalars["String"]
+  /** Number of occurrences of all shapes in this cluster */
+  readonly totalCount: Scalars["Int"]
+  /** Every shape in this cluster, most common first */
+  readonly variants: ReadonlyArray<AllomorphVariant>
+}
+
+/** One distinct shape of a morpheme. */
+export type AllomorphVariant = {
+  readonly __typename?: "AllomorphVariant"
+  /** Number of occurrences of this shape */
+  readonly count: Scalars["Int"]
+  /** A few of the words containing this shape */
+  readonly exampleWordIds: ReadonlyArray<Scalars["UUID"]>
+  /** Shape of the morpheme, written in the orthography shapes were compared in */
+  readonly shape: Scalars["String"]
+}
+
 export type AnnotatedDoc = {
   readonly __typename?: "AnnotatedDoc"
   /** When the document was bookmarked by the current user, if it was. */
@@ -99,6 +141,11 @@ export type AnnotatedDoc = {
   readonly id: Scalars["UUID"]
   /** The audio for this document that was ingested from GoogleSheets, if there is any. */
   readonly ingestedAudioTrack: Maybe<AudioSlice>
+  /**
+   * The whole document as interlinear glossed text, one example per
+   * paragraph, with morphemes and tags written in the given system.
+   */
+  readonly interlinearText: Scalars["String"]
   /**
    * Is this document a reference source (unstructured list of words)?
    * Otherwise, it is considered a structured document with a translation.
@@ -145,6 +192,11 @@ export type AnnotatedDocFormsArgs = {
   start: InputMaybe<Scalars["Int"]>
 }
 
+export type AnnotatedDocInterlinearTextArgs = {
+  format?: IgtFormat
+  system?: Scalars["OrthographyId"]
+}
+
 /**
  * A single word in an annotated document.
  * One word contains several layers of interpretation, including the original
@@ -187,6 +239,13 @@ export type AnnotatedForm = {
   readonly phonemic: Maybe<Scalars["String"]>
   /** Position of the form within the context of its parent document */
   readonly position: PositionInDocument
+  /**
+   * The phonetic layer of this word written with the given system, which
+   * may be any registered orthography for the language of this word's
+   * document. Passing `spelling` writes the phonemic layer with the
+   * spelling choices of a romanized Cherokee system, changed by those
+   * options.
+   */
   readonly romanizedSource: Maybe<Scalars["String"]>
   /**
    * The root morpheme of the word.
@@ -194,11 +253,24 @@ export type AnnotatedForm = {
    * corresponding to "catch."
    */
   readonly root: Maybe<WordSegment>
+  /**
+   * The morphemic segmentation of this word written with the given system,
+   * which may be any registered orthography for the language of this
+   * word's document. Cherokee systems also choose which tags to show.
+   * Passing `spelling` writes each morpheme with the spelling choices of a
+   * romanized Cherokee system, changed by those options.
+   */
   readonly segments: ReadonlyArray<WordSegment>
   /** All other observed words with the same root morpheme as this word. */
   readonly similarForms: ReadonlyArray<AnnotatedForm>
   /** Original source text */
   readonly source: Scalars["String"]
+  /**
+   * Each syllable of the phonemic layer lined up with the source syllabary
+   * character that should spell it. Mismatches may point to transcription
+   * slips in either layer.
+   */
+  readonly syllables: ReadonlyArray<SyllableAlignment>
   /**
    * Audio for this word that has been recorded by community members. Will be
    * empty if user does not have access to uncurated contributions.
@@ -214,7 +286,8 @@ export type AnnotatedForm = {
  * TODO Split into two types, one for migration and one for SQL + GraphQL
  */
 export type AnnotatedFormRomanizedSourceArgs = {
-  system: CherokeeOrthography
+  spelling: InputMaybe<SpellingOptions>
+  system?: Scalars["OrthographyId"]
 }
 
 /**
@@ -224,7 +297,8 @@ export type AnnotatedFormRomanizedSourceArgs = {
  * TODO Split into two types, one for migration and one for SQL + GraphQL
  */
 export type AnnotatedFormSegmentsArgs = {
-  system: CherokeeOrthography
+  spelling: InputMaybe<SpellingOptions>
+  system?: Scalars["OrthographyId"]
 }
 
 /**
@@ -244,6 +318,11 @@ export type AnnotatedFormUpdate = {
   readonly segments: InputMaybe<ReadonlyArray<MorphemeSegmentUpdate>>
   /** Possible update to source content */
   readonly source: InputMaybe<Scalars["String"]>
+  /**
+   * Which Cherokee representation system the romanized source is written
+   * with, TAOC if not given
+   */
+  readonly system: InputMaybe<CherokeeOrthography>
 }
 
 /** Element within a spreadsheet before being transformed into a full document. */
@@ -338,10 +417,18 @@ export type ChapterSlugInfo = {
  */
 export enum CherokeeOrthography {
   Crg = "CRG",
+  Ipa = "IPA",
   Learner = "LEARNER",
+  Syllabary = "SYLLABARY",
   Taoc = "TAOC",
 }
 
+/** Whether a first person dual or plural includes the addressee. */
+export enum Clusivity {
+  Exclusive = "EXCLUSIVE",
+  Inclusive = "INCLUSIVE",
+}
+
 /** Structure to represent a single chapter. Used to send data to the front end. */
 export type CollectionChapter = {
   readonly __typename?: "CollectionChapter"
@@ -418,6 +505,44 @@ export type CommentUpdate = {
   readonly textContent: InputMaybe<Scalars["String"]>
 }
 
+/** One use of a word, along with the words around it in its paragraph. */
+export type ConcordanceLine = {
+  readonly __typename?: "ConcordanceLine"
+  /** Document that the match is in */
+  readonly document: DocumentReference
+  /** Words right before the match in its paragraph, in reading order */
+  readonly left: ReadonlyArray<AnnotatedForm>
+  /** Words right after the match in its paragraph, in reading order */
+  readonly right: ReadonlyArray<AnnotatedForm>
+  /** The matching word */
+  readonly word: AnnotatedForm
+}
+
+/** What a concordance search compares against each word. */
+export enum ConcordanceMatch {
+  Gloss = "GLOSS",
+  Surface = "SURFACE",
+  Syllabary = "SYLLABARY",
+}
+
+/** Which words to list in a concordance. */
+export type ConcordanceQuery = {
+  /** Only list words in this document */
+  readonly documentId: InputMaybe<Scalars["UUID"]>
+  /** What to compare the query against in each word */
+  readonly matchBy: ConcordanceMatch
+  /** Gloss, romanized form, or piece of syllabary to look for */
+  readonly query: Scalars["String"]
+}
+
+/** How to order the lines of a concordance. */
+export enum ConcordanceSort {
+  Date = "DATE",
+  Document = "DOCUMENT",
+  LeftContext = "LEFT_CONTEXT",
+  RightContext = "RIGHT_CONTEXT",
+}
+
 /**
  * A block of content, which may be one of several types.
  * Each page contains several blocks.
@@ -588,6 +713,12 @@ export type DeleteContributorAttribution = {
   readonly documentId: Scalars["UUID"]
 }
 
+/** How to group the words containing a morpheme. */
+export enum DistributionGrouping {
+  Decade = "DECADE",
+  Document = "DOCUMENT",
+}
+
 export type DocumentCollection = {
   readonly __typename?: "DocumentCollection"
   /**
@@ -653,12 +784,23 @@ export type DocumentParagraph = {
   readonly id: Scalars["UUID"]
   /** 1-indexed position of this paragraph in a document */
   readonly index: Scalars["Int"]
+  /**
+   * This paragraph as interlinear glossed text, with morphemes and tags
+   * written in the given system.
+   */
+  readonly interlinearText: Scalars["String"]
   /** Source text of the paragraph broken down into words */
   readonly source: ReadonlyArray<AnnotatedSeg>
   /** English translation of the whole paragraph */
   readonly translation: Scalars["String"]
 }
 
+/** One paragraph within a [`DocumentPage`] */
+export type DocumentParagraphInterlinearTextArgs = {
+  format?: IgtFormat
+  system?: Scalars["OrthographyId"]
+}
+
 /**
  * Reference to a document with a limited subset of fields, namely no contents
  * of the document.
@@ -711,6 +853,85 @@ export type EditedCollection = {
   readonly wordpressMenuId: Maybe<Scalars["Int"]>
 }
 
+/** A Cherokee form whose English translation matches a search. */
+export type EnglishMatch = {
+  readonly __typename?: "EnglishMatch"
+  /**
+   * Number of words in the corpus that attest this form. Dictionary
+   * entries count words in other documents that use their morpheme or one
+   * linked to it.
+   */
+  readonly attestationCount: Scalars["Int"]
+  /**
+   * The Cherokee form, like a dictionary headword, the most common shape of
+   * a morpheme, a word as written in its document, or a whole paragraph
+   */
+  readonly cherokee: Scalars["String"]
+  /** Documents where this form appears with this translation */
+  readonly documents: ReadonlyArray<AnnotatedDoc>
+  /** The English translation that matched */
+  readonly english: Scalars["String"]
+  /** Identifier of the morpheme for entries and glosses, like "DF1975:catch" */
+  readonly morphemeId: Maybe<Scalars["String"]>
+  /** Romanized spelling of the form, if known */
+  readonly romanized: Maybe<Scalars["String"]>
+  /** How closely the English matches the search, higher being closer */
+  readonly score: Scalars["Float"]
+  /** Where the matching English comes from */
+  readonly source: EnglishMatchSource
+}
+
+/**
+ * Where the English that matched a Cherokee form comes from. Matches are
+ * ranked in the order listed here.
+ */
+export enum EnglishMatchSource {
+  DictionaryEntry = "DICTIONARY_ENTRY",
+  MorphemeGloss = "MORPHEME_GLOSS",
+  Paragraph = "PARAGRAPH",
+  Word = "WORD",
+}
+
+/** A directed link between two morpheme glosses. */
+export type EtymologyEdge = {
+  readonly __typename?: "EtymologyEdge"
+  /** Gloss this link starts from */
+  readonly from: Scalars["UUID"]
+  /** How the two glosses are related */
+  readonly kind: MorphemeRelationKind
+  /** Gloss this link points to */
+  readonly to: Scalars["UUID"]
+}
+
+/** The glosses linked to a morpheme and the links between them. */
+export type EtymologyGraph = {
+  readonly __typename?: "EtymologyGraph"
+  /** Every link between two glosses in the graph */
+  readonly edges: ReadonlyArray<EtymologyEdge>
+  /** Every gloss in the graph, oldest document first */
+  readonly nodes: ReadonlyArray<EtymologyNode>
+}
+
+/** One morpheme gloss in an etymology graph. */
+export type EtymologyNode = {
+  readonly __typename?: "EtymologyNode"
+  /** Date of the document defining this gloss, if known */
+  readonly date: Maybe<Date>
+  /**
+   * Short name of the document defining this gloss, missing for glosses
+   * shared by all documents
+   */
+  readonly documentName: Maybe<Scalars["String"]>
+  /** The gloss itself, like "catch" */
+  readonly gloss: Scalars["String"]
+  /** Database ID of the morpheme gloss, which edges refer to */
+  readonly id: Scalars["UUID"]
+  /** Identifier of the gloss, like "DF1975:catch" */
+  readonly morphemeId: Scalars["String"]
+  /** Example shape of the morpheme in DAILP internal orthography */
+  readonly shape: Maybe<Scalars["String"]>
+}
+
 /** Stores the physical or digital medium associated with a document */
 export type Format = {
   readonly __typename?: "Format"
@@ -785,6 +1006,62 @@ export type Geometry = {
   readonly yMin: Scalars["Float"]
 }
 
+/** A problem with a morphemic segmentation or its glosses. */
+export type GlossWarning = {
+  readonly __typename?: "GlossWarning"
+  /** The gloss with the problem, if it's limited to one */
+  readonly gloss: Maybe<Scalars["String"]>
+  /** What kind of problem this is */
+  readonly kind: GlossWarningKind
+  /** Description of the problem that can be shown to an editor */
+  readonly message: Scalars["String"]
+  /** Index of the segment with the problem, if it's limited to one */
+  readonly segmentIndex: Maybe<Scalars["Int"]>
+  /** Known tags that are spelled like the given gloss, closest first */
+  readonly suggestions: ReadonlyArray<Scalars["String"]>
+}
+
+/**
+ * Kind of problem found in a morpheme gloss that doesn't stop it from being
+ * saved as is.
+ */
+export enum GlossWarningKind {
+  CountMismatch = "COUNT_MISMATCH",
+  MalformedSegmentation = "MALFORMED_SEGMENTATION",
+  MalformedTag = "MALFORMED_TAG",
+  UnknownTag = "UNKNOWN_TAG",
+}
+
+/** How to write the glottal stop. */
+export enum GlottalStopSpelling {
+  Apostrophe = "APOSTROPHE",
+  Letter = "LETTER",
+}
+
+/** Grammatical number marked by a pronominal prefix. */
+export enum GrammaticalNumber {
+  Dual = "DUAL",
+  Plural = "PLURAL",
+  Singular = "SINGULAR",
+}
+
+/** A run of text that either matched a search or didn't. */
+export type HighlightFragment = {
+  readonly __typename?: "HighlightFragment"
+  /** Whether this run matched the search */
+  readonly matched: Scalars["Boolean"]
+  /** Text of this run, exactly as written in the field */
+  readonly text: Scalars["String"]
+}
+
+/** Markup to write interlinear glossed text in. */
+export enum IgtFormat {
+  Expex = "EXPEX",
+  Gb_4E = "GB_4E",
+  Html = "HTML",
+  PlainText = "PLAIN_TEXT",
+}
+
 export type IiifImages = {
   readonly __typename?: "IiifImages"
   /** Information about the data source for this set of images */
@@ -817,6 +1094,12 @@ export type KeywordUpdate = {
   readonly name: Scalars["String"]
 }
 
+/** How to spell the labiovelar stops /kw/ and /kwh/. */
+export enum LabiovelarSpelling {
+  Kw = "KW",
+  Qu = "QU",
+}
+
 /** Stores a language associated with a document */
 export type Language = {
   readonly __typename?: "Language"
@@ -824,6 +1107,8 @@ export type Language = {
   readonly id: Scalars["UUID"]
   /** Name of the language */
   readonly name: Scalars["String"]
+  /** Writing systems registered for this language */
+  readonly orthographies: ReadonlyArray<RegisteredOrthography>
   /** Status (pending, approved, rejected) of a language */
   readonly status: ApprovalStatus
 }
@@ -836,6 +1121,48 @@ export type LanguageUpdate = {
   readonly name: Scalars["String"]
 }
 
+/** One headword of a dictionary, describing a morpheme defined there. */
+export type LexicalEntry = {
+  readonly __typename?: "LexicalEntry"
+  /**
+   * Words from other documents that contain this morpheme or one linked to
+   * it, like the uses of a verb in a letter
+   */
+  readonly attestations: ReadonlyArray<AnnotatedForm>
+  /** How to cite this entry, like "Cherokee–English Dictionary (DF1975), p. 42" */
+  readonly citation: Scalars["String"]
+  /** Entries in other dictionaries that describe a related morpheme */
+  readonly crossReferences: ReadonlyArray<LexicalEntry>
+  /** Gloss identifying this entry within its dictionary, like "catch" */
+  readonly gloss: Scalars["String"]
+  /** Headword as written in the dictionary */
+  readonly headword: Scalars["String"]
+  /** Unique identifier of this entry */
+  readonly id: Scalars["UUID"]
+  /**
+   * Identifier of the morpheme this entry describes, like "DF1975:catch",
+   * which other queries take as a gloss.
+   */
+  readonly morphemeId: Scalars["String"]
+  /** Page of the dictionary this entry is on */
+  readonly pageNumber: Maybe<Scalars["String"]>
+  /** Part of speech the headword is listed under, if known */
+  readonly partOfSpeech: Maybe<PartOfSpeech>
+  /** English translations, in the order the dictionary gives them */
+  readonly senses: ReadonlyArray<Scalars["String"]>
+  /** Shape of the morpheme, written in the given registered orthography */
+  readonly shape: Maybe<Scalars["String"]>
+  /** Dictionary that this entry comes from */
+  readonly source: DocumentReference
+  /** Inflected forms that the dictionary lists under this entry */
+  readonly surfaceForms: ReadonlyArray<AnnotatedForm>
+}
+
+/** One headword of a dictionary, describing a morpheme defined there. */
+export type LexicalEntryShapeArgs = {
+  system?: Scalars["OrthographyId"]
+}
+
 /** Start of a new line */
 export type LineBreak = {
   readonly __typename?: "LineBreak"
@@ -896,6 +1223,65 @@ export type MenuUpdate = {
   readonly name: InputMaybe<Scalars["String"]>
 }
 
+/** How often a neighboring morpheme appears next to the variants of a cluster. */
+export type MorphemeContext = {
+  readonly __typename?: "MorphemeContext"
+  /** Number of occurrences next to this morpheme */
+  readonly count: Scalars["Int"]
+  /** Gloss of the neighboring morpheme, missing at the edge of a word */
+  readonly gloss: Maybe<Scalars["String"]>
+}
+
+/** How often another morpheme appears in the same words as a given one. */
+export type MorphemeCooccurrence = {
+  readonly __typename?: "MorphemeCooccurrence"
+  /** A few of the words containing both morphemes */
+  readonly exampleWordIds: ReadonlyArray<Scalars["UUID"]>
+  /** Gloss of the other morpheme */
+  readonly gloss: Scalars["String"]
+  /** Number of words containing both morphemes */
+  readonly wordCount: Scalars["Int"]
+}
+
+/** How often a morpheme appears in one document or decade. */
+export type MorphemeDistributionBucket = {
+  readonly __typename?: "MorphemeDistributionBucket"
+  /**
+   * First year of the decade, when grouping by decade. Missing for
+   * documents without a date.
+   */
+  readonly decade: Maybe<Scalars["Int"]>
+  /** Document of this group, when grouping by document */
+  readonly documentId: Maybe<Scalars["UUID"]>
+  /** Short name of the document, when grouping by document */
+  readonly documentName: Maybe<Scalars["String"]>
+  /** A few of the words in this group containing the morpheme */
+  readonly exampleWordIds: ReadonlyArray<Scalars["UUID"]>
+  /** Share of the words in this group that contain the morpheme */
+  readonly relativeFrequency: Scalars["Float"]
+  /** Number of words in this group overall */
+  readonly totalWords: Scalars["Int"]
+  /** Number of words in this group containing the morpheme */
+  readonly wordCount: Scalars["Int"]
+}
+
+/** A functional morpheme that never appears in the same word as a given one. */
+export type MorphemeExclusion = {
+  readonly __typename?: "MorphemeExclusion"
+  /** A few of the words containing the other morpheme */
+  readonly exampleWordIds: ReadonlyArray<Scalars["UUID"]>
+  /**
+   * How many words would contain both if the two morphemes appeared
+   * independently of each other. The higher this is, the less likely it is
+   * that they never appear together by chance.
+   */
+  readonly expectedCooccurrences: Scalars["Float"]
+  /** Gloss of the other morpheme */
+  readonly gloss: Scalars["String"]
+  /** Number of words containing the other morpheme */
+  readonly wordCount: Scalars["Int"]
+}
+
 /** One particular morpheme and all the known words that contain that exact morpheme. */
 export type MorphemeReference = {
   readonly __typename?: "MorphemeReference"
@@ -905,8 +1291,25 @@ export type MorphemeReference = {
   readonly morpheme: Scalars["String"]
 }
 
+/**
+ * How one morpheme gloss came from or led to another. Each link points from
+ * one gloss to another, as described by each variant.
+ */
+export enum MorphemeRelationKind {
+  Merger = "MERGER",
+  OlderEntry = "OLDER_ENTRY",
+  PhraseToWord = "PHRASE_TO_WORD",
+  Related = "RELATED",
+  WordToEntry = "WORD_TO_ENTRY",
+}
+
 /** A single unit of meaning and its gloss which can be edited. */
 export type MorphemeSegmentUpdate = {
+  /**
+   * Whether this segment is a root, affix, or clitic. If not given, this is
+   * decided from the tag inventory.
+   */
+  readonly class: InputMaybe<WordSegmentClass>
   /** Target language representation of this segment. */
   readonly gloss: Scalars["String"]
   /** Source language representation of this segment. */
@@ -920,6 +1323,13 @@ export type MorphemeSegmentUpdate = {
   readonly system: InputMaybe<CherokeeOrthography>
 }
 
+/** Which side of a morpheme to look at for others appearing with it. */
+export enum MorphemeSide {
+  After = "AFTER",
+  Before = "BEFORE",
+  Either = "EITHER",
+}
+
 /** A concrete representation of a particular functional morpheme. */
 export type MorphemeTag = {
   readonly __typename?: "MorphemeTag"
@@ -959,6 +1369,11 @@ export type Mutation = {
   readonly addCollectionChapter: Scalars["UUID"]
   /** Minimal mutation to add a document with only essential fields */
   readonly addDocument: AddDocumentPayload
+  /**
+   * Link one morpheme gloss to another, like "WJ46:catch" to
+   * "DF1975:catch". Replaces the kind of any existing link between them.
+   */
+  readonly addMorphemeRelation: Scalars["Boolean"]
   /**
    * Mutation must have at least one visible field for introspection to work
    * correctly, so we just provide an API version which might be useful in
@@ -989,6 +1404,10 @@ export type Mutation = {
   readonly deleteComment: CommentParent
   /** Mutation for deleting contributor attributions */
   readonly deleteContributorAttribution: Scalars["UUID"]
+  /**
+   * Add a custom tag to the language of the given document, or to Cherokee
+   * without one.
+   */
   readonly insertCustomMorphemeTag: Scalars["Boolean"]
   /** Post a new comment on a given object */
   readonly postComment: CommentParent
@@ -996,6 +1415,11 @@ export type Mutation = {
   readonly removeBookmark: AnnotatedDoc
   /** Removes the provided chapter id from a TOC by setting its index to -1 */
   readonly removeCollectionChapter: Scalars["UUID"]
+  /**
+   * Remove the link from one morpheme gloss to another. Returns whether
+   * there was a link to remove.
+   */
+  readonly removeMorphemeRelation: Scalars["Boolean"]
   /** Inverts associated collection's visiblity */
   readonly toggleCollectionVisibility: EditedCollection
   readonly updateAnnotation: Scalars["Boolean"]
@@ -1030,6 +1454,12 @@ export type MutationAddDocumentArgs = {
   input: CreateDocumentFromFormInput
 }
 
+export type MutationAddMorphemeRelationArgs = {
+  from: Scalars["String"]
+  kind?: MorphemeRelationKind
+  to: Scalars["String"]
+}
+
 export type MutationAttachAudioToDocumentArgs = {
   input: AttachAudioToDocumentInput
 }
@@ -1064,6 +1494,7 @@ export type MutationDeleteContributorAttributionArgs = {
 }
 
 export type MutationInsertCustomMorphemeTagArgs = {
+  documentId: InputMaybe<Scalars["UUID"]>
   system: Scalars["String"]
   tag: Scalars["String"]
   title: Scalars["String"]
@@ -1081,6 +1512,11 @@ export type MutationRemoveCollectionChapterArgs = {
   chapterId: Scalars["UUID"]
 }
 
+export type MutationRemoveMorphemeRelationArgs = {
+  from: Scalars["String"]
+  to: Scalars["String"]
+}
+
 export type MutationToggleCollectionVisibilityArgs = {
   collectionId: Scalars["UUID"]
 }
@@ -1171,6 +1607,39 @@ export type PageImage = {
   readonly url: Scalars["String"]
 }
 
+/** Information about pagination in a connection */
+export type PageInfo = {
+  readonly __typename?: "PageInfo"
+  /** When paginating forwards, the cursor to continue. */
+  readonly endCursor: Maybe<Scalars["String"]>
+  /** When paginating forwards, are there more items? */
+  readonly hasNextPage: Scalars["Boolean"]
+  /** When paginating backwards, are there more items? */
+  readonly hasPreviousPage: Scalars["Boolean"]
+  /** When paginating backwards, the cursor to continue. */
+  readonly startCursor: Maybe<Scalars["String"]>
+}
+
+/** All attested forms of a verb in one person, number, and aspect. */
+export type ParadigmCell = {
+  readonly __typename?: "ParadigmCell"
+  /** Verb form of this column */
+  readonly aspect: VerbAspect
+  /** Attested forms, which may come from several documents */
+  readonly forms: ReadonlyArray<AnnotatedForm>
+  /** True when no form is attested for this cell */
+  readonly isGap: Scalars["Boolean"]
+}
+
+/** One row of a conjugation table. */
+export type ParadigmRow = {
+  readonly __typename?: "ParadigmRow"
+  /** One cell for each aspect, in the order of [`VerbParadigm::aspects`] */
+  readonly cells: ReadonlyArray<ParadigmCell>
+  /** Person and number of every form in this row */
+  readonly personNumber: PersonNumber
+}
+
 /** A paragraph in an annotated document that can be edited. */
 export type ParagraphUpdate = {
   /** Unique identifier of the form */
@@ -1179,6 +1648,37 @@ export type ParagraphUpdate = {
   readonly translation: InputMaybe<Scalars["String"]>
 }
 
+/** Part of speech that a dictionary headword is listed under. */
+export enum PartOfSpeech {
+  Adjective = "ADJECTIVE",
+  Noun = "NOUN",
+  Numeral = "NUMERAL",
+  Particle = "PARTICLE",
+  Verb = "VERB",
+}
+
+/** Grammatical person marked by a pronominal prefix. */
+export enum Person {
+  First = "FIRST",
+  Second = "SECOND",
+  Third = "THIRD",
+}
+
+/**
+ * Which person and number a verb form is inflected for. Set A and set B
+ * prefixes of the same person and number share a row, since the choice
+ * between them depends on the aspect.
+ */
+export type PersonNumber = {
+  readonly __typename?: "PersonNumber"
+  /** Whether the addressee is included, only for first person dual and plural */
+  readonly clusivity: Maybe<Clusivity>
+  /** Grammatical number */
+  readonly number: GrammaticalNumber
+  /** Grammatical person */
+  readonly person: Person
+}
+
 /** The reference position within a document of one specific form */
 export type PositionInDocument = {
   readonly __typename?: "PositionInDocument"
@@ -1225,6 +1725,21 @@ export type PostCommentInput = {
 export type Query = {
   readonly __typename?: "Query"
   readonly abbreviationIdFromShortName: Scalars["UUID"]
+  /**
+   * Find words with a small query language, like
+   * `gloss:catch doc:DF1975 year:1800..1850 has:audio -unresolved`.
+   * Terms are separated by spaces and all must match. Plain text matches
+   * any of the syllabary, phonetics, gloss, or commentary, while the
+   * fields `source`, `phonetics`, `english`, `commentary`, `gloss`,
+   * `morpheme`, `doc`, `collection`, `genre`, `keyword`, `year`, and `has`
+   * (one of `audio`, `commentary`, `english`, or `segments`) each check one
+   * thing. The bare keyword `unresolved` matches words with a "?" in a
+   * morpheme or gloss, or in the source when there are no segments.
+   * A leading "-" excludes words matching a term.
+   * Syntax errors carry the `offset`, `length`, and `token` of the part of
+   * the query that couldn't be read.
+   */
+  readonly advancedSearch: ReadonlyArray<AnnotatedForm>
   /** Returns a chapter slug info for all unassigned chapters in a given chatper */
   readonly allChapterSlugs: ReadonlyArray<ChapterSlugInfo>
   /** List of all the document collections available. */
@@ -1236,13 +1751,31 @@ export type Query = {
   readonly allPages: ReadonlyArray<Page>
   /** Fetch all available subject headings. */
   readonly allSubjectHeadings: ReadonlyArray<SubjectHeading>
-  /** List of all the functional morpheme tags available */
+  /**
+   * List of all the functional morpheme tags available in the language of
+   * the given document, or in Cherokee without one
+   */
   readonly allTags: ReadonlyArray<MorphemeTag>
+  /**
+   * Clusters the shapes of a morpheme with the given gloss into likely
+   * allomorphs, linking shapes that are at most `max_distance` consonant
+   * clusters or vowels apart. Each cluster lists its variants and the
+   * morphemes that most often come right before and after it.
+   */
+  readonly allomorphs: ReadonlyArray<AllomorphCluster>
   /** Retrieves all documents that are bookmarked by the current user. */
   readonly bookmarkedDocuments: ReadonlyArray<AnnotatedDoc>
   /** Retrieves a chapter and its contents by its collection and chapter slug. */
   readonly chapter: Maybe<CollectionChapter>
   readonly collection: DocumentCollection
+  /**
+   * Lists every use of a word or morpheme in the paragraphs of documents
+   * with up to `contextSize` words on either side from the same paragraph,
+   * also known as keyword in context (KWIC). Words are matched by the gloss
+   * of a morpheme, by how they sound, or by a piece of their syllabary.
+   * Lines are sorted before skipping `offset` of them.
+   */
+  readonly concordance: ReadonlyArray<ConcordanceLine>
   /** Gets a dailp_user by their id */
   readonly dailpUserById: User
   /** Retrieves a full document from its unique name. */
@@ -1250,13 +1783,55 @@ export type Query = {
   /** Retrieves a full document from its unique identifier. */
   readonly documentByUuid: Maybe<AnnotatedDoc>
   readonly editedCollection: Maybe<EditedCollection>
+  /**
+   * Morpheme glosses linked to the given one, like "DF1975:catch", within
+   * `depth` links, at most six, and the typed links between them. Nodes
+   * are ordered by the date of their document, oldest first. Without a
+   * document name, starts from the gloss in every document.
+   */
+  readonly etymologyGraph: EtymologyGraph
+  /**
+   * Functional morphemes that never appear in the same word as the one with
+   * the given internal gloss, ignoring those attested in fewer than
+   * `min_word_count` words. Fails if no word has the given gloss, which is
+   * most likely misspelled.
+   */
+  readonly exclusiveMorphemes: ReadonlyArray<MorphemeExclusion>
+  /**
+   * Check a morphemic segmentation and its glosses without saving them,
+   * so that editors can fix unknown tags and mismatched segments first.
+   * Both layers are written in the given system, and tags are checked
+   * against those of the given document's language.
+   */
+  readonly glossWarnings: ReadonlyArray<GlossWarning>
+  /**
+   * Browse dictionary entries in alphabetical order of their headwords.
+   * Optionally only lists those in the dictionary with the given short
+   * name, like "DF1975", with the given part of speech, or with headwords
+   * starting with the given text.
+   */
+  readonly lexicalEntries: ReadonlyArray<LexicalEntry>
+  /** The dictionary entry with the given ID, like "DF1975:catch" */
+  readonly lexicalEntry: Maybe<LexicalEntry>
   /** Gets all dailp_user with their id, username, and role for now */
   readonly listUsers: ReadonlyArray<User>
   readonly menuBySlug: Menu
+  /**
+   * Other morphemes that appear in the same words as the one with the given
+   * internal gloss, most common first. For example, the prefixes that
+   * appear with a root are those before it with the affix class.
+   */
+  readonly morphemeCooccurrences: ReadonlyArray<MorphemeCooccurrence>
+  /**
+   * How often the morpheme with the given internal gloss appears in each
+   * document or decade, compared to all words in that group.
+   */
+  readonly morphemeDistribution: ReadonlyArray<MorphemeDistributionBucket>
   /**
    * Retrieve information for the morpheme that corresponds to the given tag
    * string. For example, "3PL.B" is the standard string referring to a 3rd
-   * person plural prefix.
+   * person plural prefix. Tags are looked up in the language of the given
+   * document, or in Cherokee without one.
    */
   readonly morphemeTag: Maybe<MorphemeTag>
   /** Forms containing the given morpheme gloss or related ones clustered over time. */
@@ -1271,39 +1846,99 @@ export type Query = {
    * Groups these words by the phonemic shape of the target morpheme.
    */
   readonly morphemesByShape: ReadonlyArray<MorphemeReference>
+  /**
+   * All registered writing systems, optionally only those for one language.
+   * Their identifiers can be passed as the `orthography` argument of
+   * `AnnotatedForm.romanizedSource` and `AnnotatedForm.segments`.
+   */
+  readonly orthographies: ReadonlyArray<RegisteredOrthography>
   /** Retrieves a full document from its unique identifier. */
   readonly page: Maybe<Page>
   readonly pageByPath: Maybe<Page>
   /** Get a single paragraph given the paragraph ID */
   readonly paragraphById: DocumentParagraph
   /**
-   * Search for words with the exact same syllabary string, or with very
-   * similar looking characters.
+   * Ranked full-text search over the syllabary, phonetics, gloss, and
+   * commentary of every word, and the translation of every paragraph.
+   * Supports quoted phrases, "or", and "-" to exclude a word. Pass the
+   * cursor of the last result as `after` to get the next page.
+   */
+  readonly search: SearchHitConnection
+  /**
+   * Find Cherokee forms from English, like "catch" or "caught a fish".
+   * Searches the senses of dictionary entries, the glosses of morphemes,
+   * and the translations of words and paragraphs, matching any form of
+   * each English word. Dictionary entries come first.
+   */
+  readonly searchByEnglish: ReadonlyArray<EnglishMatch>
+  /**
+   * Search dictionary entries by headword, gloss, shape, or English
+   * translation. Exact matches of the headword or gloss come first.
+   */
+  readonly searchLexicalEntries: ReadonlyArray<LexicalEntry>
+  /**
+   * Suggest morphemic segmentations for a word that hasn't been analyzed
+   * yet, based on words and morpheme shapes that have been. Suggestions
+   * copied from words that sound or look the same come first.
+   * Morphemes are written in the given system, which is also what the
+   * romanized source should be written in. Without a romanized source,
+   * the source is read as syllabary.
+   */
+  readonly suggestSegmentations: ReadonlyArray<SegmentationSuggestion>
+  /**
+   * Search for words containing the given syllabary string, allowing for
+   * similar looking characters and a few missing or extra characters.
+   * Results are ranked with the closest matches first, and include words
+   * spelled like at most `limit` of the closest matches.
    */
   readonly syllabarySearch: ReadonlyArray<AnnotatedForm>
   /** Basic information about the currently authenticated user, if any. */
   readonly userInfo: Maybe<UserInfo>
+  /**
+   * Conjugation table of a verb, with attested forms arranged by person,
+   * number, and aspect. Takes either a root gloss like "catch", which
+   * gathers forms from every document, or a lexical entry like
+   * "DF1975:catch", which only uses the forms listed under that entry.
+   */
+  readonly verbParadigm: VerbParadigm
   /** Get a single word given the word ID */
   readonly wordById: AnnotatedForm
   /**
    * Search for words that match any one of the given queries.
-   * Each query may match against multiple fields of a word.
+   * Each query may match against multiple fields of a word, or only against
+   * how the word sounds with `mode: PHONETIC`.
+   * Use `search` instead for ranked results a page at a time.
    */
   readonly wordSearch: ReadonlyArray<AnnotatedForm>
 }
 
 export type QueryAbbreviationIdFromShortNameArgs = {
+  documentId: InputMaybe<Scalars["UUID"]>
   shortName: Scalars["String"]
 }
 
+export type QueryAdvancedSearchArgs = {
+  limit?: Scalars["Int"]
+  offset?: Scalars["Int"]
+  query: Scalars["String"]
+}
+
 export type QueryAllChapterSlugsArgs = {
   collectionSlug: Scalars["String"]
 }
 
 export type QueryAllTagsArgs = {
+  documentId: InputMaybe<Scalars["UUID"]>
   system: CherokeeOrthography
 }
 
+export type QueryAllomorphsArgs = {
+  compareBy: InputMaybe<CherokeeOrthography>
+  exampleCount?: Scalars["Int"]
+  gloss: Scalars["String"]
+  maxDistance?: Scalars["Int"]
+}
+
 export type QueryChapterArgs = {
   chapterSlug: Scalars["String"]
   collectionSlug: Scalars["String"]
@@ -1313,6 +1948,14 @@ export type QueryCollectionArgs = {
   slug: Scalars["String"]
 }
 
+export type QueryConcordanceArgs = {
+  contextSize?: Scalars["Int"]
+  limit?: Scalars["Int"]
+  offset?: Scalars["Int"]
+  search: ConcordanceQuery
+  sortBy?: ConcordanceSort
+}
+
 export type QueryDailpUserByIdArgs = {
   id: Scalars["UUID"]
 }
@@ -1329,11 +1972,55 @@ export type QueryEditedCollectionArgs = {
   slug: Scalars["String"]
 }
 
+export type QueryEtymologyGraphArgs = {
+  depth?: Scalars["Int"]
+  morpheme: Scalars["String"]
+}
+
+export type QueryExclusiveMorphemesArgs = {
+  exampleCount?: Scalars["Int"]
+  gloss: Scalars["String"]
+  minWordCount?: Scalars["Int"]
+}
+
+export type QueryGlossWarningsArgs = {
+  documentId: InputMaybe<Scalars["UUID"]>
+  glosses: Scalars["String"]
+  morphemes: Scalars["String"]
+  system?: Scalars["OrthographyId"]
+}
+
+export type QueryLexicalEntriesArgs = {
+  dictionary: InputMaybe<Scalars["String"]>
+  limit?: Scalars["Int"]
+  offset?: Scalars["Int"]
+  partOfSpeech: InputMaybe<PartOfSpeech>
+  startsWith: InputMaybe<Scalars["String"]>
+}
+
+export type QueryLexicalEntryArgs = {
+  id: Scalars["String"]
+}
+
 export type QueryMenuBySlugArgs = {
   slug: Scalars["String"]
 }
 
+export type QueryMorphemeCooccurrencesArgs = {
+  class: InputMaybe<WordSegmentClass>
+  exampleCount?: Scalars["Int"]
+  gloss: Scalars["String"]
+  side?: MorphemeSide
+}
+
+export type QueryMorphemeDistributionArgs = {
+  exampleCount?: Scalars["Int"]
+  gloss: Scalars["String"]
+  groupBy?: DistributionGrouping
+}
+
 export type QueryMorphemeTagArgs = {
+  documentId: InputMaybe<Scalars["UUID"]>
   id: Scalars["String"]
   system: CherokeeOrthography
 }
@@ -1353,6 +2040,10 @@ export type QueryMorphemesByShapeArgs = {
   gloss: Scalars["String"]
 }
 
+export type QueryOrthographiesArgs = {
+  language: InputMaybe<Scalars["String"]>
+}
+
 export type QueryPageArgs = {
   id: Scalars["String"]
 }
@@ -1365,18 +2056,140 @@ export type QueryParagraphByIdArgs = {
   id: Scalars["UUID"]
 }
 
+export type QuerySearchArgs = {
+  after: InputMaybe<Scalars["String"]>
+  first?: Scalars["Int"]
+  query: Scalars["String"]
+}
+
+export type QuerySearchByEnglishArgs = {
+  limit?: Scalars["Int"]
+  query: Scalars["String"]
+}
+
+export type QuerySearchLexicalEntriesArgs = {
+  limit?: Scalars["Int"]
+  query: Scalars["String"]
+}
+
+export type QuerySuggestSegmentationsArgs = {
+  limit?: Scalars["Int"]
+  romanizedSource: InputMaybe<Scalars["String"]>
+  source: Scalars["String"]
+  system?: Scalars["OrthographyId"]
+}
+
 export type QuerySyllabarySearchArgs = {
+  limit?: Scalars["Int"]
   query: Scalars["String"]
 }
 
+export type QueryVerbParadigmArgs = {
+  root: Scalars["String"]
+}
+
 export type QueryWordByIdArgs = {
   id: Scalars["UUID"]
 }
 
 export type QueryWordSearchArgs = {
+  mode?: WordSearchMode
   query: Scalars["String"]
 }
 
+/** A writing system listed in the [`OrthographyRegistry`]. */
+export type RegisteredOrthography = {
+  readonly __typename?: "RegisteredOrthography"
+  /** Identifier to pass as the `system` argument of other fields */
+  readonly id: Scalars["String"]
+  /** Name of the language this system is for */
+  readonly language: Scalars["String"]
+  /** Human-readable name of the system */
+  readonly title: Scalars["String"]
+}
+
+/** Part of a search result that can match a query. */
+export enum SearchField {
+  Commentary = "COMMENTARY",
+  EnglishGloss = "ENGLISH_GLOSS",
+  SimplePhonetics = "SIMPLE_PHONETICS",
+  Source = "SOURCE",
+  Translation = "TRANSLATION",
+}
+
+/**
+ * The text of one field of a search result, split into runs that did and
+ * didn't match the search.
+ */
+export type SearchHighlight = {
+  readonly __typename?: "SearchHighlight"
+  /** Field that matched */
+  readonly field: SearchField
+  /**
+   * Runs of text in reading order. Long fields may be cut down to the
+   * parts around each match.
+   */
+  readonly fragments: ReadonlyArray<HighlightFragment>
+}
+
+/** A word or paragraph that matched a full-text search. */
+export type SearchHit = {
+  readonly __typename?: "SearchHit"
+  /** Document that this result is in */
+  readonly document: Maybe<AnnotatedDoc>
+  /** Fields of this result that matched the search */
+  readonly highlights: ReadonlyArray<SearchHighlight>
+  /** Paragraph whose translation matched, unless this result is a word */
+  readonly paragraph: Maybe<DocumentParagraph>
+  /**
+   * How well this result matches the search, where higher is better.
+   * Matches in Cherokee text count more than matches in translations.
+   */
+  readonly score: Scalars["Float"]
+  /** Matching word, unless this result is a paragraph */
+  readonly word: Maybe<AnnotatedForm>
+}
+
+export type SearchHitConnection = {
+  readonly __typename?: "SearchHitConnection"
+  /** A list of edges. */
+  readonly edges: ReadonlyArray<SearchHitEdge>
+  /** A list of nodes. */
+  readonly nodes: ReadonlyArray<SearchHit>
+  /** Information to aid in pagination. */
+  readonly pageInfo: PageInfo
+}
+
+/** An edge in a connection. */
+export type SearchHitEdge = {
+  readonly __typename?: "SearchHitEdge"
+  /** A cursor for use in pagination */
+  readonly cursor: Scalars["String"]
+  /** The item at the end of the edge */
+  readonly node: SearchHit
+}
+
+/** A possible morphemic segmentation for a word that hasn't been analyzed. */
+export type SegmentationSuggestion = {
+  readonly __typename?: "SegmentationSuggestion"
+  /** Where this suggestion came from */
+  readonly kind: SuggestionKind
+  /**
+   * Analyzed words that have exactly this segmentation, for suggestions
+   * copied from the same word
+   */
+  readonly matchingWordIds: ReadonlyArray<Scalars["UUID"]>
+  /**
+   * How likely this suggestion is compared to others of the same kind,
+   * from 0 to 1. For the same word, this is the share of matching words
+   * analyzed this way. Otherwise, it depends on how common each morpheme
+   * is.
+   */
+  readonly score: Scalars["Float"]
+  /** Proposed segments in order */
+  readonly segments: ReadonlyArray<SuggestedSegment>
+}
+
 /**
  * Attribution for a particular source, whether an institution or an individual.
  * Most commonly, this will represent the details of a library or archive that
@@ -1408,6 +2221,32 @@ export type SpatialCoverageUpdate = {
   readonly name: Scalars["String"]
 }
 
+/**
+ * Spelling choices that replace those of a romanized orthography, so that
+ * readers can match the conventions of their own community, like ts for j or
+ * qu for gw. Choices that are left out keep the spelling of the orthography.
+ */
+export type SpellingOptions = {
+  /** How to spell the affricate /c/ */
+  readonly affricate: InputMaybe<AffricateSpelling>
+  /** How to write the glottal stop */
+  readonly glottalStop: InputMaybe<GlottalStopSpelling>
+  /** How to spell the labiovelar stops /kw/ and /kwh/ */
+  readonly labiovelar: InputMaybe<LabiovelarSpelling>
+  /** How to distinguish plain stops from aspirated ones */
+  readonly stops: InputMaybe<StopSpelling>
+  /** Which tone diacritics to write on vowels */
+  readonly tone: InputMaybe<ToneMarking>
+  /** Whether long vowels are written differently from short ones */
+  readonly vowelLength: InputMaybe<VowelLength>
+}
+
+/** How to distinguish plain stops from aspirated ones. */
+export enum StopSpelling {
+  Dt = "DT",
+  Tth = "TTH",
+}
+
 /**
  * Record to store a subject heading that reflects Indigenous knowledge
  * practices associated with a document
@@ -1429,6 +2268,78 @@ export type SubjectHeadingUpdate = {
   readonly name: Scalars["String"]
 }
 
+/**
+ * One segment of a suggested segmentation, along with the analyzed data
+ * that supports it.
+ */
+export type SuggestedSegment = {
+  readonly __typename?: "SuggestedSegment"
+  /** Number of analyzed segments with this shape and gloss */
+  readonly attestations: Scalars["Int"]
+  /** A few analyzed words containing this shape and gloss */
+  readonly exampleWordIds: ReadonlyArray<Scalars["UUID"]>
+  /** Suggested gloss, or "?" if no known morpheme fits */
+  readonly gloss: Scalars["String"]
+  /** Source language representation of this segment */
+  readonly morpheme: Scalars["String"]
+  /** Which separator should precede this segment */
+  readonly role: WordSegmentRole
+}
+
+/** Where a suggested segmentation came from. */
+export enum SuggestionKind {
+  KnownMorphemes = "KNOWN_MORPHEMES",
+  SameWord = "SAME_WORD",
+}
+
+/**
+ * One syllable of a phonetic string, which the syllabary writes with a single
+ * character.
+ */
+export type Syllable = {
+  readonly __typename?: "Syllable"
+  /**
+   * Phonemic spelling of this syllable in DAILP internal orthography,
+   * including any consonants or morpheme separators that the syllabary
+   * doesn't write.
+   */
+  readonly phonemic: Scalars["String"]
+  /**
+   * Index of the first character of this syllable in the whole phonemic
+   * string.
+   */
+  readonly start: Scalars["Int"]
+  /**
+   * The syllabary character that spells this syllable, or nothing for
+   * consonants the syllabary can't write.
+   */
+  readonly syllabary: Scalars["String"]
+}
+
+/** Pairing of a phonemic syllable with a character of the source syllabary. */
+export type SyllableAlignment = {
+  readonly __typename?: "SyllableAlignment"
+  /** Whether the source character is the one the phonemic layer calls for. */
+  readonly isMatch: Scalars["Boolean"]
+  /**
+   * Character of the source syllabary, missing if the source lacks a
+   * character for this syllable.
+   */
+  readonly source: Maybe<Scalars["String"]>
+  /**
+   * Syllable from the phonemic layer, missing if the source has an extra
+   * character here.
+   */
+  readonly syllable: Maybe<Syllable>
+}
+
+/** Which tone diacritics to write on vowels. */
+export enum ToneMarking {
+  Crg = "CRG",
+  Taoc = "TAOC",
+  Unmarked = "UNMARKED",
+}
+
 /** Input for bulk updating collection chapter order */
 export type UpdateCollectionChapterOrderInput = {
   /** Ordered list of chapters with their new indices */
@@ -1520,8 +2431,57 @@ export type UserUpdate = {
   readonly role: InputMaybe<UserGroup>
 }
 
+/**
+ * One of the standard verb forms, determined by the aspect and final
+ * suffixes of the verb.
+ */
+export enum VerbAspect {
+  ExperiencedPast = "EXPERIENCED_PAST",
+  Future = "FUTURE",
+  Habitual = "HABITUAL",
+  Immediate = "IMMEDIATE",
+  Infinitive = "INFINITIVE",
+  Present = "PRESENT",
+  ReportedPast = "REPORTED_PAST",
+}
+
+/**
+ * Conjugation table of a verb, built from the forms attested in our
+ * documents and dictionaries.
+ */
+export type VerbParadigm = {
+  readonly __typename?: "VerbParadigm"
+  /** Column headers of the table, in order */
+  readonly aspects: ReadonlyArray<VerbAspect>
+  /** Number of cells with no attested form */
+  readonly gapCount: Scalars["Int"]
+  /** Gloss of the verb root */
+  readonly rootGloss: Scalars["String"]
+  /** One row for each person and number, in standard order */
+  readonly rows: ReadonlyArray<ParadigmRow>
+  /**
+   * Forms containing the root which don't fit in any cell, like those with
+   * both a subject and object prefix
+   */
+  readonly unclassifiedForms: ReadonlyArray<AnnotatedForm>
+}
+
+/** Whether long vowels are written differently from short ones. */
+export enum VowelLength {
+  Doubled = "DOUBLED",
+  Single = "SINGLE",
+}
+
+/** How the query of a word search is matched against words. */
+export enum WordSearchMode {
+  AnyField = "ANY_FIELD",
+  Phonetic = "PHONETIC",
+}
+
 export type WordSegment = {
   readonly __typename?: "WordSegment"
+  /** Whether this segment is a root, affix, or clitic, if known */
+  readonly class: Maybe<WordSegmentClass>
   /** English gloss in standard DAILP format that refers to a lexical item */
   readonly gloss: Scalars["String"]
   /**
@@ -1540,6 +2500,16 @@ export type WordSegment = {
   readonly role: WordSegmentRole
 }
 
+/**
+ * The kind of unit of meaning that a segment is, regardless of the character
+ * that separates it from the others.
+ */
+export enum WordSegmentClass {
+  Affix = "AFFIX",
+  Clitic = "CLITIC",
+  Root = "ROOT",
+}
+
 /**
  * The kind of segment that a particular sequence of characters in a morphemic
  * segmentations represent.
@@ -1738,7 +2708,7 @@ export type AnnotatedDocumentQuery = { readonly __typename?: "Query" } & {
 
 export type DocumentContentsQueryVariables = Exact<{
   slug: Scalars["String"]
-  morphemeSystem: CherokeeOrthography
+  morphemeSystem: Scalars["OrthographyId"]
   isReference: Scalars["Boolean"]
 }>
 
@@ -2589,7 +3559,7 @@ export type DocSliceQueryVariables = Exact<{
   slug: Scalars["String"]
   start: Scalars["Int"]
   end: InputMaybe<Scalars["Int"]>
-  morphemeSystem: CherokeeOrthography
+  morphemeSystem: Scalars["OrthographyId"]
 }>
 
 export type DocSliceQuery = { readonly __typename?: "Query" } & {
@@ -2985,7 +3955,7 @@ export type ParagraphCommentsQuery = { readonly __typename?: "Query" } & {
 
 export type UpdateWordMutationVariables = Exact<{
   word: AnnotatedFormUpdate
-  morphemeSystem: CherokeeOrthography
+  morphemeSystem: Scalars["OrthographyId"]
 }>
 
 export type UpdateWordMutation = { readonly __typename?: "Mutation" } & {
@@ -3931,7 +4901,7 @@ export function useAnnotatedDocumentQuery(
 export const DocumentContentsDocument = gql`
   query DocumentContents(
     $slug: String!
-    $morphemeSystem: CherokeeOrthography!
+    $morphemeSystem: OrthographyId!
     $isReference: Boolean!
   ) {
     document(slug: $slug) {
@@ -4369,7 +5339,7 @@ export const DocSliceDocument = gql`
     $slug: String!
     $start: Int!
     $end: Int
-    $morphemeSystem: CherokeeOrthography!
+    $morphemeSystem: OrthographyId!
   ) {
     document(slug: $slug) {
       title
@@ -4483,7 +5453,7 @@ export function useParagraphCommentsQuery(
 export const UpdateWordDocument = gql`
   mutation UpdateWord(
     $word: AnnotatedFormUpdate!
-    $morphemeSystem: CherokeeOrthography!
+    $morphemeSystem: OrthographyId!
   ) {
     updateWord(word: $word) {
       ...FormFields